@@ -1,25 +1,603 @@
-use crate::models::Employee;
-use csv::{ReaderBuilder, WriterBuilder};
+use crate::mapping::{ColumnTarget, MappingProfile};
+use crate::models::{Employee, Skill, MAX_PROFICIENCY, MIN_PROFICIENCY};
+use crate::notes::note_file_name;
+use crate::retention::{AttritionRisk, ImpactOfLoss};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::io::{Read, Write};
 
-/// Imports employees from a CSV data source.
+/// Placeholder used in per-cycle header templates, e.g. `"PR{cycle}"`.
+pub const CYCLE_PLACEHOLDER: &str = "{cycle}";
+
+/// Errors that can occur while importing employees from CSV.
+#[derive(Debug, thiserror::Error)]
+pub enum CsvImportError {
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error("missing required column '{0}'")]
+    MissingColumn(String),
+    #[error("line {line}, column '{column}': invalid value '{value}': {reason}")]
+    InvalidValue {
+        line: u64,
+        column: String,
+        value: String,
+        reason: String,
+    },
+}
+
+/// A fixed (cycle-independent) employee column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum EmployeeField {
+    UserId,
+    FirstName,
+    LastName,
+    CurrentPosition,
+    CurrentTempPosition,
+    Notes,
+    CurrentLabel,
+    Email,
+    ManagerId,
+    Department,
+    Location,
+    HireDate,
+    Skills,
+    AttritionRisk,
+    ImpactOfLoss,
+}
+
+/// Separator between skill names in the `Skills` column.
+pub const SKILLS_SEPARATOR: char = ';';
+/// Separator between a skill name and its proficiency in the `Skills` column, e.g. "Rust:4".
+pub const SKILL_LEVEL_SEPARATOR: char = ':';
+/// Skills matrix cell for a skill held without a recorded proficiency.
+pub const SKILL_HELD_MARKER: &str = "x";
+
+impl EmployeeField {
+    pub const ALL: [EmployeeField; 15] = [
+        EmployeeField::UserId,
+        EmployeeField::FirstName,
+        EmployeeField::LastName,
+        EmployeeField::CurrentPosition,
+        EmployeeField::CurrentTempPosition,
+        EmployeeField::Notes,
+        EmployeeField::CurrentLabel,
+        EmployeeField::Email,
+        EmployeeField::ManagerId,
+        EmployeeField::Department,
+        EmployeeField::Location,
+        EmployeeField::HireDate,
+        EmployeeField::Skills,
+        EmployeeField::AttritionRisk,
+        EmployeeField::ImpactOfLoss,
+    ];
+
+    /// The header this field is read from and written to by default.
+    pub fn default_header(self) -> &'static str {
+        match self {
+            EmployeeField::UserId => "User ID",
+            EmployeeField::FirstName => "First Name",
+            EmployeeField::LastName => "Last Name",
+            EmployeeField::CurrentPosition => "Current Position",
+            EmployeeField::CurrentTempPosition => "Current Temp Position",
+            EmployeeField::Notes => "Notes",
+            EmployeeField::CurrentLabel => "Current Label",
+            EmployeeField::Email => "Email",
+            EmployeeField::ManagerId => "Manager ID",
+            EmployeeField::Department => "Department",
+            EmployeeField::Location => "Location",
+            EmployeeField::HireDate => "Hire Date",
+            EmployeeField::Skills => "Skills",
+            EmployeeField::AttritionRisk => "Attrition Risk",
+            EmployeeField::ImpactOfLoss => "Impact of Loss",
+        }
+    }
+
+    /// Required fields must have a column in every imported file.
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            EmployeeField::UserId
+                | EmployeeField::FirstName
+                | EmployeeField::LastName
+                | EmployeeField::CurrentPosition
+        )
+    }
+
+    /// Reads this field from `employee` as it is written to CSV; empty optional fields yield `None`.
+    pub fn value(self, employee: &Employee) -> Option<String> {
+        let text = match self {
+            EmployeeField::UserId => Some(&employee.user_id),
+            EmployeeField::FirstName => Some(&employee.first_name),
+            EmployeeField::LastName => Some(&employee.last_name),
+            EmployeeField::CurrentPosition => Some(&employee.current_position),
+            EmployeeField::CurrentTempPosition => employee.current_temp_position.as_ref(),
+            EmployeeField::Notes => employee.notes.as_ref(),
+            EmployeeField::CurrentLabel => employee.current_label.as_ref(),
+            EmployeeField::Email => employee.email.as_ref(),
+            EmployeeField::ManagerId => employee.manager_id.as_ref(),
+            EmployeeField::Department => employee.department.as_ref(),
+            EmployeeField::Location => employee.location.as_ref(),
+            EmployeeField::HireDate => employee.hire_date.as_ref(),
+            EmployeeField::Skills => {
+                let skills: Vec<String> = employee
+                    .skills
+                    .iter()
+                    .map(|skill| match employee.skill_level(skill) {
+                        Some(level) => format!("{}{}{}", skill, SKILL_LEVEL_SEPARATOR, level),
+                        None => skill.clone(),
+                    })
+                    .collect();
+                return (!skills.is_empty()).then(|| skills.join(&format!("{} ", SKILLS_SEPARATOR)));
+            }
+            EmployeeField::AttritionRisk => return employee.attrition_risk.map(|risk| risk.label().to_string()),
+            EmployeeField::ImpactOfLoss => return employee.impact_of_loss.map(|impact| impact.label().to_string()),
+        };
+        text.cloned()
+    }
+
+    /// Writes `value` into this field of `employee`; `None` clears optional fields
+    /// and empties required ones. Fails, leaving the field unchanged, if `value` is not
+    /// a valid value for a typed field.
+    pub fn set_value(self, employee: &mut Employee, value: Option<String>) -> Result<(), String> {
+        match self {
+            EmployeeField::UserId => employee.user_id = value.unwrap_or_default(),
+            EmployeeField::FirstName => employee.first_name = value.unwrap_or_default(),
+            EmployeeField::LastName => employee.last_name = value.unwrap_or_default(),
+            EmployeeField::CurrentPosition => employee.current_position = value.unwrap_or_default(),
+            EmployeeField::CurrentTempPosition => employee.current_temp_position = value,
+            EmployeeField::Notes => employee.notes = value,
+            EmployeeField::CurrentLabel => employee.current_label = value,
+            EmployeeField::Email => employee.email = value,
+            EmployeeField::ManagerId => employee.manager_id = value,
+            EmployeeField::Department => employee.department = value,
+            EmployeeField::Location => employee.location = value,
+            EmployeeField::HireDate => employee.hire_date = value,
+            EmployeeField::Skills => {
+                employee.skills.clear();
+                employee.skill_levels.clear();
+                for entry in value.iter().flat_map(|value| value.split(SKILLS_SEPARATOR)) {
+                    match parse_skill_entry(entry) {
+                        (name, Some(level)) => employee.set_skill_level(name, Some(level)),
+                        (name, None) => employee.add_skill(name),
+                    };
+                }
+            }
+            EmployeeField::AttritionRisk => {
+                employee.attrition_risk = parse_typed(value, AttritionRisk::parse, "attrition risk")?;
+            }
+            EmployeeField::ImpactOfLoss => {
+                employee.impact_of_loss = parse_typed(value, ImpactOfLoss::parse, "impact of loss")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parses an optional typed value, naming `what` in the error for values that do not parse.
+fn parse_typed<T>(value: Option<String>, parse: fn(&str) -> Option<T>, what: &str) -> Result<Option<T>, String> {
+    match value {
+        Some(value) => parse(&value).map(Some).ok_or_else(|| format!("unknown {} '{}'", what, value.trim())),
+        None => Ok(None),
+    }
+}
+
+// Splits "Rust:4" into the skill name and its level. Entries whose suffix is not a valid
+// level (e.g. "C++: beginner") are taken as a plain skill name.
+fn parse_skill_entry(entry: &str) -> (&str, Option<u8>) {
+    if let Some((name, level)) = entry.rsplit_once(SKILL_LEVEL_SEPARATOR) {
+        if let Ok(level) = level.trim().parse::<u8>() {
+            if (MIN_PROFICIENCY..=MAX_PROFICIENCY).contains(&level) {
+                return (name, Some(level));
+            }
+        }
+    }
+    (entry, None)
+}
+
+/// A per-cycle employee column; one such column exists for every review cycle in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CycleField {
+    Rating,
+    NineBox,
+    Group,
+}
+
+impl CycleField {
+    pub const ALL: [CycleField; 3] = [CycleField::Group, CycleField::NineBox, CycleField::Rating];
+
+    /// The default header template, where `{cycle}` stands for the cycle name.
+    pub fn default_header_template(self) -> &'static str {
+        match self {
+            CycleField::Rating => "PR{cycle}",
+            CycleField::NineBox => "User 9Box {cycle}",
+            CycleField::Group => "PR Group {cycle}",
+        }
+    }
+
+    /// A short human-readable name for the field.
+    pub fn label(self) -> &'static str {
+        match self {
+            CycleField::Rating => "Rating",
+            CycleField::NineBox => "9-Box",
+            CycleField::Group => "PR Group",
+        }
+    }
+}
+
+/// Extracts the cycle name from `header` if it matches `template`, ignoring ASCII case.
+/// Cycle names must start with a digit (e.g. "2026" or "2026H1").
+///
+/// # Example
+/// `match_cycle_header("PR{cycle}", "PR2026")` returns `Some("2026")`.
+pub fn match_cycle_header(template: &str, header: &str) -> Option<String> {
+    let (prefix, suffix) = template.split_once(CYCLE_PLACEHOLDER)?;
+    if header.len() < prefix.len() + suffix.len() {
+        return None;
+    }
+    let (head, rest) = (header.get(..prefix.len())?, header.get(prefix.len()..)?);
+    let split_at = rest.len() - suffix.len();
+    let (cycle, tail) = (rest.get(..split_at)?, rest.get(split_at..)?);
+    if !head.eq_ignore_ascii_case(prefix) || !tail.eq_ignore_ascii_case(suffix) {
+        return None;
+    }
+    if cycle.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(cycle.to_string())
+    } else {
+        None
+    }
+}
+
+/// Builds the header for `cycle` from a template such as `"User 9Box {cycle}"`.
+pub fn cycle_header(template: &str, cycle: &str) -> String {
+    template.replace(CYCLE_PLACEHOLDER, cycle)
+}
+
+// Column groups in the order they are written on export.
+#[derive(Clone, Copy)]
+enum ExportColumn {
+    Field(EmployeeField),
+    Cycles(CycleField),
+}
+
+const EXPORT_LAYOUT: [ExportColumn; 18] = [
+    ExportColumn::Field(EmployeeField::UserId),
+    ExportColumn::Cycles(CycleField::Group),
+    ExportColumn::Field(EmployeeField::FirstName),
+    ExportColumn::Field(EmployeeField::LastName),
+    ExportColumn::Field(EmployeeField::CurrentPosition),
+    ExportColumn::Field(EmployeeField::CurrentTempPosition),
+    ExportColumn::Cycles(CycleField::Rating),
+    ExportColumn::Cycles(CycleField::NineBox),
+    ExportColumn::Field(EmployeeField::Skills),
+    ExportColumn::Field(EmployeeField::AttritionRisk),
+    ExportColumn::Field(EmployeeField::ImpactOfLoss),
+    ExportColumn::Field(EmployeeField::Notes),
+    ExportColumn::Field(EmployeeField::CurrentLabel),
+    ExportColumn::Field(EmployeeField::Email),
+    ExportColumn::Field(EmployeeField::ManagerId),
+    ExportColumn::Field(EmployeeField::Department),
+    ExportColumn::Field(EmployeeField::Location),
+    ExportColumn::Field(EmployeeField::HireDate),
+];
+
+// Parses one record, collecting a `RejectedRow` for every cell that cannot be interpreted.
+fn parse_employee(
+    headers: &StringRecord,
+    columns: &[ColumnTarget],
+    record: &StringRecord,
+    line: u64,
+) -> Result<Employee, Vec<RejectedRow>> {
+    let mut employee = Employee::default();
+    let mut problems = Vec::new();
+    for (index, raw_value) in record.iter().enumerate() {
+        let value = if raw_value.is_empty() {
+            None
+        } else {
+            Some(raw_value.to_string())
+        };
+        match columns.get(index) {
+            Some(ColumnTarget::Field(field)) => {
+                if let Err(reason) = field.set_value(&mut employee, value.clone()) {
+                    problems.push(RejectedRow {
+                        line,
+                        column: headers.get(index).map(str::to_string),
+                        raw_value: value,
+                        reason,
+                    });
+                }
+            }
+            Some(ColumnTarget::Cycle(cycle_field, cycle)) => {
+                let Some(value) = value else { continue };
+                let cycle_record = employee.cycle_mut(cycle);
+                match cycle_field {
+                    CycleField::Rating => match value.trim().parse::<f64>() {
+                        Ok(rating) => cycle_record.rating = Some(rating),
+                        Err(e) => problems.push(RejectedRow {
+                            line,
+                            column: headers.get(index).map(str::to_string),
+                            raw_value: Some(value),
+                            reason: e.to_string(),
+                        }),
+                    },
+                    CycleField::NineBox => cycle_record.nine_box = Some(value),
+                    CycleField::Group => cycle_record.group = Some(value),
+                }
+            }
+            Some(ColumnTarget::Custom) => {
+                let header = headers.get(index).unwrap_or_default();
+                employee.set_source_custom_field(header, raw_value, index);
+            }
+            None => {}
+        }
+    }
+    if problems.is_empty() {
+        Ok(employee)
+    } else {
+        Err(problems)
+    }
+}
+
+// Reads the header row and checks that every required field has a column.
+fn resolve_headers<R: Read>(
+    rdr: &mut csv::Reader<R>,
+    profile: &MappingProfile,
+) -> Result<(StringRecord, Vec<ColumnTarget>), CsvImportError> {
+    let headers = rdr.headers()?.clone();
+    let columns: Vec<ColumnTarget> = headers.iter().map(|header| profile.resolve_column(header)).collect();
+
+    for field in EmployeeField::ALL.into_iter().filter(|field| field.is_required()) {
+        if !columns.contains(&ColumnTarget::Field(field)) {
+            return Err(CsvImportError::MissingColumn(profile.field_rule(field).header));
+        }
+    }
+    Ok((headers, columns))
+}
+
+// Formats ratings the way they appear in source files ("4.0" rather than "4").
+fn format_rating(rating: f64) -> String {
+    if rating.is_finite() && rating.fract() == 0.0 {
+        format!("{:.1}", rating)
+    } else {
+        rating.to_string()
+    }
+}
+
+fn cycle_value(employee: &Employee, cycle_field: CycleField, cycle: &str) -> Option<String> {
+    match cycle_field {
+        CycleField::Rating => employee.rating(cycle).map(format_rating),
+        CycleField::NineBox => employee.nine_box(cycle).map(str::to_string),
+        CycleField::Group => employee.group(cycle).map(str::to_string),
+    }
+}
+
+/// Imports employees from a CSV data source using the standard column headers.
+///
+/// Fixed columns are matched by their header (e.g. `User ID`), while per-cycle
+/// columns such as `PR2026`, `User 9Box 2026` or `PR Group 2026` are detected
+/// from the header and stored in `Employee::performance_history`.
+///
+/// # Arguments
+/// * `reader` - A type that implements `std::io::Read` (e.g., a file or a byte slice).
+///
+/// # Returns
+/// A `Result` containing a `Vec<Employee>` on success, or a `CsvImportError` on failure.
+pub fn import_employees_from_csv<R: Read>(reader: R) -> Result<Vec<Employee>, CsvImportError> {
+    import_employees_with_profile(reader, &MappingProfile::standard())
+}
+
+/// Imports employees from a CSV data source, resolving headers through a mapping profile.
 ///
 /// # Arguments
 /// * `reader` - A type that implements `std::io::Read` (e.g., a file or a byte slice).
+/// * `profile` - The `MappingProfile` describing which header feeds which field.
 ///
 /// # Returns
-/// A `Result` containing a `Vec<Employee>` on success, or a `csv::Error` on failure.
-pub fn import_employees_from_csv<R: Read>(reader: R) -> Result<Vec<Employee>, csv::Error> {
+/// A `Result` containing a `Vec<Employee>` on success, or a `CsvImportError` on failure.
+pub fn import_employees_with_profile<R: Read>(
+    reader: R,
+    profile: &MappingProfile,
+) -> Result<Vec<Employee>, CsvImportError> {
     let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(reader);
+    let (headers, columns) = resolve_headers(&mut rdr, profile)?;
+
     let mut employees = Vec::new();
-    for result in rdr.deserialize() {
-        let employee: Employee = result?;
+    for result in rdr.records() {
+        let record = result?;
+        let line = record.position().map_or(0, |position| position.line());
+        let employee = parse_employee(&headers, &columns, &record, line).map_err(|mut problems| {
+            let problem = problems.remove(0);
+            CsvImportError::InvalidValue {
+                line: problem.line,
+                column: problem.column.unwrap_or_default(),
+                value: problem.raw_value.unwrap_or_default(),
+                reason: problem.reason,
+            }
+        })?;
         employees.push(employee);
     }
     Ok(employees)
 }
 
-/// Exports a slice of employees to a CSV data sink.
+/// A problem that kept a row (or one of its cells) from being imported.
+/// `column` and `raw_value` are `None` for problems affecting the whole row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedRow {
+    pub line: u64,
+    pub column: Option<String>,
+    pub raw_value: Option<String>,
+    pub reason: String,
+}
+
+/// A `user_id` that appears on more than one line. Only the first line is imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateId {
+    pub user_id: String,
+    pub lines: Vec<u64>,
+}
+
+/// An imported employee whose `manager_id` does not match any imported employee.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownManager {
+    pub line: u64,
+    pub user_id: String,
+    pub manager_id: String,
+}
+
+/// Everything found wrong with a file imported by `import_employees_with_report`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportReport {
+    pub rejected_rows: Vec<RejectedRow>,
+    pub duplicate_ids: Vec<DuplicateId>,
+    pub unknown_managers: Vec<UnknownManager>,
+}
+
+impl ImportReport {
+    /// Whether the file was imported without any problem.
+    pub fn is_clean(&self) -> bool {
+        self.issue_count() == 0
+    }
+
+    pub fn issue_count(&self) -> usize {
+        self.rejected_rows.len() + self.duplicate_ids.len() + self.unknown_managers.len()
+    }
+}
+
+/// The employees that could be imported, together with the report of everything that could not.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportOutcome {
+    pub employees: Vec<Employee>,
+    pub report: ImportReport,
+}
+
+/// Imports employees like `import_employees_with_profile`, but instead of stopping at the
+/// first bad row it keeps every good row and reports the rest.
+///
+/// Rows with unparseable values, without a `User ID` or with one too long to name a note file
+/// are rejected, later rows repeating an earlier `User ID` are skipped, and manager IDs that
+/// match no imported employee are reported.
+///
+/// # Arguments
+/// * `reader` - A type that implements `std::io::Read` (e.g., a file or a byte slice).
+/// * `profile` - The `MappingProfile` describing which header feeds which field.
+///
+/// # Returns
+/// A `Result` containing the `ImportOutcome`, or a `CsvImportError` if the header row
+/// cannot be read or a required column is missing.
+pub fn import_employees_with_report<R: Read>(
+    reader: R,
+    profile: &MappingProfile,
+) -> Result<ImportOutcome, CsvImportError> {
+    let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(reader);
+    let (headers, columns) = resolve_headers(&mut rdr, profile)?;
+
+    let mut outcome = ImportOutcome::default();
+    let mut employee_lines: Vec<u64> = Vec::new();
+    let mut lines_by_id: HashMap<String, Vec<u64>> = HashMap::new();
+    let mut duplicate_order: Vec<String> = Vec::new();
+
+    for result in rdr.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                outcome.report.rejected_rows.push(RejectedRow {
+                    line: e.position().map_or(0, |position| position.line()),
+                    column: None,
+                    raw_value: None,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |position| position.line());
+        let employee = match parse_employee(&headers, &columns, &record, line) {
+            Ok(employee) => employee,
+            Err(problems) => {
+                outcome.report.rejected_rows.extend(problems);
+                continue;
+            }
+        };
+        if employee.user_id.trim().is_empty() {
+            outcome.report.rejected_rows.push(RejectedRow {
+                line,
+                column: Some(profile.field_rule(EmployeeField::UserId).header),
+                raw_value: Some(employee.user_id),
+                reason: "missing user ID".to_string(),
+            });
+            continue;
+        }
+        // Notes are stored in a file named after the ID, so an ID that cannot name one is unusable
+        if let Err(e) = note_file_name(&employee.user_id) {
+            outcome.report.rejected_rows.push(RejectedRow {
+                line,
+                column: Some(profile.field_rule(EmployeeField::UserId).header),
+                raw_value: Some(employee.user_id),
+                reason: format!("user ID cannot name a note file: {}", e),
+            });
+            continue;
+        }
+
+        let lines = lines_by_id.entry(employee.user_id.clone()).or_default();
+        lines.push(line);
+        if lines.len() > 1 {
+            if lines.len() == 2 {
+                duplicate_order.push(employee.user_id.clone());
+            }
+            continue;
+        }
+        employee_lines.push(line);
+        outcome.employees.push(employee);
+    }
+
+    for user_id in duplicate_order {
+        let lines = lines_by_id.remove(&user_id).unwrap_or_default();
+        outcome.report.duplicate_ids.push(DuplicateId { user_id, lines });
+    }
+
+    let known_ids: HashSet<&str> = outcome.employees.iter().map(|e| e.user_id.as_str()).collect();
+    for (employee, line) in outcome.employees.iter().zip(&employee_lines) {
+        if let Some(manager_id) = employee.manager_id.as_deref() {
+            if !known_ids.contains(manager_id) {
+                outcome.report.unknown_managers.push(UnknownManager {
+                    line: *line,
+                    user_id: employee.user_id.clone(),
+                    manager_id: manager_id.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// The header row and first data rows of a CSV file, used to preview a mapping.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CsvPreview {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Reads the header row and up to `max_rows` data rows without interpreting them.
+///
+/// # Arguments
+/// * `reader` - A type that implements `std::io::Read`.
+/// * `max_rows` - The maximum number of data rows to return.
+///
+/// # Returns
+/// A `Result` containing the `CsvPreview`, or a `csv::Error` on failure.
+pub fn read_csv_preview<R: Read>(reader: R, max_rows: usize) -> Result<CsvPreview, csv::Error> {
+    let mut rdr = ReaderBuilder::new().has_headers(true).flexible(true).from_reader(reader);
+    let headers = rdr.headers()?.iter().map(str::to_string).collect();
+    let mut rows = Vec::new();
+    for result in rdr.records().take(max_rows) {
+        rows.push(result?.iter().map(str::to_string).collect());
+    }
+    Ok(CsvPreview { headers, rows })
+}
+
+/// Exports a slice of employees to a CSV data sink using the standard column headers.
+///
+/// One column is written per review cycle that has a value for at least one employee.
 ///
 /// # Arguments
 /// * `employees` - A slice of `Employee` structs to export.
@@ -31,9 +609,147 @@ pub fn export_employees_to_csv<W: Write>(
     employees: &[Employee],
     writer: W,
 ) -> Result<(), csv::Error> {
+    export_employees_with_profile(employees, &MappingProfile::standard(), writer)
+}
+
+/// Exports a slice of employees to a CSV data sink, writing each field under the
+/// main header of its rule in `profile`.
+///
+/// # Arguments
+/// * `employees` - A slice of `Employee` structs to export.
+/// * `profile` - The `MappingProfile` providing the header for each field.
+/// * `writer` - A type that implements `std::io::Write` (e.g., a file or a `Vec<u8>`).
+///
+/// # Returns
+/// A `Result` indicating success or a `csv::Error` on failure.
+pub fn export_employees_with_profile<W: Write>(
+    employees: &[Employee],
+    profile: &MappingProfile,
+    writer: W,
+) -> Result<(), csv::Error> {
+    let cycles_with_values = |cycle_field: CycleField| -> BTreeSet<&str> {
+        employees
+            .iter()
+            .flat_map(|employee| {
+                employee
+                    .performance_history
+                    .keys()
+                    .filter(move |cycle| cycle_value(employee, cycle_field, cycle).is_some())
+            })
+            .map(String::as_str)
+            .collect()
+    };
+
+    // Expand the layout into concrete columns: (header, column group, cycle for per-cycle fields).
+    let mut columns: Vec<(String, ExportColumn, Option<&str>)> = Vec::new();
+    for column in EXPORT_LAYOUT {
+        match column {
+            ExportColumn::Field(field) => {
+                columns.push((profile.field_rule(field).header, column, None));
+            }
+            ExportColumn::Cycles(cycle_field) => {
+                for cycle in cycles_with_values(cycle_field) {
+                    let header = cycle_header(&profile.cycle_rule(cycle_field).header, cycle);
+                    columns.push((header, column, Some(cycle)));
+                }
+            }
+        }
+    }
+
+    // Custom columns go back to where they were in the source header, as first seen on import;
+    // columns with no source position follow the known ones.
+    let mut custom_headers: Vec<(&str, Option<usize>)> = Vec::new();
+    for field in employees.iter().flat_map(|employee| &employee.custom_fields) {
+        let header = field.header.as_str();
+        if !custom_headers.iter().any(|(custom, _)| *custom == header)
+            && !columns.iter().any(|(known, _, _)| known == header)
+        {
+            custom_headers.push((header, field.column));
+        }
+    }
+    custom_headers.sort_by_key(|(_, column)| column.unwrap_or(usize::MAX));
+
+    enum OutputColumn<'a> {
+        Known(usize),
+        Custom(&'a str),
+    }
+    let mut output: Vec<OutputColumn> = (0..columns.len()).map(OutputColumn::Known).collect();
+    for (header, column) in custom_headers {
+        let at = column.map_or(output.len(), |column| column.min(output.len()));
+        output.insert(at, OutputColumn::Custom(header));
+    }
+
     let mut wtr = WriterBuilder::new().from_writer(writer);
+    wtr.write_record(output.iter().map(|column| match column {
+        OutputColumn::Known(index) => columns[*index].0.as_str(),
+        OutputColumn::Custom(header) => header,
+    }))?;
     for employee in employees {
-        wtr.serialize(employee)?;
+        let values = output.iter().map(|column| match column {
+            OutputColumn::Known(index) => {
+                let value = match &columns[*index] {
+                    (_, ExportColumn::Field(field), _) => field.value(employee),
+                    (_, ExportColumn::Cycles(cycle_field), Some(cycle)) => {
+                        cycle_value(employee, *cycle_field, cycle)
+                    }
+                    (_, ExportColumn::Cycles(_), None) => None,
+                };
+                value.unwrap_or_default()
+            }
+            OutputColumn::Custom(header) => employee.custom_field(header).unwrap_or_default().to_string(),
+        });
+        wtr.write_record(values)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Exports a skills matrix: one row per employee and one column per skill.
+///
+/// Skill columns follow the catalog order, followed by any skill held by an employee
+/// that the catalog does not list. Each cell holds the employee's proficiency,
+/// `SKILL_HELD_MARKER` for a skill held without a level, or nothing.
+///
+/// # Arguments
+/// * `employees` - A slice of `Employee` structs to export.
+/// * `catalog` - The skills catalog that orders the skill columns.
+/// * `writer` - A type that implements `std::io::Write` (e.g., a file or a `Vec<u8>`).
+///
+/// # Returns
+/// A `Result` indicating success or a `csv::Error` on failure.
+pub fn export_skills_matrix<W: Write>(
+    employees: &[Employee],
+    catalog: &[Skill],
+    writer: W,
+) -> Result<(), csv::Error> {
+    let mut skills: Vec<&str> = catalog.iter().map(|skill| skill.name.as_str()).collect();
+    for name in employees.iter().flat_map(|employee| &employee.skills) {
+        if !skills.iter().any(|skill| skill.eq_ignore_ascii_case(name)) {
+            skills.push(name);
+        }
+    }
+
+    let identity_fields = [
+        EmployeeField::UserId,
+        EmployeeField::FirstName,
+        EmployeeField::LastName,
+        EmployeeField::CurrentPosition,
+    ];
+    let mut wtr = WriterBuilder::new().from_writer(writer);
+    wtr.write_record(
+        identity_fields
+            .iter()
+            .map(|field| field.default_header())
+            .chain(skills.iter().copied()),
+    )?;
+    for employee in employees {
+        let identity = identity_fields.iter().map(|field| field.value(employee).unwrap_or_default());
+        let levels = skills.iter().map(|skill| match employee.skill_level(skill) {
+            Some(level) => level.to_string(),
+            None if employee.has_skill(skill) => SKILL_HELD_MARKER.to_string(),
+            None => String::new(),
+        });
+        wtr.write_record(identity.chain(levels))?;
     }
     wtr.flush()?;
     Ok(())
@@ -42,23 +758,24 @@ pub fn export_employees_to_csv<W: Write>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Employee; // Already using crate::models::Employee
+    use crate::models::{CycleRecord, Employee}; // Already using crate::models::Employee
+
+    fn cycle(rating: Option<f64>, nine_box: Option<&str>, group: Option<&str>) -> CycleRecord {
+        CycleRecord {
+            rating,
+            nine_box: nine_box.map(str::to_string),
+            group: group.map(str::to_string),
+        }
+    }
 
     fn get_sample_employees() -> Vec<Employee> {
         vec![
             Employee {
                 user_id: "user1".to_string(),
-                pr_group_2025: "Group A".to_string(),
                 first_name: "John".to_string(),
                 last_name: "Doe".to_string(),
                 current_position: "Developer".to_string(),
                 current_temp_position: None,
-                pr_2021: Some(4.0),
-                pr_2022: Some(4.1),
-                pr_2023: Some(4.2),
-                pr_2024: Some(4.3),
-                user_9box_2024: Some("Top Talent".to_string()),
-                user_9box_2025: Some("Growth Potential".to_string()),
                 notes: Some("High performer".to_string()),
                 current_label: Some("Senior".to_string()),
                 email: Some("john.doe@example.com".to_string()),
@@ -66,20 +783,27 @@ mod tests {
                 department: Some("Engineering".to_string()),
                 location: Some("New York".to_string()),
                 hire_date: Some("2020-01-15".to_string()),
+                performance_history: [
+                    ("2021".to_string(), cycle(Some(4.0), None, None)),
+                    ("2022".to_string(), cycle(Some(4.1), None, None)),
+                    ("2023".to_string(), cycle(Some(4.2), None, None)),
+                    ("2024".to_string(), cycle(Some(4.3), Some("Top Talent"), None)),
+                    ("2025".to_string(), cycle(None, Some("Growth Potential"), Some("Group A"))),
+                ]
+                .into_iter()
+                .collect(),
+                custom_fields: Vec::new(),
+                skills: vec!["Rust".to_string(), "SQL".to_string()],
+                skill_levels: Default::default(),
+                attrition_risk: Some(AttritionRisk::High),
+                impact_of_loss: Some(ImpactOfLoss::Critical),
             },
             Employee {
                 user_id: "user2".to_string(),
-                pr_group_2025: "Group B".to_string(),
                 first_name: "Jane".to_string(),
                 last_name: "Smith".to_string(),
                 current_position: "Manager".to_string(),
                 current_temp_position: Some("Acting Director".to_string()),
-                pr_2021: None,
-                pr_2022: Some(4.5),
-                pr_2023: Some(4.6),
-                pr_2024: None,
-                user_9box_2024: None,
-                user_9box_2025: Some("Key Player".to_string()),
                 notes: None,
                 current_label: None,
                 email: Some("jane.smith@example.com".to_string()),
@@ -87,6 +811,18 @@ mod tests {
                 department: Some("Management".to_string()),
                 location: Some("London".to_string()),
                 hire_date: Some("2018-05-20".to_string()),
+                performance_history: [
+                    ("2022".to_string(), cycle(Some(4.5), None, None)),
+                    ("2023".to_string(), cycle(Some(4.6), None, None)),
+                    ("2025".to_string(), cycle(None, Some("Key Player"), Some("Group B"))),
+                ]
+                .into_iter()
+                .collect(),
+                custom_fields: Vec::new(),
+                skills: Vec::new(),
+                skill_levels: Default::default(),
+                attrition_risk: None,
+                impact_of_loss: None,
             },
         ]
     }
@@ -106,8 +842,9 @@ user2,Group B,Jane,Smith,Manager,Acting Director,,,4.5,4.6,,Key Player,,,jane.sm
         let emp1 = &employees[0];
         assert_eq!(emp1.user_id, "user1");
         assert_eq!(emp1.first_name, "John");
-        assert_eq!(emp1.pr_group_2025, "Group A");
-        assert_eq!(emp1.pr_2024, Some(4.3));
+        assert_eq!(emp1.group("2025"), Some("Group A"));
+        assert_eq!(emp1.rating("2024"), Some(4.3));
+        assert_eq!(emp1.nine_box("2024"), Some("Top Talent"));
         assert_eq!(emp1.notes, Some("High performer".to_string()));
         assert_eq!(emp1.current_temp_position, None);
         assert_eq!(emp1.email, Some("john.doe@example.com".to_string()));
@@ -115,18 +852,263 @@ user2,Group B,Jane,Smith,Manager,Acting Director,,,4.5,4.6,,Key Player,,,jane.sm
         assert_eq!(emp1.department, Some("Engineering".to_string()));
         assert_eq!(emp1.location, Some("New York".to_string()));
         assert_eq!(emp1.hire_date, Some("2020-01-15".to_string()));
-
+        assert!(emp1.skills.is_empty());
 
         let emp2 = &employees[1];
         assert_eq!(emp2.user_id, "user2");
         assert_eq!(emp2.first_name, "Jane");
-        assert_eq!(emp2.pr_group_2025, "Group B");
-        assert_eq!(emp2.pr_2021, None);
+        assert_eq!(emp2.group("2025"), Some("Group B"));
+        assert_eq!(emp2.rating("2021"), None);
         assert_eq!(emp2.current_temp_position, Some("Acting Director".to_string()));
         assert_eq!(emp2.email, Some("jane.smith@example.com".to_string()));
         assert_eq!(emp2.manager_id, None);
     }
 
+    #[test]
+    fn test_import_skills_column() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Skills\n\
+                        u1,Ann,Lee,Analyst,\"Skill 1;Skill 2\"\n\
+                        u2,Bob,Ray,Engineer, Rust ; ;rust;SQL \n\
+                        u3,Cat,Fox,Engineer,\n";
+        let employees = import_employees_from_csv(csv_data.as_bytes()).expect("CSV import failed");
+        assert_eq!(employees[0].skills, vec!["Skill 1", "Skill 2"]);
+        assert_eq!(employees[1].skills, vec!["Rust", "SQL"], "Blank and repeated skills should be dropped");
+        assert!(employees[2].skills.is_empty());
+    }
+
+    #[test]
+    fn test_import_attrition_risk_and_impact() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Attrition Risk,Impact of Loss\n\
+                        u1,Ann,Lee,Analyst,Extreme >6 months,critical\n\
+                        u2,Bob,Ray,Engineer,,\n\
+                        u3,Cat,Fox,Engineer,Sometimes,Low\n";
+        let outcome = import_employees_with_report(csv_data.as_bytes(), &MappingProfile::standard())
+            .expect("CSV import failed");
+        assert_eq!(outcome.employees.len(), 2);
+        assert_eq!(outcome.employees[0].attrition_risk, Some(AttritionRisk::Extreme));
+        assert_eq!(outcome.employees[0].impact_of_loss, Some(ImpactOfLoss::Critical));
+        assert_eq!(outcome.employees[1].attrition_risk, None);
+
+        let rejected = &outcome.report.rejected_rows;
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].column.as_deref(), Some("Attrition Risk"));
+        assert_eq!(rejected[0].raw_value.as_deref(), Some("Sometimes"));
+    }
+
+    #[test]
+    fn test_skill_levels_round_trip() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Skills\n\
+                        u1,Ann,Lee,Analyst,\"Rust:4; SQL ;C++: beginner;Go:9\"\n";
+        let employees = import_employees_from_csv(csv_data.as_bytes()).expect("CSV import failed");
+        let ann = &employees[0];
+        assert_eq!(ann.skills, vec!["Rust", "SQL", "C++: beginner", "Go:9"], "Invalid levels stay part of the name");
+        assert_eq!(ann.skill_level("rust"), Some(4));
+        assert_eq!(ann.skill_level("SQL"), None);
+
+        let mut buffer = Vec::new();
+        export_employees_to_csv(&employees, &mut buffer).expect("CSV export failed");
+        let exported = String::from_utf8(buffer).expect("Export is not UTF-8");
+        assert!(exported.contains("Rust:4; SQL; C++: beginner; Go:9"), "Export was: {}", exported);
+    }
+
+    #[test]
+    fn test_export_skills_matrix() {
+        let mut ann = Employee {
+            user_id: "u1".to_string(),
+            first_name: "Ann".to_string(),
+            last_name: "Lee".to_string(),
+            current_position: "Analyst".to_string(),
+            ..Employee::default()
+        };
+        ann.set_skill_level("SQL", Some(3));
+        ann.add_skill("Negotiation");
+        let bob = Employee {
+            user_id: "u2".to_string(),
+            first_name: "Bob".to_string(),
+            last_name: "Ray".to_string(),
+            current_position: "Engineer".to_string(),
+            skills: vec!["rust".to_string()],
+            ..Employee::default()
+        };
+        let catalog = vec![
+            Skill { id: "rust".to_string(), name: "Rust".to_string() },
+            Skill { id: "sql".to_string(), name: "SQL".to_string() },
+        ];
+
+        let mut buffer = Vec::new();
+        export_skills_matrix(&[ann, bob], &catalog, &mut buffer).expect("Matrix export failed");
+        let matrix = String::from_utf8(buffer).expect("Export is not UTF-8");
+        assert_eq!(
+            matrix,
+            "User ID,First Name,Last Name,Current Position,Rust,SQL,Negotiation\n\
+             u1,Ann,Lee,Analyst,,3,x\n\
+             u2,Bob,Ray,Engineer,x,,\n"
+        );
+    }
+
+    #[test]
+    fn test_import_detects_new_cycles_from_header() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,PR Group 2026,PR2026,User 9Box 2026,PR2025H2\n\
+                        u1,Ann,Lee,Analyst,Group C,3.9,2B,4.25\n";
+        let employees = import_employees_from_csv(csv_data.as_bytes()).expect("CSV import failed");
+        assert_eq!(employees.len(), 1);
+
+        let emp = &employees[0];
+        assert_eq!(emp.rating("2026"), Some(3.9));
+        assert_eq!(emp.nine_box("2026"), Some("2B"));
+        assert_eq!(emp.group("2026"), Some("Group C"));
+        assert_eq!(emp.rating("2025H2"), Some(4.25));
+        assert_eq!(emp.latest_rating(), Some(("2026", 3.9)));
+    }
+
+    #[test]
+    fn test_import_missing_required_column() {
+        let csv_data = "User ID,First Name,Current Position\nu1,Ann,Analyst\n";
+        let result = import_employees_from_csv(csv_data.as_bytes());
+        assert!(
+            matches!(result, Err(CsvImportError::MissingColumn(ref column)) if column == "Last Name"),
+            "Expected a missing 'Last Name' column error, got {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_rating() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,PR2024\nu1,Ann,Lee,Analyst,excellent\n";
+        match import_employees_from_csv(csv_data.as_bytes()) {
+            Err(CsvImportError::InvalidValue { line, column, value, .. }) => {
+                assert_eq!(line, 2);
+                assert_eq!(column, "PR2024");
+                assert_eq!(value, "excellent");
+            }
+            other => panic!("Expected an invalid value error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_match_cycle_header() {
+        assert_eq!(match_cycle_header("PR{cycle}", "PR2026"), Some("2026".to_string()));
+        assert_eq!(match_cycle_header("PR{cycle}", "PR Group 2026"), None);
+        assert_eq!(match_cycle_header("User 9Box {cycle}", "User 9Box 2024"), Some("2024".to_string()));
+        assert_eq!(match_cycle_header("User 9Box {cycle}", "User 9Box "), None);
+        assert_eq!(match_cycle_header("PR", "PR2026"), None);
+        assert_eq!(match_cycle_header("pr group {cycle}", "PR Group 2026"), Some("2026".to_string()));
+        assert_eq!(cycle_header("PR Group {cycle}", "2027"), "PR Group 2027");
+    }
+
+    #[test]
+    fn test_import_with_legacy_profile() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Dept,Reports to,PR 2021,PR 2024,User  9Box 2024\n\
+                        u1,Ann,Lee,Analyst,Finance,m1,3.5,4.0,Top Talent\n";
+
+        let standard_result = import_employees_from_csv(csv_data.as_bytes()).expect("CSV import failed");
+        assert_eq!(standard_result[0].rating("2021"), None, "Standard headers should not match 'PR 2021'");
+
+        let employees = import_employees_with_profile(csv_data.as_bytes(), &MappingProfile::legacy_hris())
+            .expect("CSV import with legacy profile failed");
+        let emp = &employees[0];
+        assert_eq!(emp.rating("2021"), Some(3.5));
+        assert_eq!(emp.rating("2024"), Some(4.0));
+        assert_eq!(emp.nine_box("2024"), Some("Top Talent"));
+        assert_eq!(emp.department, Some("Finance".to_string()));
+        assert_eq!(emp.manager_id, Some("m1".to_string()));
+    }
+
+    #[test]
+    fn test_export_with_profile_uses_profile_headers() {
+        let employees = get_sample_employees();
+        let profile = MappingProfile::legacy_hris();
+        let mut buffer = Vec::new();
+        export_employees_with_profile(&employees, &profile, &mut buffer).expect("CSV export failed");
+        let csv_output = String::from_utf8(buffer).expect("CSV output is not valid UTF-8");
+        let header = csv_output.lines().next().unwrap();
+        assert!(header.contains("PR 2021,PR 2022"), "Header was: {}", header);
+        assert!(header.contains("User  9Box 2024"), "Header was: {}", header);
+        assert!(header.contains("Reports to,Dept"), "Header was: {}", header);
+
+        let reimported = import_employees_with_profile(csv_output.as_bytes(), &profile).expect("CSV import failed");
+        assert_eq!(reimported, employees);
+    }
+
+    #[test]
+    fn test_import_with_report_collects_all_problems() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Manager ID,PR2023,PR2024\n\
+                        u1,Ann,Lee,Analyst,,3.5,4.0\n\
+                        u2,Bob,Ray,Engineer,u1,abc,4.1\n\
+                        u3,Cat,Fox,Engineer,u9,3.0,\n\
+                        u1,Ann,Again,Analyst,,,\n\
+                        ,No,Id,Intern,,,\n\
+                        u4,Dan,Oak,Engineer\n\
+                        u5,Eve,Elm,Lead,u1,x,y\n";
+        let outcome = import_employees_with_report(csv_data.as_bytes(), &MappingProfile::standard())
+            .expect("Import with report failed");
+
+        let ids: Vec<&str> = outcome.employees.iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(ids, vec!["u1", "u3"]);
+        assert_eq!(outcome.employees[0].last_name, "Lee", "The first occurrence of a duplicate ID should win");
+
+        let report = &outcome.report;
+        let rejected: Vec<(u64, Option<&str>, Option<&str>)> = report
+            .rejected_rows
+            .iter()
+            .map(|row| (row.line, row.column.as_deref(), row.raw_value.as_deref()))
+            .collect();
+        assert_eq!(
+            rejected,
+            vec![
+                (3, Some("PR2023"), Some("abc")),
+                (6, Some("User ID"), Some("")),
+                (7, None, None),
+                (8, Some("PR2023"), Some("x")),
+                (8, Some("PR2024"), Some("y")),
+            ]
+        );
+        assert_eq!(report.duplicate_ids, vec![DuplicateId { user_id: "u1".to_string(), lines: vec![2, 5] }]);
+        assert_eq!(
+            report.unknown_managers,
+            vec![UnknownManager { line: 4, user_id: "u3".to_string(), manager_id: "u9".to_string() }]
+        );
+        assert_eq!(report.issue_count(), 7);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_import_with_report_clean_file() {
+        let csv_data = "User ID,First Name,Last Name,Current Position,Manager ID\nu1,Ann,Lee,Analyst,\nu2,Bob,Ray,Engineer,u1\n";
+        let outcome = import_employees_with_report(csv_data.as_bytes(), &MappingProfile::standard())
+            .expect("Import with report failed");
+        assert_eq!(outcome.employees.len(), 2);
+        assert!(outcome.report.is_clean());
+    }
+
+    #[test]
+    fn test_import_with_report_rejects_ids_that_cannot_name_a_note_file() {
+        let long_id = "E".repeat(100);
+        let csv_data = format!("User ID,First Name,Last Name,Current Position\nu1,Ann,Lee,Analyst\n{},Bob,Ray,Engineer\n", long_id);
+        let outcome = import_employees_with_report(csv_data.as_bytes(), &MappingProfile::standard())
+            .expect("Import with report failed");
+        assert_eq!(outcome.employees.len(), 1);
+        let rejected = &outcome.report.rejected_rows;
+        assert_eq!(rejected.len(), 1);
+        assert_eq!((rejected[0].line, rejected[0].raw_value.as_deref()), (3, Some(long_id.as_str())));
+        assert!(rejected[0].reason.contains("note file"), "Unexpected reason: {}", rejected[0].reason);
+    }
+
+    #[test]
+    fn test_import_with_report_still_requires_columns() {
+        let csv_data = "User ID,First Name\nu1,Ann\n";
+        let result = import_employees_with_report(csv_data.as_bytes(), &MappingProfile::standard());
+        assert!(matches!(result, Err(CsvImportError::MissingColumn(_))), "Got {:?}", result);
+    }
+
+    #[test]
+    fn test_read_csv_preview() {
+        let csv_data = "A,B\n1,2\n3,4\n5\n";
+        let preview = read_csv_preview(csv_data.as_bytes(), 2).expect("Preview failed");
+        assert_eq!(preview.headers, vec!["A", "B"]);
+        assert_eq!(preview.rows, vec![vec!["1", "2"], vec!["3", "4"]]);
+    }
+
     #[test]
     fn test_export_simple_csv() {
         let employees = get_sample_employees();
@@ -137,15 +1119,33 @@ user2,Group B,Jane,Smith,Manager,Acting Director,,,4.5,4.6,,Key Player,,,jane.sm
 
         let csv_output = String::from_utf8(buffer).expect("CSV output is not valid UTF-8");
 
-        // Construct expected CSV string carefully, matching Serde's output order and empty fields for None
-        let expected_csv_header = "User ID,PR Group 2025,First Name,Last Name,Current Position,Current Temp Position,PR2021,PR2022,PR2023,PR2024,User 9Box 2024,User 9Box 2025,Notes,Current Label,Email,Manager ID,Department,Location,Hire Date\n";
-        let expected_csv_emp1 = "user1,Group A,John,Doe,Developer,,4.0,4.1,4.2,4.3,Top Talent,Growth Potential,High performer,Senior,john.doe@example.com,manager1,Engineering,New York,2020-01-15\n";
-        let expected_csv_emp2 = "user2,Group B,Jane,Smith,Manager,Acting Director,,,4.5,4.6,,Key Player,,,,jane.smith@example.com,,Management,London,2018-05-20\n";
+        // Per-cycle columns are written for every cycle that has a value, in cycle order
+        let expected_csv_header = "User ID,PR Group 2025,First Name,Last Name,Current Position,Current Temp Position,PR2021,PR2022,PR2023,PR2024,User 9Box 2024,User 9Box 2025,Skills,Attrition Risk,Impact of Loss,Notes,Current Label,Email,Manager ID,Department,Location,Hire Date\n";
+        let expected_csv_emp1 = "user1,Group A,John,Doe,Developer,,4.0,4.1,4.2,4.3,Top Talent,Growth Potential,Rust; SQL,High,Critical,High performer,Senior,john.doe@example.com,manager1,Engineering,New York,2020-01-15\n";
+        let expected_csv_emp2 = "user2,Group B,Jane,Smith,Manager,Acting Director,,4.5,4.6,,,Key Player,,,,,,jane.smith@example.com,,Management,London,2018-05-20\n";
         let expected_csv_data = format!("{}{}{}", expected_csv_header, expected_csv_emp1, expected_csv_emp2);
 
         assert_eq!(csv_output, expected_csv_data);
     }
 
+    #[test]
+    fn test_export_writes_new_cycles() {
+        let mut employees = get_sample_employees();
+        employees[1].cycle_mut("2026").rating = Some(3.75);
+        employees[1].cycle_mut("2026").nine_box = Some("1B".to_string());
+
+        let mut buffer = Vec::new();
+        export_employees_to_csv(&employees, &mut buffer).expect("CSV export failed");
+        let csv_output = String::from_utf8(buffer).expect("CSV output is not valid UTF-8");
+        let header = csv_output.lines().next().unwrap();
+        assert!(header.contains("PR2024,PR2026,User 9Box 2024,User 9Box 2025,User 9Box 2026"), "Header was: {}", header);
+        assert!(!header.contains("PR Group 2026"), "Cycles without values should not get a column: {}", header);
+
+        let reimported = import_employees_from_csv(csv_output.as_bytes()).expect("CSV import failed");
+        assert_eq!(reimported[1].rating("2026"), Some(3.75));
+        assert_eq!(reimported[1].nine_box("2026"), Some("1B"));
+    }
+
     #[test]
     fn test_csv_round_trip() {
         let original_employees = get_sample_employees();
@@ -160,8 +1160,61 @@ user2,Group B,Jane,Smith,Manager,Acting Director,,,4.5,4.6,,Key Player,,,jane.sm
         assert!(import_result.is_ok(), "Import failed: {:?}", import_result.err());
 
         let imported_employees = import_result.unwrap();
-        
+
         // Assert equality (requires Employee to derive PartialEq)
         assert_eq!(original_employees, imported_employees);
     }
+
+    #[test]
+    fn test_unknown_columns_survive_round_trip() {
+        let csv_data = "User ID,Grade,First Name,Last Name,Sub Function,Current Position,TOP,YOS\n\
+                        u1,7,Ann,Lee,Payroll,Analyst,Y,4\n\
+                        u2,,Bob,Ray,Tax,Engineer,N,12\n";
+        let employees = import_employees_from_csv(csv_data.as_bytes()).expect("CSV import failed");
+        let headers: Vec<&str> = employees[0].custom_fields.iter().map(|f| f.header.as_str()).collect();
+        assert_eq!(headers, vec!["Grade", "Sub Function", "TOP", "YOS"]);
+        let columns: Vec<Option<usize>> = employees[0].custom_fields.iter().map(|f| f.column).collect();
+        assert_eq!(columns, vec![Some(1), Some(4), Some(6), Some(7)]);
+        assert_eq!(employees[0].custom_field("Sub Function"), Some("Payroll"));
+        assert_eq!(employees[1].custom_field("Grade"), Some(""));
+        assert_eq!(employees[1].custom_field("District"), None);
+    }
+
+    #[test]
+    fn test_export_keeps_the_source_column_order() {
+        // A source file with every exported column, and custom ones between the known ones
+        let mut employee = Employee {
+            user_id: "u1".to_string(),
+            first_name: "Ann".to_string(),
+            last_name: "Lee".to_string(),
+            current_position: "Analyst".to_string(),
+            ..Employee::default()
+        };
+        employee.cycle_mut("2024").rating = Some(4.0);
+        let mut buffer = Vec::new();
+        export_employees_to_csv(&[employee], &mut buffer).expect("CSV export failed");
+        let mut reader = csv::Reader::from_reader(buffer.as_slice());
+        let mut header: Vec<String> = reader.headers().unwrap().iter().map(str::to_string).collect();
+        let mut row: Vec<String> = reader.records().next().unwrap().unwrap().iter().map(str::to_string).collect();
+        let grade_at = header.iter().position(|h| h == "First Name").unwrap() + 1;
+        header.insert(grade_at, "Grade".to_string());
+        row.insert(grade_at, "7".to_string());
+        header.insert(0, "Badge".to_string());
+        row.insert(0, "B-17".to_string());
+        let mut source = csv::Writer::from_writer(Vec::new());
+        source.write_record(&header).unwrap();
+        source.write_record(&row).unwrap();
+        let source = source.into_inner().unwrap();
+
+        let employees = import_employees_from_csv(source.as_slice()).expect("CSV import failed");
+        let mut buffer = Vec::new();
+        export_employees_to_csv(&employees, &mut buffer).expect("CSV export failed");
+        let exported = String::from_utf8(buffer).expect("CSV output is not valid UTF-8");
+        let exported_header = exported.lines().next().unwrap();
+        assert_eq!(exported_header, header.join(","), "Grade stays between First Name and Last Name");
+        assert_eq!(exported.as_bytes(), source.as_slice());
+
+        let reimported = import_employees_from_csv(exported.as_bytes()).expect("CSV import failed");
+        assert_eq!(reimported, employees);
+    }
 }
@@ -0,0 +1,130 @@
+use crate::csv_processing::EmployeeField;
+use crate::grid::GridPosition;
+use crate::history::Change;
+use std::fmt;
+use std::sync::Arc;
+
+/// Something that changed in a `Workspace`. Listeners receive these after the change has
+/// been made, including changes made by undo and redo.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceEvent {
+    /// Assigned to, moved between or taken off grid boxes.
+    EmployeeMoved {
+        employee_id: String,
+        from: Option<GridPosition>,
+        to: Option<GridPosition>,
+    },
+    EmployeeEdited { employee_id: String, field: EmployeeField },
+    /// A review cycle's 9-box value changed, e.g. alongside a grid move.
+    NineBoxChanged { employee_id: String, cycle: String },
+    NoteChanged { employee_id: String },
+    EmployeesImported { count: usize },
+    ProjectOpened,
+    GridReseeded { cycle: String },
+    GridTemplateChanged { name: String, unplaced: Vec<String> },
+    LabelSchemeChanged { name: String },
+    SkillCatalogChanged,
+    RoleRequirementsChanged,
+    SuccessionPlanChanged,
+    EditUndone { description: String },
+    EditRedone { description: String },
+}
+
+impl WorkspaceEvent {
+    /// The event reporting that `change` was applied.
+    pub fn from_change(change: &Change) -> Self {
+        match change {
+            Change::Placement { employee_id, from, to } => WorkspaceEvent::EmployeeMoved {
+                employee_id: employee_id.clone(),
+                from: *from,
+                to: *to,
+            },
+            Change::Note { employee_id, .. } => WorkspaceEvent::NoteChanged { employee_id: employee_id.clone() },
+            Change::Field { employee_id, field, .. } => WorkspaceEvent::EmployeeEdited {
+                employee_id: employee_id.clone(),
+                field: *field,
+            },
+            Change::NineBox { employee_id, cycle, .. } => WorkspaceEvent::NineBoxChanged {
+                employee_id: employee_id.clone(),
+                cycle: cycle.clone(),
+            },
+            Change::SkillCatalog { .. } => WorkspaceEvent::SkillCatalogChanged,
+            Change::RoleRequirements { .. } => WorkspaceEvent::RoleRequirementsChanged,
+            Change::Succession { .. } => WorkspaceEvent::SuccessionPlanChanged,
+        }
+    }
+
+    /// Whether the grid placements changed, so a saved grid is out of date.
+    pub fn changes_grid(&self) -> bool {
+        matches!(
+            self,
+            WorkspaceEvent::EmployeeMoved { .. }
+                | WorkspaceEvent::EmployeesImported { .. }
+                | WorkspaceEvent::ProjectOpened
+                | WorkspaceEvent::GridReseeded { .. }
+                | WorkspaceEvent::GridTemplateChanged { .. }
+        )
+    }
+}
+
+impl fmt::Display for WorkspaceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let box_name = |position: &Option<GridPosition>| position.map_or("off the grid".to_string(), |p| p.code());
+        match self {
+            WorkspaceEvent::EmployeeMoved { employee_id, from, to } => {
+                write!(f, "Employee {} moved from {} to {}", employee_id, box_name(from), box_name(to))
+            }
+            WorkspaceEvent::EmployeeEdited { employee_id, field } => {
+                write!(f, "Employee {}: {} edited", employee_id, field.default_header())
+            }
+            WorkspaceEvent::NineBoxChanged { employee_id, cycle } => {
+                write!(f, "Employee {}: 9-box for {} changed", employee_id, cycle)
+            }
+            WorkspaceEvent::NoteChanged { employee_id } => write!(f, "Note for employee {} changed", employee_id),
+            WorkspaceEvent::EmployeesImported { count } => write!(f, "Imported {} employees", count),
+            WorkspaceEvent::ProjectOpened => write!(f, "Project opened"),
+            WorkspaceEvent::GridReseeded { cycle } => write!(f, "Grid seeded from cycle {}", cycle),
+            WorkspaceEvent::GridTemplateChanged { name, unplaced } => {
+                write!(f, "Grid template set to {}; {} employee(s) unplaced", name, unplaced.len())
+            }
+            WorkspaceEvent::LabelSchemeChanged { name } => write!(f, "Box labels set to {}", name),
+            WorkspaceEvent::SkillCatalogChanged => write!(f, "Skills catalog changed"),
+            WorkspaceEvent::RoleRequirementsChanged => write!(f, "Role requirements changed"),
+            WorkspaceEvent::SuccessionPlanChanged => write!(f, "Succession plan changed"),
+            WorkspaceEvent::EditUndone { description } => write!(f, "Undid: {}", description),
+            WorkspaceEvent::EditRedone { description } => write!(f, "Redid: {}", description),
+        }
+    }
+}
+
+/// A callback that receives every event. Listeners run synchronously on the thread that
+/// made the change, so they should hand work off (e.g. into a channel) rather than block.
+pub type EventListener = Arc<dyn Fn(&WorkspaceEvent) + Send + Sync>;
+
+/// The listeners subscribed to a workspace.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    listeners: Vec<EventListener>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self, listener: impl Fn(&WorkspaceEvent) + Send + Sync + 'static) {
+        self.listeners.push(Arc::new(listener));
+    }
+
+    pub fn emit(&self, event: WorkspaceEvent) {
+        for listener in &self.listeners {
+            listener(&event);
+        }
+    }
+
+    pub fn listener_count(&self) -> usize {
+        self.listeners.len()
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus").field("listeners", &self.listeners.len()).finish()
+    }
+}
@@ -0,0 +1,529 @@
+use crate::labels::normalize_box_value;
+use crate::models::{Employee, GridBox, GridState};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Largest number of levels on either axis; codes use a single digit and a single letter.
+pub const MAX_GRID_LEVELS: u8 = 9;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum GridError {
+    #[error("Unknown grid box '{0}'")]
+    UnknownBox(String),
+    #[error("Invalid grid template '{name}': {reason}")]
+    InvalidTemplate { name: String, reason: String },
+}
+
+/// A box on the grid: a performance level crossed with a potential level.
+///
+/// Levels are indices counted from the top, so `0` is the highest level on either axis.
+/// The short code puts the performance row first as a digit and the potential column
+/// second as a letter, e.g. `"1A"` is high performance / high potential and `"3C"` is
+/// low performance / low potential on the standard 9-box. Templates with other axes
+/// (see `GridTemplate`) use the same scheme for their row and column axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridPosition {
+    performance: u8,
+    potential: u8,
+}
+
+impl GridPosition {
+    /// The box at the given performance and potential levels, if a code can name it.
+    /// Whether the box exists on a particular grid is checked by `GridTemplate::contains`.
+    pub fn new(performance: u8, potential: u8) -> Option<Self> {
+        (performance < MAX_GRID_LEVELS && potential < MAX_GRID_LEVELS)
+            .then_some(GridPosition { performance, potential })
+    }
+
+    pub fn performance(&self) -> u8 {
+        self.performance
+    }
+
+    pub fn potential(&self) -> u8 {
+        self.potential
+    }
+
+    /// The short code for this box, e.g. `"2B"`.
+    pub fn code(&self) -> String {
+        format!("{}{}", self.performance + 1, (b'A' + self.potential) as char)
+    }
+
+    /// Parses a short code such as `"1A"` (case-insensitive).
+    pub fn from_code(code: &str) -> Result<Self, GridError> {
+        let unknown = || GridError::UnknownBox(code.to_string());
+        let mut chars = code.trim().chars();
+        let (Some(row), Some(column), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(unknown());
+        };
+        let performance = row.to_digit(10).and_then(|digit| digit.checked_sub(1)).ok_or_else(unknown)?;
+        if !column.is_ascii_alphabetic() {
+            return Err(unknown());
+        }
+        let potential = column.to_ascii_uppercase() as u32 - 'A' as u32;
+        if potential >= MAX_GRID_LEVELS as u32 {
+            return Err(unknown());
+        }
+        GridPosition::new(performance as u8, potential as u8).ok_or_else(unknown)
+    }
+}
+
+impl fmt::Display for GridPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code())
+    }
+}
+
+impl FromStr for GridPosition {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GridPosition::from_code(s)
+    }
+}
+
+// Stored as its short code so grid state files keep their `"1A"` keys.
+impl Serialize for GridPosition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for GridPosition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+/// One axis of a grid: its name and its level names, highest level first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GridAxis {
+    pub name: String,
+    pub levels: Vec<String>,
+}
+
+impl GridAxis {
+    pub fn new(name: &str, levels: &[&str]) -> Self {
+        GridAxis {
+            name: name.to_string(),
+            levels: levels.iter().map(|level| level.to_string()).collect(),
+        }
+    }
+
+    fn len(&self) -> u8 {
+        self.levels.len() as u8
+    }
+
+    // Whether `word` names this axis: the full name or an abbreviation of at least three letters.
+    fn matches_name(&self, word: &str) -> bool {
+        let name = self.name.to_ascii_lowercase();
+        let word = word.to_ascii_lowercase();
+        word.len() >= 3 && name.starts_with(&word)
+    }
+
+    // The index of the level called `name`; common abbreviations of High/Medium/Low are accepted.
+    fn level_index(&self, name: &str) -> Option<u8> {
+        let wanted = canonical_level(name);
+        self.levels
+            .iter()
+            .position(|level| canonical_level(level) == wanted)
+            .map(|index| index as u8)
+    }
+}
+
+fn canonical_level(name: &str) -> String {
+    let name = name.trim().to_ascii_lowercase();
+    match name.as_str() {
+        "hi" => "high".to_string(),
+        "med" | "mid" | "moderate" => "medium".to_string(),
+        "lo" => "low".to_string(),
+        _ => name,
+    }
+}
+
+// A built-in box entry with a label, description and color but no icon.
+fn colored_box(id: &str, label: &str, description: &str, color: &str) -> GridBox {
+    GridBox {
+        id: id.to_string(),
+        label: label.to_string(),
+        description: description.to_string(),
+        color: Some(color.to_string()),
+        icon: None,
+    }
+}
+
+/// The shape and wording of a grid: a row axis (performance on the 9-box) crossed with a
+/// column axis (potential), plus optional per-box label, description, color and icon.
+/// Boxes are identified by their position code (`GridBox::id`, e.g. `"1A"`); boxes without
+/// an entry are labelled from the axis level names.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GridTemplate {
+    pub name: String,
+    pub rows: GridAxis,
+    pub columns: GridAxis,
+    #[serde(default)]
+    pub boxes: Vec<GridBox>,
+}
+
+impl Default for GridTemplate {
+    fn default() -> Self {
+        GridTemplate::nine_box()
+    }
+}
+
+impl GridTemplate {
+    /// The classic 3×3 performance × potential grid.
+    pub fn nine_box() -> Self {
+        GridTemplate {
+            name: "9-Box (3×3)".to_string(),
+            rows: GridAxis::new("Performance", &["High", "Medium", "Low"]),
+            columns: GridAxis::new("Potential", &["High", "Medium", "Low"]),
+            boxes: vec![
+                colored_box("1A", "High Perf / High Pot", "Consistently exceeds expectations and is ready for broader scope.", "#597864"),
+                colored_box("1B", "High Perf / Med Pot", "Delivers strong results; can grow further in the current area.", "#92C0C1"),
+                colored_box("1C", "High Perf / Low Pot", "Strong contributor who is well placed in the current role.", "#274973"),
+                colored_box("2A", "Med Perf / High Pot", "Shows high potential that is not yet reflected in results.", "#92C0C1"),
+                colored_box("2B", "Med Perf / Med Pot", "Meets expectations with room to develop.", "#274973"),
+                colored_box("2C", "Med Perf / Low Pot", "Meets expectations; may be misaligned with the role.", "#FECB86"),
+                colored_box("3A", "Low Perf / High Pot", "Capable but underperforming; check fit, support and engagement.", "#274973"),
+                colored_box("3B", "Low Perf / Med Pot", "Below expectations; needs a development plan.", "#FECB86"),
+                colored_box("3C", "Low Perf / Low Pot", "Below expectations with limited growth; action needed.", "#BF4228"),
+            ],
+        }
+    }
+
+    /// A 4×4 talent matrix with finer-grained levels on both axes.
+    pub fn four_by_four() -> Self {
+        GridTemplate {
+            name: "Talent Matrix (4×4)".to_string(),
+            rows: GridAxis::new("Performance", &["Exceptional", "Strong", "Solid", "Developing"]),
+            columns: GridAxis::new("Potential", &["Very High", "High", "Moderate", "Limited"]),
+            boxes: Vec::new(),
+        }
+    }
+
+    /// A 2×2 retention grid of impact of loss × risk of loss.
+    pub fn impact_risk() -> Self {
+        GridTemplate {
+            name: "Impact × Risk (2×2)".to_string(),
+            rows: GridAxis::new("Impact", &["High", "Low"]),
+            columns: GridAxis::new("Risk", &["High", "Low"]),
+            boxes: vec![
+                colored_box("1A", "Act Now", "Hard to replace and likely to leave; agree a retention plan.", "#BF4228"),
+                colored_box("1B", "Keep Engaged", "Hard to replace but settled; keep them growing.", "#597864"),
+                colored_box("2A", "Monitor", "Likely to leave but replaceable; prepare cover.", "#FECB86"),
+                colored_box("2B", "Stable", "Settled and replaceable; no action needed.", "#92C0C1"),
+            ],
+        }
+    }
+
+    /// Templates that ship with the application.
+    pub fn built_in() -> Vec<GridTemplate> {
+        vec![GridTemplate::nine_box(), GridTemplate::four_by_four(), GridTemplate::impact_risk()]
+    }
+
+    pub fn row_count(&self) -> u8 {
+        self.rows.len()
+    }
+
+    pub fn column_count(&self) -> u8 {
+        self.columns.len()
+    }
+
+    /// Whether `position` is a box on this grid.
+    pub fn contains(&self, position: GridPosition) -> bool {
+        position.performance < self.row_count() && position.potential < self.column_count()
+    }
+
+    /// Every box on the grid, row by row from the highest row level down and,
+    /// within a row, from the highest column level down.
+    pub fn positions(&self) -> Vec<GridPosition> {
+        (0..self.row_count())
+            .flat_map(|performance| {
+                (0..self.column_count()).map(move |potential| GridPosition { performance, potential })
+            })
+            .collect()
+    }
+
+    /// The per-box settings for `position`, if the template defines any.
+    pub fn grid_box(&self, position: GridPosition) -> Option<&GridBox> {
+        let code = position.code();
+        self.boxes.iter().find(|grid_box| grid_box.id.eq_ignore_ascii_case(&code))
+    }
+
+    /// The label shown for `position`: the box's own label or, failing that, its axis label.
+    /// A position off this grid is shown by its code.
+    pub fn box_label(&self, position: GridPosition) -> String {
+        match self.grid_box(position) {
+            Some(grid_box) if !grid_box.label.is_empty() => grid_box.label.clone(),
+            _ => self.axis_label(position).unwrap_or_else(|| position.code()),
+        }
+    }
+
+    /// The position spelled out on both axes, e.g. `"High Performance / Medium Potential"`.
+    /// `None` if the position is not on this grid.
+    pub fn axis_label(&self, position: GridPosition) -> Option<String> {
+        let row = self.rows.levels.get(position.performance as usize)?;
+        let column = self.columns.levels.get(position.potential as usize)?;
+        Some(format!("{} {} / {} {}", row, self.rows.name, column, self.columns.name))
+    }
+
+    /// Finds the box named by `text`: a short code (`"1A"`), a box label (`"Act Now"`) or an
+    /// axis label with either axis first (`"High Perf / Low Pot"`). Case is ignored.
+    pub fn position(&self, text: &str) -> Result<GridPosition, GridError> {
+        let unknown = || GridError::UnknownBox(text.to_string());
+        if let Ok(position) = GridPosition::from_code(text) {
+            return if self.contains(position) { Ok(position) } else { Err(unknown()) };
+        }
+        let trimmed = text.trim();
+        if let Some(position) = self
+            .positions()
+            .into_iter()
+            .find(|position| self.box_label(*position).eq_ignore_ascii_case(trimmed))
+        {
+            return Ok(position);
+        }
+        self.parse_axis_label(trimmed).ok_or_else(unknown)
+    }
+
+    fn parse_axis_label(&self, label: &str) -> Option<GridPosition> {
+        let mut performance = None;
+        let mut potential = None;
+        for part in label.split('/') {
+            let words: Vec<&str> = part.split_whitespace().collect();
+            let (axis_word, level_words) = words.split_last()?;
+            let level = level_words.join(" ");
+            let (axis, slot) = if self.rows.matches_name(axis_word) {
+                (&self.rows, &mut performance)
+            } else if self.columns.matches_name(axis_word) {
+                (&self.columns, &mut potential)
+            } else {
+                return None;
+            };
+            if slot.replace(axis.level_index(&level)?).is_some() {
+                return None;
+            }
+        }
+        Some(GridPosition { performance: performance?, potential: potential? })
+    }
+
+    /// Checks that both axes have between one and `MAX_GRID_LEVELS` levels and that every
+    /// box entry names a distinct box on the grid.
+    pub fn validate(&self) -> Result<(), GridError> {
+        let invalid = |reason: String| GridError::InvalidTemplate { name: self.name.clone(), reason };
+        for axis in [&self.rows, &self.columns] {
+            if axis.levels.is_empty() || axis.levels.len() > MAX_GRID_LEVELS as usize {
+                return Err(invalid(format!(
+                    "axis '{}' must have between 1 and {} levels",
+                    axis.name, MAX_GRID_LEVELS
+                )));
+            }
+        }
+        let mut seen = HashSet::new();
+        for grid_box in &self.boxes {
+            let position = GridPosition::from_code(&grid_box.id)
+                .ok()
+                .filter(|position| self.contains(*position))
+                .ok_or_else(|| invalid(format!("box '{}' is not on the grid", grid_box.id)))?;
+            if !seen.insert(position) {
+                return Err(invalid(format!("box '{}' is defined more than once", grid_box.id)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An employee whose 9-box value for the seeded cycle names no box on the grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnrecognizedBox {
+    pub user_id: String,
+    pub value: String,
+}
+
+/// What happened when the grid was seeded from a review cycle's 9-box column.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GridSeedReport {
+    pub cycle: String,
+    pub placed: usize,
+    pub missing: Vec<String>, // IDs of employees with no 9-box value for the cycle
+    pub unrecognized: Vec<UnrecognizedBox>,
+}
+
+impl GridSeedReport {
+    /// Number of employees left off the grid.
+    pub fn unplaced_count(&self) -> usize {
+        self.missing.len() + self.unrecognized.len()
+    }
+}
+
+/// Review cycles that carry at least one 9-box value, oldest first.
+pub fn nine_box_cycles(employees: &[Employee]) -> Vec<String> {
+    employees
+        .iter()
+        .flat_map(|employee| &employee.performance_history)
+        .filter(|(_, record)| record.nine_box.is_some())
+        .map(|(cycle, _)| cycle.clone())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+/// Places every employee in the box named by their 9-box value for `cycle`.
+/// Values are read in any known label scheme; employees without a value or with a value
+/// that names no box on `template` are left unplaced and listed in the report.
+pub fn seed_grid_from_cycle(employees: &[Employee], cycle: &str, template: &GridTemplate) -> (GridState, GridSeedReport) {
+    let mut grid_state = GridState::default();
+    let mut report = GridSeedReport {
+        cycle: cycle.to_string(),
+        ..GridSeedReport::default()
+    };
+    for employee in employees {
+        let Some(value) = employee.nine_box(cycle).filter(|value| !value.trim().is_empty()) else {
+            report.missing.push(employee.user_id.clone());
+            continue;
+        };
+        match normalize_box_value(value, template) {
+            Some(position) => {
+                grid_state.assignments.entry(position).or_default().push(employee.user_id.clone());
+                report.placed += 1;
+            }
+            None => report.unrecognized.push(UnrecognizedBox {
+                user_id: employee.user_id.clone(),
+                value: value.to_string(),
+            }),
+        }
+    }
+    (grid_state, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
+    }
+
+    #[test]
+    fn test_positions_cover_the_grid_in_row_order() {
+        let codes: Vec<String> = GridTemplate::nine_box().positions().iter().map(GridPosition::code).collect();
+        assert_eq!(codes, vec!["1A", "1B", "1C", "2A", "2B", "2C", "3A", "3B", "3C"]);
+        assert_eq!(GridTemplate::four_by_four().positions().len(), 16);
+        assert_eq!(GridTemplate::impact_risk().positions().len(), 4);
+    }
+
+    #[test]
+    fn test_parse_codes() {
+        let parsed = GridPosition::from_code("2c").expect("Failed to parse code");
+        assert_eq!((parsed.performance(), parsed.potential()), (1, 2));
+        assert_eq!(parsed.to_string(), "2C");
+        for code in ["", "1", "0A", "1J", "A1", "1AA", "TestBox_Assign"] {
+            assert_eq!(GridPosition::from_code(code), Err(GridError::UnknownBox(code.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_template_rejects_boxes_outside_the_grid() {
+        let nine_box = GridTemplate::nine_box();
+        assert_eq!(nine_box.position("3c"), Ok(position("3C")));
+        assert_eq!(nine_box.position("4A"), Err(GridError::UnknownBox("4A".to_string())));
+        assert_eq!(GridTemplate::four_by_four().position("4D"), Ok(position("4D")));
+        assert!(GridTemplate::impact_risk().position("2C").is_err());
+
+        assert_eq!(nine_box.axis_label(position("4A")), None);
+        assert_eq!(nine_box.axis_label(position("1D")), None);
+        assert_eq!(nine_box.box_label(position("4A")), "4A", "Off-grid boxes fall back to their code");
+        assert_eq!(GridTemplate::impact_risk().axis_label(position("2C")), None);
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        let nine_box = GridTemplate::nine_box();
+        for grid_position in nine_box.positions() {
+            assert_eq!(nine_box.position(&nine_box.box_label(grid_position)), Ok(grid_position));
+            assert_eq!(nine_box.position(&nine_box.axis_label(grid_position).unwrap()), Ok(grid_position));
+        }
+        assert_eq!(nine_box.position("Moderate Potential / High Performance"), Ok(position("1B")));
+        assert_eq!(nine_box.position("low perf / low pot"), Ok(position("3C")));
+        for label in ["High Perf", "High Perf / High Perf", "Top Perf / Low Pot", "High Perf / Low Pot / Low Pot"] {
+            assert!(nine_box.position(label).is_err(), "'{}' should be rejected", label);
+        }
+
+        let impact_risk = GridTemplate::impact_risk();
+        assert_eq!(impact_risk.position("act now"), Ok(position("1A")));
+        assert_eq!(impact_risk.position("Low Risk / High Impact"), Ok(position("1B")));
+
+        let four_by_four = GridTemplate::four_by_four();
+        assert_eq!(four_by_four.box_label(position("1A")), "Exceptional Performance / Very High Potential");
+        assert_eq!(four_by_four.position("Very High Pot / Developing Perf"), Ok(position("4A")));
+    }
+
+    #[test]
+    fn test_validate_templates() {
+        for template in GridTemplate::built_in() {
+            assert!(template.validate().is_ok(), "Built-in template '{}' is invalid", template.name);
+        }
+
+        let mut template = GridTemplate::impact_risk();
+        template.boxes[3].id = "3C".to_string();
+        assert!(matches!(template.validate(), Err(GridError::InvalidTemplate { .. })));
+
+        let mut template = GridTemplate::impact_risk();
+        template.boxes[1].id = "1a".to_string();
+        assert!(matches!(template.validate(), Err(GridError::InvalidTemplate { .. })));
+
+        let mut template = GridTemplate::nine_box();
+        template.rows.levels.clear();
+        assert!(matches!(template.validate(), Err(GridError::InvalidTemplate { .. })));
+    }
+
+    #[test]
+    fn test_serializes_as_code_and_rejects_unknown_boxes() {
+        let mut assignments = HashMap::new();
+        assignments.insert(position("1A"), vec!["emp1".to_string()]);
+        let json = serde_json::to_string(&assignments).expect("Failed to serialize assignments");
+        assert_eq!(json, r#"{"1A":["emp1"]}"#);
+
+        let restored: HashMap<GridPosition, Vec<String>> =
+            serde_json::from_str(&json).expect("Failed to deserialize assignments");
+        assert_eq!(restored, assignments);
+
+        let unknown: Result<HashMap<GridPosition, Vec<String>>, _> = serde_json::from_str(r#"{"TestBox":[]}"#);
+        assert!(unknown.is_err(), "Unknown boxes should be rejected");
+    }
+
+    #[test]
+    fn test_seed_grid_from_cycle_reports_unplaced_employees() {
+        let employee = |id: &str, cycle: &str, value: &str| {
+            let mut employee = Employee {
+                user_id: id.to_string(),
+                ..Employee::default()
+            };
+            employee.cycle_mut(cycle).nine_box = Some(value.to_string());
+            employee
+        };
+        let employees = vec![
+            employee("u1", "2025", "1A"),
+            employee("u2", "2025", "Solid Talent"),
+            employee("u3", "2025", "Growth Potential"),
+            employee("u4", "2024", "3C"),
+            employee("u5", "2025", "Star"),
+        ];
+        assert_eq!(nine_box_cycles(&employees), vec!["2024", "2025"]);
+
+        let (grid_state, report) = seed_grid_from_cycle(&employees, "2025", &GridTemplate::nine_box());
+        assert_eq!(grid_state.assignments.get(&position("1A")), Some(&vec!["u1".to_string(), "u5".to_string()]));
+        assert_eq!(grid_state.assignments.get(&position("2B")), Some(&vec!["u2".to_string()]));
+        assert_eq!(report.placed, 3);
+        assert_eq!(report.missing, vec!["u4"]);
+        assert_eq!(
+            report.unrecognized,
+            vec![UnrecognizedBox { user_id: "u3".to_string(), value: "Growth Potential".to_string() }]
+        );
+        assert_eq!(report.unplaced_count(), 2);
+    }
+}
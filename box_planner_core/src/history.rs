@@ -0,0 +1,351 @@
+use crate::csv_processing::EmployeeField;
+use crate::grid::GridPosition;
+use crate::models::{Employee, GridState, RoleRequirement, Skill};
+use crate::succession::SuccessionPlan;
+use std::collections::{BTreeSet, HashMap};
+
+/// One reversible mutation of the session. Each change stores the value before and after,
+/// so it can be applied in either direction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The employee was assigned to, moved between or removed from grid boxes.
+    Placement {
+        employee_id: String,
+        from: Option<GridPosition>,
+        to: Option<GridPosition>,
+    },
+    /// The employee's note (the per-employee note file, not the Notes column).
+    Note {
+        employee_id: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// An employee column, in the text form `EmployeeField::value` reads it as.
+    Field {
+        employee_id: String,
+        field: EmployeeField,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// The 9-box value of one review cycle.
+    NineBox {
+        employee_id: String,
+        cycle: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// The whole skills catalog.
+    SkillCatalog { from: Vec<Skill>, to: Vec<Skill> },
+    /// The skills every position requires.
+    RoleRequirements {
+        from: Vec<RoleRequirement>,
+        to: Vec<RoleRequirement>,
+    },
+    /// The whole succession plan.
+    Succession { from: SuccessionPlan, to: SuccessionPlan },
+}
+
+/// The session state an edit is applied to.
+#[derive(Debug)]
+pub struct EditTarget<'a> {
+    pub employees: &'a mut [Employee],
+    pub grid_state: &'a mut GridState,
+    pub notes: &'a mut HashMap<String, String>,
+    pub skills: &'a mut Vec<Skill>,
+    pub role_requirements: &'a mut Vec<RoleRequirement>,
+    pub succession: &'a mut SuccessionPlan,
+}
+
+impl Change {
+    /// The employee the change is about; `None` for session-wide changes.
+    pub fn employee_id(&self) -> Option<&str> {
+        match self {
+            Change::Placement { employee_id, .. }
+            | Change::Note { employee_id, .. }
+            | Change::Field { employee_id, .. }
+            | Change::NineBox { employee_id, .. } => Some(employee_id),
+            Change::SkillCatalog { .. } | Change::RoleRequirements { .. } | Change::Succession { .. } => None,
+        }
+    }
+
+    /// Whether applying the change would leave everything as it was.
+    pub fn is_noop(&self) -> bool {
+        match self {
+            Change::Placement { from, to, .. } => from == to,
+            Change::Note { from, to, .. } | Change::Field { from, to, .. } | Change::NineBox { from, to, .. } => from == to,
+            Change::SkillCatalog { from, to } => from == to,
+            Change::RoleRequirements { from, to } => from == to,
+            Change::Succession { from, to } => from == to,
+        }
+    }
+
+    /// The same change in the opposite direction.
+    pub fn inverse(&self) -> Change {
+        let mut inverse = self.clone();
+        match &mut inverse {
+            Change::Placement { from, to, .. } => std::mem::swap(from, to),
+            Change::Note { from, to, .. } | Change::Field { from, to, .. } | Change::NineBox { from, to, .. } => {
+                std::mem::swap(from, to)
+            }
+            Change::SkillCatalog { from, to } => std::mem::swap(from, to),
+            Change::RoleRequirements { from, to } => std::mem::swap(from, to),
+            Change::Succession { from, to } => std::mem::swap(from, to),
+        }
+        inverse
+    }
+
+    /// Sets the changed value to its `to` side. Changes to employees that are no longer in
+    /// the list are skipped.
+    pub fn apply(&self, target: &mut EditTarget) {
+        match self {
+            Change::Placement { employee_id, to, .. } => {
+                target.grid_state.place(employee_id, *to);
+            }
+            Change::Note { employee_id, to, .. } => match to {
+                Some(note) => {
+                    target.notes.insert(employee_id.clone(), note.clone());
+                }
+                None => {
+                    target.notes.remove(employee_id);
+                }
+            },
+            Change::Field { employee_id, field, to, .. } => {
+                if let Some(employee) = target.employees.iter_mut().find(|employee| employee.user_id == *employee_id) {
+                    // Values were read with `EmployeeField::value`, so they always parse back
+                    let _ = field.set_value(employee, to.clone());
+                }
+            }
+            Change::NineBox { employee_id, cycle, to, .. } => {
+                if let Some(employee) = target.employees.iter_mut().find(|employee| employee.user_id == *employee_id) {
+                    employee.cycle_mut(cycle).nine_box = to.clone();
+                }
+            }
+            Change::SkillCatalog { to, .. } => *target.skills = to.clone(),
+            Change::RoleRequirements { to, .. } => *target.role_requirements = to.clone(),
+            Change::Succession { to, .. } => *target.succession = to.clone(),
+        }
+    }
+}
+
+/// The changes `after` makes to `before`: one `Field` change per differing column and one
+/// `NineBox` change per cycle whose 9-box value differs. Both must be the same employee.
+pub fn employee_changes(before: &Employee, after: &Employee) -> Vec<Change> {
+    let employee_id = &before.user_id;
+    let mut changes: Vec<Change> = EmployeeField::ALL
+        .iter()
+        .filter(|field| **field != EmployeeField::UserId)
+        .map(|field| Change::Field {
+            employee_id: employee_id.clone(),
+            field: *field,
+            from: field.value(before),
+            to: field.value(after),
+        })
+        .collect();
+    let cycles: BTreeSet<&String> = before.performance_history.keys().chain(after.performance_history.keys()).collect();
+    changes.extend(cycles.into_iter().map(|cycle| Change::NineBox {
+        employee_id: employee_id.clone(),
+        cycle: cycle.clone(),
+        from: before.cycle(cycle).and_then(|record| record.nine_box.clone()),
+        to: after.cycle(cycle).and_then(|record| record.nine_box.clone()),
+    }));
+    changes.retain(|change| !change.is_noop());
+    changes
+}
+
+/// A group of changes undone and redone as one step, e.g. a grid move together with the
+/// 9-box value it updates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub description: String, // Shown on the Undo/Redo buttons, e.g. "Move Jane Smith"
+    pub changes: Vec<Change>,
+}
+
+impl Edit {
+    pub fn new(description: &str, changes: Vec<Change>) -> Self {
+        Edit {
+            description: description.to_string(),
+            changes,
+        }
+    }
+
+    fn apply(&self, target: &mut EditTarget) {
+        for change in &self.changes {
+            change.apply(target);
+        }
+    }
+
+    fn inverse(&self) -> Edit {
+        Edit {
+            description: self.description.clone(),
+            changes: self.changes.iter().rev().map(Change::inverse).collect(),
+        }
+    }
+}
+
+/// Unlimited undo and redo over recorded edits. Recording a new edit discards the redo stack.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+}
+
+impl History {
+    /// Records an edit that has already been applied. Changes that changed nothing are
+    /// dropped; returns `false`, recording nothing, if none are left.
+    pub fn record(&mut self, mut edit: Edit) -> bool {
+        edit.changes.retain(|change| !change.is_noop());
+        if edit.changes.is_empty() {
+            return false;
+        }
+        self.undo_stack.push(edit);
+        self.redo_stack.clear();
+        true
+    }
+
+    /// Applies `edit` and records it. Returns whether anything changed.
+    pub fn apply(&mut self, edit: Edit, target: &mut EditTarget) -> bool {
+        edit.apply(target);
+        self.record(edit)
+    }
+
+    /// Reverts the most recent edit. Returns the edit that was undone, if there was one.
+    pub fn undo(&mut self, target: &mut EditTarget) -> Option<&Edit> {
+        let edit = self.undo_stack.pop()?;
+        edit.inverse().apply(target);
+        self.redo_stack.push(edit);
+        self.redo_stack.last()
+    }
+
+    /// Re-applies the most recently undone edit. Returns it, if there was one.
+    pub fn redo(&mut self, target: &mut EditTarget) -> Option<&Edit> {
+        let edit = self.redo_stack.pop()?;
+        edit.apply(target);
+        self.undo_stack.push(edit);
+        self.undo_stack.last()
+    }
+
+    /// The edit `undo` would revert.
+    pub fn next_undo(&self) -> Option<&Edit> {
+        self.undo_stack.last()
+    }
+
+    /// The edit `redo` would re-apply.
+    pub fn next_redo(&self) -> Option<&Edit> {
+        self.redo_stack.last()
+    }
+
+    /// Forgets all edits, e.g. after the employee list is replaced.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::retention::AttritionRisk;
+
+    fn position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
+    }
+
+    #[derive(Default)]
+    struct Session {
+        employees: Vec<Employee>,
+        grid_state: GridState,
+        notes: HashMap<String, String>,
+        skills: Vec<Skill>,
+        role_requirements: Vec<RoleRequirement>,
+        succession: SuccessionPlan,
+    }
+
+    impl Session {
+        fn target(&mut self) -> EditTarget<'_> {
+            EditTarget {
+                employees: &mut self.employees,
+                grid_state: &mut self.grid_state,
+                notes: &mut self.notes,
+                skills: &mut self.skills,
+                role_requirements: &mut self.role_requirements,
+                succession: &mut self.succession,
+            }
+        }
+    }
+
+    fn employee(id: &str) -> Employee {
+        Employee {
+            user_id: id.to_string(),
+            first_name: "Ann".to_string(),
+            ..Employee::default()
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_grid_moves() {
+        let mut session = Session { employees: vec![employee("u1")], ..Session::default() };
+        let mut history = History::default();
+
+        let assign = Edit::new(
+            "Assign Ann",
+            vec![Change::Placement { employee_id: "u1".to_string(), from: None, to: Some(position("2B")) }],
+        );
+        assert!(history.apply(assign, &mut session.target()));
+        let moved = Edit::new(
+            "Move Ann",
+            vec![
+                Change::Placement { employee_id: "u1".to_string(), from: Some(position("2B")), to: Some(position("1A")) },
+                Change::NineBox { employee_id: "u1".to_string(), cycle: "2024".to_string(), from: None, to: Some("1A".to_string()) },
+            ],
+        );
+        assert!(history.apply(moved, &mut session.target()));
+        assert_eq!(session.grid_state.position_of("u1"), Some(position("1A")));
+        assert_eq!(session.employees[0].nine_box("2024"), Some("1A"));
+
+        let undone = history.undo(&mut session.target()).map(|edit| edit.description.clone());
+        assert_eq!(undone.as_deref(), Some("Move Ann"));
+        assert_eq!(session.grid_state.position_of("u1"), Some(position("2B")));
+        assert_eq!(session.employees[0].nine_box("2024"), None);
+
+        history.undo(&mut session.target());
+        assert_eq!(session.grid_state.position_of("u1"), None);
+        assert!(session.grid_state.assignments.is_empty(), "Unassigning leaves no empty boxes behind");
+        assert!(history.undo(&mut session.target()).is_none());
+
+        history.redo(&mut session.target());
+        history.redo(&mut session.target());
+        assert_eq!(session.grid_state.position_of("u1"), Some(position("1A")));
+        assert!(history.next_redo().is_none());
+    }
+
+    #[test]
+    fn test_field_and_note_edits_are_reversible() {
+        let before = employee("u1");
+        let mut after = before.clone();
+        after.attrition_risk = Some(AttritionRisk::High);
+        after.set_skill_level("Rust", Some(4));
+        let changes = employee_changes(&before, &after);
+        assert_eq!(changes.len(), 2, "One change per edited column: {:?}", changes);
+
+        let mut session = Session { employees: vec![after], ..Session::default() };
+        let mut history = History::default();
+        assert!(history.record(Edit::new("Edit Ann", changes)));
+        assert!(history.apply(
+            Edit::new("Note", vec![Change::Note { employee_id: "u1".to_string(), from: None, to: Some("Promote".to_string()) }]),
+            &mut session.target()
+        ));
+        assert!(!history.record(Edit::new("Nothing", employee_changes(&before, &before))), "No-op edits are not recorded");
+
+        history.undo(&mut session.target());
+        assert!(session.notes.is_empty());
+        history.undo(&mut session.target());
+        assert_eq!(session.employees[0], before);
+
+        history.redo(&mut session.target());
+        assert_eq!(session.employees[0].skill_level("Rust"), Some(4));
+        assert_eq!(session.employees[0].attrition_risk, Some(AttritionRisk::High));
+
+        history.record(Edit::new("New", vec![Change::Note { employee_id: "u1".to_string(), from: None, to: Some("x".to_string()) }]));
+        assert!(history.next_redo().is_none(), "A new edit discards the redo stack");
+    }
+}
@@ -0,0 +1,250 @@
+use crate::grid::{GridPosition, GridTemplate};
+use crate::models::Employee;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Name of the scheme that shows each template's own box labels.
+pub const DESCRIPTIVE_SCHEME_NAME: &str = "Descriptive";
+
+/// What a scheme shows for a box it has no label for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum LabelFallback {
+    /// The grid template's label for the box (e.g. "High Perf / High Pot").
+    #[default]
+    TemplateLabel,
+    /// The box's short code (e.g. "1A").
+    Code,
+}
+
+/// A naming convention for grid boxes. Historical data mixes several of these
+/// (codes such as "1A", names such as "Top Talent" or "Star"), so every scheme
+/// can also be read back into a `GridPosition`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LabelScheme {
+    pub name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<GridPosition, String>,
+    #[serde(default)]
+    pub fallback: LabelFallback,
+}
+
+impl Default for LabelScheme {
+    fn default() -> Self {
+        LabelScheme::descriptive()
+    }
+}
+
+impl LabelScheme {
+    /// Short codes such as "1A" (performance row digit, potential column letter).
+    pub fn codes() -> Self {
+        LabelScheme {
+            name: "Codes".to_string(),
+            labels: BTreeMap::new(),
+            fallback: LabelFallback::Code,
+        }
+    }
+
+    /// The labels defined by the grid template itself.
+    pub fn descriptive() -> Self {
+        LabelScheme {
+            name: DESCRIPTIVE_SCHEME_NAME.to_string(),
+            labels: BTreeMap::new(),
+            fallback: LabelFallback::TemplateLabel,
+        }
+    }
+
+    /// The 9-box names used on employee cards in the previous (Swift) version of the tool.
+    pub fn performer() -> Self {
+        LabelScheme::nine_box_names(
+            "Star / Core Player",
+            [
+                ["Star", "High Potential", "Potential Gem"],
+                ["High Performer", "Core Player", "Inconsistent Player"],
+                ["Solid Performer", "Average Performer", "Risk"],
+            ],
+        )
+    }
+
+    /// The 9-box names used on the grid in the previous (Swift) version of the tool.
+    pub fn talent() -> Self {
+        LabelScheme::nine_box_names(
+            "Top Talent / Solid Talent",
+            [
+                ["Top Talent", "Emerging Talent", "Master/Mentor"],
+                ["Rising Talent", "Solid Talent", "Professional"],
+                ["Budding Talent", "Misaligned Talent", "Low Performer"],
+            ],
+        )
+    }
+
+    // `names` is indexed [potential][performance], highest level first, matching how the
+    // Swift app laid its grid out (potential rows, performance columns).
+    fn nine_box_names(name: &str, names: [[&str; 3]; 3]) -> Self {
+        let mut labels = BTreeMap::new();
+        for (potential, row) in names.iter().enumerate() {
+            for (performance, label) in row.iter().enumerate() {
+                if let Some(position) = GridPosition::new(performance as u8, potential as u8) {
+                    labels.insert(position, label.to_string());
+                }
+            }
+        }
+        LabelScheme {
+            name: name.to_string(),
+            labels,
+            fallback: LabelFallback::TemplateLabel,
+        }
+    }
+
+    /// Schemes that ship with the application.
+    pub fn built_in() -> Vec<LabelScheme> {
+        vec![
+            LabelScheme::descriptive(),
+            LabelScheme::codes(),
+            LabelScheme::performer(),
+            LabelScheme::talent(),
+        ]
+    }
+
+    /// Whether this scheme can name the boxes of `template`: it either defines no labels
+    /// of its own or labels exactly the template's boxes.
+    pub fn fits(&self, template: &GridTemplate) -> bool {
+        let positions: BTreeSet<GridPosition> = template.positions().into_iter().collect();
+        self.labels.is_empty() || self.labels.keys().copied().eq(positions)
+    }
+
+    /// The label this scheme shows for `position` on `template`.
+    pub fn label(&self, position: GridPosition, template: &GridTemplate) -> String {
+        if self.fits(template) {
+            if let Some(label) = self.labels.get(&position) {
+                return label.clone();
+            }
+        }
+        match self.fallback {
+            LabelFallback::TemplateLabel => template.box_label(position),
+            LabelFallback::Code => position.code(),
+        }
+    }
+
+    /// The box this scheme labels `text` (case and surrounding whitespace are ignored).
+    pub fn position(&self, text: &str) -> Option<GridPosition> {
+        let text = text.trim();
+        self.labels
+            .iter()
+            .find(|(_, label)| label.eq_ignore_ascii_case(text))
+            .map(|(position, _)| *position)
+    }
+}
+
+/// Reads a 9-box value written in any known form: a code or label of `template`,
+/// or a label from any built-in scheme that fits it.
+pub fn normalize_box_value(value: &str, template: &GridTemplate) -> Option<GridPosition> {
+    if let Ok(position) = template.position(value) {
+        return Some(position);
+    }
+    LabelScheme::built_in()
+        .iter()
+        .filter(|scheme| scheme.fits(template))
+        .find_map(|scheme| scheme.position(value))
+}
+
+/// Rewrites every recognised per-cycle 9-box value as its short code, so that data
+/// imported with mixed labels compares and exports consistently. Unrecognised values
+/// are left untouched. Returns the number of values that were rewritten.
+pub fn normalize_nine_boxes(employees: &mut [Employee], template: &GridTemplate) -> usize {
+    let mut rewritten = 0;
+    for record in employees.iter_mut().flat_map(|employee| employee.performance_history.values_mut()) {
+        let Some(value) = record.nine_box.as_mut() else { continue };
+        if let Some(position) = normalize_box_value(value, template) {
+            let code = position.code();
+            if *value != code {
+                *value = code;
+                rewritten += 1;
+            }
+        }
+    }
+    rewritten
+}
+
+/// A copy of `employees` with every recognised 9-box value written in `scheme`, for export.
+pub fn relabel_nine_boxes(employees: &[Employee], template: &GridTemplate, scheme: &LabelScheme) -> Vec<Employee> {
+    let mut relabelled = employees.to_vec();
+    for record in relabelled.iter_mut().flat_map(|employee| employee.performance_history.values_mut()) {
+        if let Some(value) = record.nine_box.as_mut() {
+            if let Some(position) = normalize_box_value(value, template) {
+                *value = scheme.label(position, template);
+            }
+        }
+    }
+    relabelled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
+    }
+
+    #[test]
+    fn test_swift_schemes_map_names_to_positions() {
+        let talent = LabelScheme::talent();
+        assert_eq!(talent.position("Top Talent"), Some(position("1A")));
+        assert_eq!(talent.position("master/mentor"), Some(position("3A")));
+        assert_eq!(talent.position("Budding Talent"), Some(position("1C")));
+        assert_eq!(talent.position("Low Performer"), Some(position("3C")));
+
+        let performer = LabelScheme::performer();
+        assert_eq!(performer.position("Star"), Some(position("1A")));
+        assert_eq!(performer.position("Potential Gem"), Some(position("3A")));
+        assert_eq!(performer.position("Solid Performer"), Some(position("1C")));
+        assert_eq!(performer.position("Risk"), Some(position("3C")));
+        assert_eq!(performer.position("Top Talent"), None);
+    }
+
+    #[test]
+    fn test_labels_follow_scheme_and_template() {
+        let nine_box = GridTemplate::nine_box();
+        assert_eq!(LabelScheme::codes().label(position("2B"), &nine_box), "2B");
+        assert_eq!(LabelScheme::descriptive().label(position("2B"), &nine_box), "Med Perf / Med Pot");
+        assert_eq!(LabelScheme::talent().label(position("2B"), &nine_box), "Solid Talent");
+
+        // Nine-box names do not fit other grid shapes, so the template's labels are used instead
+        let impact_risk = GridTemplate::impact_risk();
+        assert!(!LabelScheme::talent().fits(&impact_risk));
+        assert_eq!(LabelScheme::talent().label(position("1A"), &impact_risk), "Act Now");
+    }
+
+    #[test]
+    fn test_normalize_mixed_values() {
+        let nine_box = GridTemplate::nine_box();
+        let mut employee = Employee::default();
+        employee.cycle_mut("2023").nine_box = Some("1a".to_string());
+        employee.cycle_mut("2024").nine_box = Some("Top Talent".to_string());
+        employee.cycle_mut("2025").nine_box = Some("Growth Potential".to_string());
+        let mut colleague = Employee::default();
+        colleague.cycle_mut("2024").nine_box = Some("Solid Performer".to_string());
+        colleague.cycle_mut("2025").nine_box = Some("Low Perf / Low Pot".to_string());
+        let mut employees = vec![employee, colleague];
+
+        assert_eq!(normalize_nine_boxes(&mut employees, &nine_box), 4);
+        assert_eq!(employees[0].nine_box("2023"), Some("1A"));
+        assert_eq!(employees[0].nine_box("2024"), Some("1A"));
+        assert_eq!(employees[0].nine_box("2025"), Some("Growth Potential"), "Unknown values are kept as-is");
+        assert_eq!(employees[1].nine_box("2024"), Some("1C"));
+        assert_eq!(employees[1].nine_box("2025"), Some("3C"));
+
+        let exported = relabel_nine_boxes(&employees, &nine_box, &LabelScheme::performer());
+        assert_eq!(exported[0].nine_box("2024"), Some("Star"));
+        assert_eq!(exported[1].nine_box("2025"), Some("Risk"));
+        assert_eq!(employees[0].nine_box("2024"), Some("1A"), "Relabelling must not touch the originals");
+    }
+
+    #[test]
+    fn test_scheme_json_round_trip() {
+        let scheme = LabelScheme::talent();
+        let json = serde_json::to_string(&scheme).expect("Failed to serialize scheme");
+        let restored: LabelScheme = serde_json::from_str(&json).expect("Failed to deserialize scheme");
+        assert_eq!(restored, scheme);
+    }
+}
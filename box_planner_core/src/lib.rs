@@ -1,13 +1,57 @@
 pub mod models;
 pub mod csv_processing;
 pub mod persistence;
+pub mod mapping;
+pub mod grid;
+pub mod labels;
+pub mod skills;
+pub mod succession;
+pub mod retention;
+pub mod org;
+pub mod history;
+pub mod workspace;
+pub mod events;
+pub mod notes;
 
 pub use models::{
-    Employee, Skill, GridBox, GridState, AppSettings
+    Employee, CycleRecord, CustomField, Skill, SkillRequirement, RoleRequirement, MIN_PROFICIENCY, MAX_PROFICIENCY, GridBox, GridState, AppSettings, ProjectDocument, ProjectSettings,
+    PROJECT_FORMAT_VERSION, PROJECT_FILE_EXTENSION
 };
 pub use csv_processing::{
-    import_employees_from_csv, export_employees_to_csv
+    import_employees_from_csv, export_employees_to_csv, import_employees_with_profile,
+    export_employees_with_profile, import_employees_with_report, read_csv_preview, CsvImportError,
+    CsvPreview, EmployeeField, CycleField, SKILLS_SEPARATOR, SKILL_LEVEL_SEPARATOR,
+    SKILL_HELD_MARKER, export_skills_matrix, ImportOutcome, ImportReport, RejectedRow, DuplicateId,
+    UnknownManager
 };
+pub use mapping::{HeaderRule, MappingProfile, ColumnTarget};
+pub use grid::{
+    GridPosition, GridError, GridAxis, GridTemplate, GridSeedReport, UnrecognizedBox, MAX_GRID_LEVELS,
+    nine_box_cycles, seed_grid_from_cycle
+};
+pub use labels::{
+    LabelScheme, LabelFallback, normalize_box_value, normalize_nine_boxes, relabel_nine_boxes
+};
+pub use skills::{
+    skill_id, find_skill, add_skill_to_catalog, register_employee_skills, remove_skill_from_catalog,
+    employees_with_skill, proficiency_label, find_role_requirement, set_role_requirement, SkillGap, skill_gaps,
+    employee_skill_gaps, TeamSkillGap, team_skill_gaps
+};
+pub use succession::{
+    Criticality, Readiness, Successor, CriticalRole, BenchStrength, SuccessionPlan, SuccessionImport,
+    UnresolvedSuccessor, SUCCESSORS_SEPARATOR, succession_from_custom_fields, write_succession_fields
+};
+pub use retention::{
+    AttritionRisk, ImpactOfLoss, risk_matrix_template, risk_position, set_risk_position, risk_matrix
+};
+pub use org::{OrgTree, DanglingManager, employees_under};
+pub use history::{Change, Edit, EditTarget, History, employee_changes};
+pub use workspace::{Workspace, WorkspaceError, ImportSummary};
+pub use events::{WorkspaceEvent, EventBus, EventListener};
+pub use notes::{employee_id_from_note_file, note_file_name, NoteCategory, NoteEntry, NoteHistory, utc_timestamp};
 pub use persistence::{
-    save_employee_note, load_employee_note, save_app_settings, load_app_settings
+    save_employee_note, append_employee_note, load_employee_note, load_note_history, save_note_history, delete_employee_note, migrate_note_files, NoteFileMigration, save_app_settings, load_app_settings,
+    save_grid_state, load_grid_state, load_all_employee_notes, save_project, load_project,
+    save_grid_template, load_grid_template, save_skills_catalog, load_skills_catalog,
+    save_role_requirements, load_role_requirements, save_succession_plan, load_succession_plan
 };
@@ -0,0 +1,207 @@
+use crate::csv_processing::{match_cycle_header, CycleField, EmployeeField};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Name of the built-in profile that matches the headers written by `export_employees_to_csv`.
+pub const STANDARD_PROFILE_NAME: &str = "Standard";
+
+/// The header a target is written to, plus any alternative headers accepted on import.
+/// Per-cycle rules use `{cycle}` in place of the cycle name (e.g. `"PR {cycle}"`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HeaderRule {
+    pub header: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl HeaderRule {
+    pub fn new(header: &str) -> Self {
+        HeaderRule {
+            header: header.to_string(),
+            aliases: Vec::new(),
+        }
+    }
+
+    pub fn with_aliases(header: &str, aliases: &[&str]) -> Self {
+        HeaderRule {
+            header: header.to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        }
+    }
+
+    /// All headers accepted on import: the main header followed by its aliases.
+    pub fn accepted_headers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.header.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    /// Whether `header` names this target. Surrounding whitespace and ASCII case are ignored.
+    pub fn matches(&self, header: &str) -> bool {
+        let header = header.trim();
+        self.accepted_headers()
+            .any(|accepted| accepted.trim().eq_ignore_ascii_case(header))
+    }
+
+    /// The cycle named by `header` if it matches one of this rule's templates.
+    pub fn match_cycle(&self, header: &str) -> Option<String> {
+        let header = header.trim();
+        self.accepted_headers()
+            .find_map(|template| match_cycle_header(template.trim(), header))
+    }
+}
+
+/// What a single source column is imported into.
+/// Columns that match no rule are kept verbatim as custom fields.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnTarget {
+    Field(EmployeeField),
+    Cycle(CycleField, String),
+    Custom,
+}
+
+impl ColumnTarget {
+    /// A short human-readable description, e.g. "Rating 2024".
+    pub fn describe(&self) -> String {
+        match self {
+            ColumnTarget::Field(field) => field.default_header().to_string(),
+            ColumnTarget::Cycle(cycle_field, cycle) => format!("{} {}", cycle_field.label(), cycle),
+            ColumnTarget::Custom => "(custom field)".to_string(),
+        }
+    }
+}
+
+/// A named set of header rules mapping CSV columns onto `Employee` fields.
+/// Fields without a rule fall back to their standard header.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MappingProfile {
+    pub name: String,
+    #[serde(default)]
+    pub fields: BTreeMap<EmployeeField, HeaderRule>,
+    #[serde(default)]
+    pub cycle_fields: BTreeMap<CycleField, HeaderRule>,
+}
+
+impl Default for MappingProfile {
+    fn default() -> Self {
+        MappingProfile::standard()
+    }
+}
+
+impl MappingProfile {
+    /// The profile matching the headers this application writes by default.
+    pub fn standard() -> Self {
+        MappingProfile {
+            name: STANDARD_PROFILE_NAME.to_string(),
+            fields: EmployeeField::ALL
+                .into_iter()
+                .map(|field| (field, HeaderRule::new(field.default_header())))
+                .collect(),
+            cycle_fields: CycleField::ALL
+                .into_iter()
+                .map(|cycle_field| (cycle_field, HeaderRule::new(cycle_field.default_header_template())))
+                .collect(),
+        }
+    }
+
+    /// The layout of the legacy HRIS export read by the previous (Swift) version of the tool.
+    pub fn legacy_hris() -> Self {
+        let mut profile = MappingProfile::standard();
+        profile.name = "Legacy HRIS".to_string();
+        profile.fields.insert(EmployeeField::ManagerId, HeaderRule::with_aliases("Reports to", &["Manager ID"]));
+        profile.fields.insert(EmployeeField::Department, HeaderRule::with_aliases("Dept", &["Department"]));
+        profile.fields.insert(EmployeeField::HireDate, HeaderRule::with_aliases("Last Hire Date", &["Hire Date"]));
+        profile.fields.insert(EmployeeField::Notes, HeaderRule::with_aliases("PR Notes", &["Notes"]));
+        profile.cycle_fields.insert(CycleField::Rating, HeaderRule::with_aliases("PR {cycle}", &["PR{cycle}"]));
+        profile.cycle_fields.insert(
+            CycleField::NineBox,
+            HeaderRule::with_aliases("User  9Box {cycle}", &["User 9Box {cycle}"]),
+        );
+        profile
+    }
+
+    /// Profiles that ship with the application.
+    pub fn built_in() -> Vec<MappingProfile> {
+        vec![MappingProfile::standard(), MappingProfile::legacy_hris()]
+    }
+
+    /// The rule for `field`, falling back to its standard header.
+    pub fn field_rule(&self, field: EmployeeField) -> HeaderRule {
+        self.fields
+            .get(&field)
+            .cloned()
+            .unwrap_or_else(|| HeaderRule::new(field.default_header()))
+    }
+
+    /// The rule for `cycle_field`, falling back to its standard header template.
+    pub fn cycle_rule(&self, cycle_field: CycleField) -> HeaderRule {
+        self.cycle_fields
+            .get(&cycle_field)
+            .cloned()
+            .unwrap_or_else(|| HeaderRule::new(cycle_field.default_header_template()))
+    }
+
+    /// Decides which target a source column with `header` is imported into.
+    /// Fixed fields take precedence over per-cycle templates.
+    pub fn resolve_column(&self, header: &str) -> ColumnTarget {
+        if let Some(field) = EmployeeField::ALL
+            .into_iter()
+            .find(|field| self.field_rule(*field).matches(header))
+        {
+            return ColumnTarget::Field(field);
+        }
+        for cycle_field in CycleField::ALL {
+            if let Some(cycle) = self.cycle_rule(cycle_field).match_cycle(header) {
+                return ColumnTarget::Cycle(cycle_field, cycle);
+            }
+        }
+        ColumnTarget::Custom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_profile_resolves_default_headers() {
+        let profile = MappingProfile::standard();
+        assert_eq!(profile.resolve_column("User ID"), ColumnTarget::Field(EmployeeField::UserId));
+        assert_eq!(profile.resolve_column(" manager id "), ColumnTarget::Field(EmployeeField::ManagerId));
+        assert_eq!(profile.resolve_column("PR2024"), ColumnTarget::Cycle(CycleField::Rating, "2024".to_string()));
+        assert_eq!(profile.resolve_column("PR Group 2025"), ColumnTarget::Cycle(CycleField::Group, "2025".to_string()));
+        assert_eq!(profile.resolve_column("PR 2024"), ColumnTarget::Custom);
+        assert_eq!(profile.resolve_column("Grade"), ColumnTarget::Custom);
+    }
+
+    #[test]
+    fn test_legacy_profile_resolves_hris_headers_and_aliases() {
+        let profile = MappingProfile::legacy_hris();
+        assert_eq!(profile.resolve_column("PR 2021"), ColumnTarget::Cycle(CycleField::Rating, "2021".to_string()));
+        assert_eq!(profile.resolve_column("PR2022"), ColumnTarget::Cycle(CycleField::Rating, "2022".to_string()));
+        assert_eq!(
+            profile.resolve_column("User  9Box 2024"),
+            ColumnTarget::Cycle(CycleField::NineBox, "2024".to_string())
+        );
+        assert_eq!(profile.resolve_column("Reports to"), ColumnTarget::Field(EmployeeField::ManagerId));
+        assert_eq!(profile.resolve_column("Manager ID"), ColumnTarget::Field(EmployeeField::ManagerId));
+        assert_eq!(profile.resolve_column("Dept"), ColumnTarget::Field(EmployeeField::Department));
+    }
+
+    #[test]
+    fn test_missing_rules_fall_back_to_standard_headers() {
+        let profile = MappingProfile {
+            name: "Sparse".to_string(),
+            fields: BTreeMap::new(),
+            cycle_fields: BTreeMap::new(),
+        };
+        assert_eq!(profile.field_rule(EmployeeField::Email), HeaderRule::new("Email"));
+        assert_eq!(profile.resolve_column("User 9Box 2026"), ColumnTarget::Cycle(CycleField::NineBox, "2026".to_string()));
+    }
+
+    #[test]
+    fn test_profile_json_round_trip() {
+        let profile = MappingProfile::legacy_hris();
+        let json = serde_json::to_string(&profile).expect("Failed to serialize profile");
+        let restored: MappingProfile = serde_json::from_str(&json).expect("Failed to deserialize profile");
+        assert_eq!(restored, profile);
+    }
+}
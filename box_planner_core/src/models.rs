@@ -1,12 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
+use crate::grid::{GridPosition, GridTemplate};
+use crate::labels::LabelScheme;
+use crate::mapping::MappingProfile;
+use crate::notes::NoteHistory;
+use crate::retention::{AttritionRisk, ImpactOfLoss};
+use crate::succession::SuccessionPlan;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Employee {
     #[serde(rename = "User ID")]
     pub user_id: String,
-    #[serde(rename = "PR Group 2025")]
-    pub pr_group_2025: String,
     #[serde(rename = "First Name")]
     pub first_name: String,
     #[serde(rename = "Last Name")]
@@ -15,18 +19,6 @@ pub struct Employee {
     pub current_position: String,
     #[serde(rename = "Current Temp Position")]
     pub current_temp_position: Option<String>,
-    #[serde(rename = "PR2021")]
-    pub pr_2021: Option<f64>,
-    #[serde(rename = "PR2022")]
-    pub pr_2022: Option<f64>,
-    #[serde(rename = "PR2023")]
-    pub pr_2023: Option<f64>,
-    #[serde(rename = "PR2024")]
-    pub pr_2024: Option<f64>,
-    #[serde(rename = "User 9Box 2024")]
-    pub user_9box_2024: Option<String>,
-    #[serde(rename = "User 9Box 2025")]
-    pub user_9box_2025: Option<String>,
     #[serde(rename = "Notes")]
     pub notes: Option<String>,
     #[serde(rename = "Current Label")]
@@ -42,6 +34,170 @@ pub struct Employee {
     pub location: Option<String>,
     #[serde(rename = "Hire Date")]
     pub hire_date: Option<String>, // Consider using a date/time type if appropriate
+    /// Ratings, 9-box placements and PR groups keyed by review cycle (e.g. "2024").
+    #[serde(rename = "Performance History", default)]
+    pub performance_history: BTreeMap<String, CycleRecord>,
+    /// Columns from the source file that map to no other field, in their original order.
+    #[serde(rename = "Custom Fields", default)]
+    pub custom_fields: Vec<CustomField>,
+    /// Names of the skills this employee has, in the order they were added.
+    #[serde(rename = "Skills", default)]
+    pub skills: Vec<String>,
+    /// Proficiency per skill name (as written in `skills`), for skills whose level is known.
+    #[serde(rename = "Skill Levels", default)]
+    pub skill_levels: BTreeMap<String, u8>,
+    #[serde(rename = "Attrition Risk", default)]
+    pub attrition_risk: Option<AttritionRisk>,
+    #[serde(rename = "Impact of Loss", default)]
+    pub impact_of_loss: Option<ImpactOfLoss>,
+}
+
+/// A value from a source column that `Employee` has no dedicated field for (e.g. "Grade").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CustomField {
+    pub header: String,
+    pub value: String,
+    #[serde(default)]
+    pub column: Option<usize>, // Index of the column in the source file's header; export puts it back there
+}
+
+/// What was recorded for an employee in a single review cycle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CycleRecord {
+    pub rating: Option<f64>,
+    pub nine_box: Option<String>,
+    pub group: Option<String>,
+}
+
+impl Employee {
+    /// Returns the record for `cycle`, if anything was recorded for it.
+    pub fn cycle(&self, cycle: &str) -> Option<&CycleRecord> {
+        self.performance_history.get(cycle)
+    }
+
+    /// Returns the record for `cycle`, creating an empty one if needed.
+    pub fn cycle_mut(&mut self, cycle: &str) -> &mut CycleRecord {
+        self.performance_history.entry(cycle.to_string()).or_default()
+    }
+
+    pub fn rating(&self, cycle: &str) -> Option<f64> {
+        self.cycle(cycle).and_then(|record| record.rating)
+    }
+
+    pub fn nine_box(&self, cycle: &str) -> Option<&str> {
+        self.cycle(cycle).and_then(|record| record.nine_box.as_deref())
+    }
+
+    pub fn group(&self, cycle: &str) -> Option<&str> {
+        self.cycle(cycle).and_then(|record| record.group.as_deref())
+    }
+
+    /// The most recent cycle with a rating, together with that rating.
+    pub fn latest_rating(&self) -> Option<(&str, f64)> {
+        self.performance_history
+            .iter()
+            .rev()
+            .find_map(|(cycle, record)| record.rating.map(|rating| (cycle.as_str(), rating)))
+    }
+
+    /// The most recent cycle with a 9-box placement, together with that placement.
+    pub fn latest_nine_box(&self) -> Option<(&str, &str)> {
+        self.performance_history
+            .iter()
+            .rev()
+            .find_map(|(cycle, record)| record.nine_box.as_deref().map(|nine_box| (cycle.as_str(), nine_box)))
+    }
+
+    /// Whether the employee has the skill called `name` (ignoring case).
+    pub fn has_skill(&self, name: &str) -> bool {
+        let name = name.trim();
+        self.skills.iter().any(|skill| skill.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds the skill called `name` unless it is blank or already present.
+    /// Returns whether the skill was added.
+    pub fn add_skill(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.has_skill(name) {
+            return false;
+        }
+        self.skills.push(name.to_string());
+        true
+    }
+
+    /// Removes the skill called `name` (ignoring case), together with its level.
+    /// Returns whether it was present.
+    pub fn remove_skill(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        let before = self.skills.len();
+        self.skills.retain(|skill| !skill.eq_ignore_ascii_case(name));
+        self.skill_levels.retain(|skill, _| !skill.eq_ignore_ascii_case(name));
+        self.skills.len() != before
+    }
+
+    /// The recorded proficiency for the skill called `name`, if the employee has it and a level is known.
+    pub fn skill_level(&self, name: &str) -> Option<u8> {
+        let name = name.trim();
+        self.skill_levels
+            .iter()
+            .find(|(skill, _)| skill.eq_ignore_ascii_case(name))
+            .map(|(_, level)| *level)
+    }
+
+    /// Records `level` for the skill called `name`, adding the skill if needed; `None` clears
+    /// the level but keeps the skill. Levels outside `MIN_PROFICIENCY..=MAX_PROFICIENCY` and
+    /// blank names are ignored. Returns whether anything changed.
+    pub fn set_skill_level(&mut self, name: &str, level: Option<u8>) -> bool {
+        if level.is_some_and(|level| !(MIN_PROFICIENCY..=MAX_PROFICIENCY).contains(&level)) {
+            return false;
+        }
+        let added = self.add_skill(name);
+        let Some(skill) = self.skills.iter().find(|skill| skill.eq_ignore_ascii_case(name.trim())).cloned() else {
+            return false;
+        };
+        let previous = self.skill_level(&skill);
+        self.skill_levels.retain(|existing, _| !existing.eq_ignore_ascii_case(&skill));
+        if let Some(level) = level {
+            self.skill_levels.insert(skill, level);
+        }
+        added || previous != level
+    }
+
+    /// The value of the custom field with `header`, if the source file had that column.
+    pub fn custom_field(&self, header: &str) -> Option<&str> {
+        self.custom_fields
+            .iter()
+            .find(|field| field.header == header)
+            .map(|field| field.value.as_str())
+    }
+
+    /// Sets the custom field with `header`, appending it if it does not exist yet.
+    pub fn set_custom_field(&mut self, header: &str, value: &str) {
+        match self.custom_fields.iter_mut().find(|field| field.header == header) {
+            Some(field) => field.value = value.to_string(),
+            None => self.custom_fields.push(CustomField {
+                header: header.to_string(),
+                value: value.to_string(),
+                column: None,
+            }),
+        }
+    }
+
+    /// Sets the custom field with `header` from column `column` of a source file's header.
+    pub fn set_source_custom_field(&mut self, header: &str, value: &str, column: usize) {
+        self.set_custom_field(header, value);
+        if let Some(field) = self.custom_fields.iter_mut().find(|field| field.header == header) {
+            field.column = Some(column);
+        }
+    }
+
+    /// The most recent cycle with a PR group, together with that group.
+    pub fn latest_group(&self) -> Option<(&str, &str)> {
+        self.performance_history
+            .iter()
+            .rev()
+            .find_map(|(cycle, record)| record.group.as_deref().map(|group| (cycle.as_str(), group)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -50,17 +206,82 @@ pub struct Skill {
     pub name: String,
 }
 
+/// Lowest proficiency an employee can hold a skill at, also assumed for skills without a recorded level.
+pub const MIN_PROFICIENCY: u8 = 1;
+/// Highest proficiency an employee can hold a skill at.
+pub const MAX_PROFICIENCY: u8 = 5;
+
+/// A skill a role needs, with the proficiency the role calls for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkillRequirement {
+    pub skill: String,
+    pub level: u8,
+}
+
+/// The skills expected of everyone whose current position is `position`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct RoleRequirement {
+    pub position: String,
+    #[serde(default)]
+    pub skills: Vec<SkillRequirement>,
+}
+
 // Grid-Related Structures
+/// Display settings for one box of a `GridTemplate`; `id` is the box's position code (e.g. "1A").
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct GridBox {
     pub id: String,
     pub label: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
+    pub color: Option<String>, // Hex color, e.g. "#597864"
+    #[serde(default)]
+    pub icon: Option<String>, // Short symbol shown before the label
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct GridState {
-    pub assignments: HashMap<String, Vec<String>>,
+    pub assignments: HashMap<GridPosition, Vec<String>>,
+}
+
+impl GridState {
+    /// The box `employee_id` is placed in, if any.
+    pub fn position_of(&self, employee_id: &str) -> Option<GridPosition> {
+        self.assignments
+            .iter()
+            .find(|(_, employee_ids)| employee_ids.iter().any(|id| id == employee_id))
+            .map(|(position, _)| *position)
+    }
+
+    /// Moves `employee_id` to the end of box `to`, or off the grid for `None`, leaving no
+    /// empty boxes behind. Returns the box they were in.
+    pub fn place(&mut self, employee_id: &str, to: Option<GridPosition>) -> Option<GridPosition> {
+        let from = self.position_of(employee_id);
+        for employee_ids in self.assignments.values_mut() {
+            employee_ids.retain(|id| id != employee_id);
+        }
+        self.assignments.retain(|_, employee_ids| !employee_ids.is_empty());
+        if let Some(to) = to {
+            self.assignments.entry(to).or_default().push(employee_id.to_string());
+        }
+        from
+    }
+
+    /// Removes placements in boxes that `template` does not have.
+    /// Returns the IDs of the employees that were unplaced.
+    pub fn retain_template(&mut self, template: &GridTemplate) -> Vec<String> {
+        let mut unplaced = Vec::new();
+        self.assignments.retain(|position, employee_ids| {
+            let keep = template.contains(*position);
+            if !keep {
+                unplaced.append(employee_ids);
+            }
+            keep
+        });
+        unplaced.sort();
+        unplaced
+    }
 }
 
 // Application Settings
@@ -70,6 +291,12 @@ pub struct AppSettings {
     pub department_colors: HashMap<String, String>,
     pub auto_save_enabled: bool,
     pub view_scale: Option<f32>, // New field
+    #[serde(default)]
+    pub mapping_profiles: Vec<MappingProfile>, // User-defined CSV column mappings
+    #[serde(default)]
+    pub active_mapping_profile: Option<String>,
+    #[serde(default)]
+    pub note_author: String, // Recorded on each note version; empty falls back to the login name
 }
 
 impl Default for AppSettings {
@@ -79,6 +306,97 @@ impl Default for AppSettings {
             department_colors: HashMap::new(),
             auto_save_enabled: false,
             view_scale: Some(1.0), // Default view_scale
+            mapping_profiles: Vec::new(),
+            active_mapping_profile: None,
+            note_author: String::new(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// All selectable mapping profiles: the built-in ones followed by user-defined ones.
+    /// A user-defined profile replaces a built-in profile with the same name.
+    pub fn available_mapping_profiles(&self) -> Vec<MappingProfile> {
+        let mut profiles: Vec<MappingProfile> = MappingProfile::built_in()
+            .into_iter()
+            .filter(|built_in| !self.mapping_profiles.iter().any(|p| p.name == built_in.name))
+            .collect();
+        profiles.extend(self.mapping_profiles.iter().cloned());
+        profiles
+    }
+
+    /// The profile selected for import and export, or the standard profile if none is selected.
+    pub fn active_mapping_profile(&self) -> MappingProfile {
+        self.active_mapping_profile
+            .as_ref()
+            .and_then(|name| self.available_mapping_profiles().into_iter().find(|p| &p.name == name))
+            .unwrap_or_default()
+    }
+
+    /// Stores `profile` as a user-defined profile, replacing any with the same name.
+    pub fn upsert_mapping_profile(&mut self, profile: MappingProfile) {
+        match self.mapping_profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.mapping_profiles.push(profile),
+        }
+    }
+}
+
+// Project Document
+/// Current version of the project file format written by `save_project`.
+/// Version 2 added `note_histories`.
+pub const PROJECT_FORMAT_VERSION: u32 = 2;
+/// File extension used for project documents (without the leading dot).
+pub const PROJECT_FILE_EXTENSION: &str = "9box";
+
+/// Settings that travel with a project file rather than with the local installation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProjectSettings {
+    #[serde(default)]
+    pub department_colors: HashMap<String, String>,
+    #[serde(default)]
+    pub grid_template: GridTemplate,
+    #[serde(default)]
+    pub label_scheme: LabelScheme, // How boxes are named on the grid and in exported 9-box columns
+    #[serde(default)]
+    pub placement_cycle: Option<String>, // Review cycle whose 9-box column the grid mirrors
+}
+
+/// A complete calibration session in one document: employees, grid placements,
+/// notes and their version histories keyed by employee ID, the skills catalog, role requirements, the succession plan
+/// and project settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectDocument {
+    pub format_version: u32,
+    pub employees: Vec<Employee>,
+    #[serde(default)]
+    pub grid_state: GridState,
+    #[serde(default)]
+    pub notes: HashMap<String, String>, // Current text of each note
+    #[serde(default)]
+    pub note_histories: HashMap<String, NoteHistory>, // Every saved version of each note, with times, authors and categories
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub role_requirements: Vec<RoleRequirement>,
+    #[serde(default)]
+    pub succession: SuccessionPlan,
+    #[serde(default)]
+    pub settings: ProjectSettings,
+}
+
+impl Default for ProjectDocument {
+    fn default() -> Self {
+        ProjectDocument {
+            format_version: PROJECT_FORMAT_VERSION,
+            employees: Vec::new(),
+            grid_state: GridState::default(),
+            notes: HashMap::new(),
+            note_histories: HashMap::new(),
+            skills: Vec::new(),
+            role_requirements: Vec::new(),
+            succession: SuccessionPlan::default(),
+            settings: ProjectSettings::default(),
         }
     }
 }
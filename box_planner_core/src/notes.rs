@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What a note version is about, as the calibration sessions file comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteCategory {
+    Strengths,
+    Development,
+    CalibrationComment,
+}
+
+impl NoteCategory {
+    pub const ALL: [NoteCategory; 3] = [NoteCategory::Strengths, NoteCategory::Development, NoteCategory::CalibrationComment];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoteCategory::Strengths => "Strengths",
+            NoteCategory::Development => "Development",
+            NoteCategory::CalibrationComment => "Calibration comment",
+        }
+    }
+}
+
+impl fmt::Display for NoteCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// One saved version of an employee's note.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteEntry {
+    pub text: String, // The whole note as of this version; empty once the note was cleared
+    #[serde(default)]
+    pub recorded_at: String, // UTC, e.g. "2024-03-01T09:30:00Z"; empty for notes saved before history was kept
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub category: Option<NoteCategory>,
+}
+
+impl NoteEntry {
+    /// A version recorded now.
+    pub fn new(text: &str, author: &str, category: Option<NoteCategory>) -> Self {
+        NoteEntry {
+            text: text.to_string(),
+            recorded_at: utc_timestamp(SystemTime::now()),
+            author: author.to_string(),
+            category,
+        }
+    }
+
+    /// When the version was saved, for display, e.g. "2024-03-01 09:30 UTC".
+    pub fn recorded_at_label(&self) -> String {
+        if self.recorded_at.is_empty() {
+            return "Before history was kept".to_string();
+        }
+        // Drop the seconds; `recorded_at` is always written by `utc_timestamp`
+        let minutes = self.recorded_at.get(..16).unwrap_or(&self.recorded_at);
+        format!("{} UTC", minutes.replace('T', " "))
+    }
+}
+
+/// Every saved version of an employee's note, oldest first. Versions are only ever appended;
+/// restoring an earlier one appends it again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoteHistory {
+    pub entries: Vec<NoteEntry>,
+}
+
+impl NoteHistory {
+    /// History for a note saved before versions were kept: one undated version.
+    pub fn from_legacy(text: &str) -> Self {
+        NoteHistory {
+            entries: vec![NoteEntry {
+                text: text.to_string(),
+                recorded_at: String::new(),
+                author: String::new(),
+                category: None,
+            }],
+        }
+    }
+
+    /// The latest version.
+    pub fn current(&self) -> Option<&NoteEntry> {
+        self.entries.last()
+    }
+
+    /// The note as it stands; empty if there is none or it was cleared.
+    pub fn current_text(&self) -> &str {
+        self.current().map_or("", |entry| entry.text.as_str())
+    }
+
+    /// Adds `entry` as the latest version. Returns `false`, adding nothing, if it has the same
+    /// text and category as the current version.
+    pub fn append(&mut self, entry: NoteEntry) -> bool {
+        if self.current().is_some_and(|current| current.text == entry.text && current.category == entry.category) {
+            return false;
+        }
+        // Clearing a note that never existed is not a version
+        if self.entries.is_empty() && entry.text.is_empty() {
+            return false;
+        }
+        self.entries.push(entry);
+        true
+    }
+}
+
+// Longest file name most file systems accept, in bytes
+const MAX_NOTE_FILE_NAME_LEN: usize = 255;
+
+// Device names Windows reserves whatever the extension or case
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "lpt1", "lpt2",
+    "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// The name of the file in the notes directory that holds an employee's note history.
+///
+/// Lowercase ASCII letters, digits, `_` and `-` are kept; every other byte of the ID, uppercase
+/// letters included, is written as `%XX`. The name is therefore a single path component
+/// that is valid on every platform, and no two IDs share a name even on file systems that
+/// ignore case. Windows device names such as `con` get their first letter escaped.
+///
+/// # Returns
+/// `Ok(String)`, e.g. `"%45001.json"` for `"E001"`, or an error message `String` for an
+/// empty ID or one too long to name a file.
+pub fn note_file_name(employee_id: &str) -> Result<String, String> {
+    if employee_id.is_empty() {
+        return Err("the employee ID is empty".to_string());
+    }
+    let mut stem = String::with_capacity(employee_id.len());
+    for byte in employee_id.bytes() {
+        if byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_' || byte == b'-' {
+            stem.push(char::from(byte));
+        } else {
+            stem.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    if WINDOWS_RESERVED_NAMES.contains(&stem.as_str()) {
+        stem = format!("%{:02X}{}", stem.as_bytes()[0], &stem[1..]);
+    }
+    let file_name = format!("{}.json", stem);
+    if file_name.len() > MAX_NOTE_FILE_NAME_LEN {
+        return Err(format!("the employee ID is too long to name a note file ({} bytes once escaped)", file_name.len()));
+    }
+    Ok(file_name)
+}
+
+/// The employee ID a note file belongs to, if `file_name` is exactly what `note_file_name`
+/// gives for that ID. Note files named before IDs were escaped give `None`.
+pub fn employee_id_from_note_file(file_name: &str) -> Option<String> {
+    let stem = file_name.strip_suffix(".json")?;
+    let mut bytes = Vec::with_capacity(stem.len());
+    let mut rest = stem.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    let employee_id = String::from_utf8(bytes).ok()?;
+    (note_file_name(&employee_id).ok()? == file_name).then_some(employee_id)
+}
+
+/// `time` as an ISO 8601 UTC timestamp with seconds, e.g. "2024-03-01T09:30:00Z".
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_700_000_000)), "2023-11-14T22:13:20Z");
+        assert_eq!(utc_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_note_file_names_are_safe_and_distinct() {
+        assert_eq!(note_file_name("emp_123-a").unwrap(), "emp_123-a.json");
+        assert_eq!(note_file_name("E001").unwrap(), "%45001.json");
+        assert_eq!(note_file_name("e001").unwrap(), "e001.json", "IDs differing only in case get different files");
+        assert_eq!(note_file_name("../secrets").unwrap(), "%2E%2E%2Fsecrets.json");
+        assert_eq!(note_file_name("a\\b:c").unwrap(), "a%5Cb%3Ac.json");
+        assert_eq!(note_file_name("con").unwrap(), "%63on.json");
+        assert_eq!(note_file_name("com1x").unwrap(), "com1x.json");
+        assert_eq!(note_file_name("50%").unwrap(), "50%25.json");
+        assert_eq!(note_file_name("José").unwrap(), "%4Aos%C3%A9.json");
+        assert!(note_file_name("").is_err());
+        assert!(note_file_name(&"x".repeat(251)).is_err());
+        assert!(note_file_name(&"x".repeat(250)).is_ok());
+
+        for employee_id in ["E001", "../secrets", "con", "50%", "José", "emp_1"] {
+            let file_name = note_file_name(employee_id).unwrap();
+            assert_eq!(employee_id_from_note_file(&file_name).as_deref(), Some(employee_id));
+        }
+        // Names the encoding would not produce belong to files written before it
+        assert_eq!(employee_id_from_note_file("E001.json"), None);
+        assert_eq!(employee_id_from_note_file("con.json"), None);
+        assert_eq!(employee_id_from_note_file("%65001.json"), None, "Unneeded escapes are not canonical");
+        assert_eq!(employee_id_from_note_file("%4.json"), None);
+        assert_eq!(employee_id_from_note_file("e001.txt"), None);
+    }
+
+    #[test]
+    fn test_history_appends_only_new_versions() {
+        let mut history = NoteHistory::default();
+        assert!(!history.append(NoteEntry::new("", "Ann", None)), "Clearing a missing note adds nothing");
+        assert!(history.append(NoteEntry::new("Strong presenter", "Ann", Some(NoteCategory::Strengths))));
+        assert!(!history.append(NoteEntry::new("Strong presenter", "Bob", Some(NoteCategory::Strengths))));
+        assert!(history.append(NoteEntry::new("Strong presenter", "Bob", Some(NoteCategory::CalibrationComment))));
+        assert!(history.append(NoteEntry::new("", "Bob", None)));
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.current_text(), "");
+
+        let legacy = NoteHistory::from_legacy("Old note");
+        assert_eq!(legacy.current_text(), "Old note");
+        assert_eq!(legacy.current().map(NoteEntry::recorded_at_label).as_deref(), Some("Before history was kept"));
+        let entry = NoteEntry { recorded_at: "2024-03-01T09:30:15Z".to_string(), ..NoteEntry::new("x", "Ann", None) };
+        assert_eq!(entry.recorded_at_label(), "2024-03-01 09:30 UTC");
+    }
+}
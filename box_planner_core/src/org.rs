@@ -0,0 +1,255 @@
+use crate::models::Employee;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// An employee whose manager ID matches nobody in the employee list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingManager {
+    pub employee_id: String,
+    pub manager_id: String,
+}
+
+/// The reporting hierarchy built from each employee's `manager_id`.
+///
+/// Employees without a manager, or whose manager is unknown, are roots. Reporting
+/// cycles (e.g. A reports to B and B reports to A) are recorded in `cycles()`; the
+/// employees in them have no layer and are not reachable from any root.
+#[derive(Debug, Clone, Default)]
+pub struct OrgTree {
+    roots: Vec<String>,
+    managers: HashMap<String, String>,
+    direct_reports: HashMap<String, Vec<String>>,
+    layers: HashMap<String, usize>,
+    cycles: Vec<Vec<String>>,
+    dangling: Vec<DanglingManager>,
+}
+
+impl OrgTree {
+    /// Builds the hierarchy. Reports keep the order of `employees`; a blank manager ID
+    /// or one pointing at the employee themselves counts as no manager.
+    pub fn build(employees: &[Employee]) -> Self {
+        let known: HashSet<&str> = employees.iter().map(|employee| employee.user_id.as_str()).collect();
+        let mut tree = OrgTree::default();
+
+        for employee in employees {
+            let manager_id = employee.manager_id.as_deref().map(str::trim).filter(|id| !id.is_empty());
+            match manager_id {
+                Some(manager_id) if manager_id == employee.user_id => tree.roots.push(employee.user_id.clone()),
+                Some(manager_id) if known.contains(manager_id) => {
+                    tree.managers.insert(employee.user_id.clone(), manager_id.to_string());
+                    tree.direct_reports
+                        .entry(manager_id.to_string())
+                        .or_default()
+                        .push(employee.user_id.clone());
+                }
+                Some(manager_id) => {
+                    tree.dangling.push(DanglingManager {
+                        employee_id: employee.user_id.clone(),
+                        manager_id: manager_id.to_string(),
+                    });
+                    tree.roots.push(employee.user_id.clone());
+                }
+                None => tree.roots.push(employee.user_id.clone()),
+            }
+        }
+
+        // Layers by breadth-first walk from the roots; anyone not reached sits on a cycle
+        // or below one.
+        let mut queue: VecDeque<(String, usize)> = tree.roots.iter().map(|root| (root.clone(), 0)).collect();
+        while let Some((id, layer)) = queue.pop_front() {
+            if tree.layers.contains_key(&id) {
+                continue;
+            }
+            for report in tree.direct_reports(&id) {
+                queue.push_back((report.clone(), layer + 1));
+            }
+            tree.layers.insert(id, layer);
+        }
+        tree.cycles = tree.find_cycles(employees);
+        tree
+    }
+
+    // Follows the manager chain from every unreached employee; a chain that returns to an
+    // ID it already visited closes a cycle. Each cycle is reported once, starting from
+    // its smallest ID.
+    fn find_cycles(&self, employees: &[Employee]) -> Vec<Vec<String>> {
+        let mut seen: BTreeSet<Vec<String>> = BTreeSet::new();
+        let mut cycles = Vec::new();
+        for employee in employees.iter().filter(|employee| !self.layers.contains_key(&employee.user_id)) {
+            let mut chain: Vec<&str> = Vec::new();
+            let mut current = employee.user_id.as_str();
+            let closed = loop {
+                if chain.contains(&current) {
+                    break true;
+                }
+                chain.push(current);
+                match self.manager_of(current) {
+                    Some(manager) => current = manager,
+                    None => break false,
+                }
+            };
+            let Some(start) = chain.iter().position(|id| *id == current).filter(|_| closed) else {
+                continue;
+            };
+            let mut cycle: Vec<String> = chain[start..].iter().map(|id| id.to_string()).collect();
+            if let Some(smallest) = cycle.iter().enumerate().min_by_key(|(_, id)| *id).map(|(index, _)| index) {
+                cycle.rotate_left(smallest);
+            }
+            if seen.insert(cycle.clone()) {
+                cycles.push(cycle);
+            }
+        }
+        cycles
+    }
+
+    /// Employees at the top of the hierarchy, in list order.
+    pub fn roots(&self) -> &[String] {
+        &self.roots
+    }
+
+    /// The known manager of `employee_id`.
+    pub fn manager_of(&self, employee_id: &str) -> Option<&str> {
+        self.managers.get(employee_id).map(String::as_str)
+    }
+
+    pub fn direct_reports(&self, manager_id: &str) -> &[String] {
+        self.direct_reports.get(manager_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Number of direct reports.
+    pub fn span_of_control(&self, manager_id: &str) -> usize {
+        self.direct_reports(manager_id).len()
+    }
+
+    /// Everyone who reports to `manager_id` directly or indirectly, nearest layers first.
+    /// The manager themselves is never included, even on a cycle.
+    pub fn all_reports(&self, manager_id: &str) -> Vec<String> {
+        let mut visited: HashSet<&str> = HashSet::from([manager_id]);
+        let mut reports = Vec::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([manager_id]);
+        while let Some(id) = queue.pop_front() {
+            for report in self.direct_reports(id) {
+                if visited.insert(report) {
+                    reports.push(report.clone());
+                    queue.push_back(report);
+                }
+            }
+        }
+        reports
+    }
+
+    /// Whether `employee_id` reports to `manager_id`, directly or indirectly.
+    pub fn is_under(&self, employee_id: &str, manager_id: &str) -> bool {
+        self.chain_of_command(employee_id).iter().any(|id| id == manager_id)
+    }
+
+    /// The managers above `employee_id`, nearest first; stops before repeating on a cycle.
+    pub fn chain_of_command(&self, employee_id: &str) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        let mut current = employee_id;
+        while let Some(manager) = self.manager_of(current) {
+            if manager == employee_id || chain.iter().any(|id| id == manager) {
+                break;
+            }
+            chain.push(manager.to_string());
+            current = manager;
+        }
+        chain
+    }
+
+    /// Depth below the top of the hierarchy (roots are layer 0); `None` on or below a cycle.
+    pub fn layer(&self, employee_id: &str) -> Option<usize> {
+        self.layers.get(employee_id).copied()
+    }
+
+    /// Number of management layers, counting the roots' layer.
+    pub fn layer_count(&self) -> usize {
+        self.layers.values().max().map_or(0, |deepest| deepest + 1)
+    }
+
+    /// Managers with at least one report, in no particular order.
+    pub fn managers(&self) -> impl Iterator<Item = &str> {
+        self.direct_reports.keys().map(String::as_str)
+    }
+
+    /// Reporting cycles, each listed from its smallest employee ID.
+    pub fn cycles(&self) -> &[Vec<String>] {
+        &self.cycles
+    }
+
+    /// Employees whose manager ID matches nobody.
+    pub fn dangling_managers(&self) -> &[DanglingManager] {
+        &self.dangling
+    }
+}
+
+/// Everyone under `manager_id` in `employees`, directly or indirectly, in list order.
+pub fn employees_under<'a>(employees: &'a [Employee], manager_id: &str) -> Vec<&'a Employee> {
+    let reports: HashSet<String> = OrgTree::build(employees).all_reports(manager_id).into_iter().collect();
+    employees.iter().filter(|employee| reports.contains(&employee.user_id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee(id: &str, manager_id: Option<&str>) -> Employee {
+        Employee {
+            user_id: id.to_string(),
+            manager_id: manager_id.map(str::to_string),
+            ..Employee::default()
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_layers_and_reports() {
+        let employees = vec![
+            employee("ceo", None),
+            employee("cto", Some("ceo")),
+            employee("cfo", Some("ceo")),
+            employee("dev1", Some("cto")),
+            employee("dev2", Some("cto")),
+            employee("intern", Some("dev1")),
+            employee("contractor", Some("agency")),
+        ];
+        let tree = OrgTree::build(&employees);
+
+        assert_eq!(tree.roots(), ["ceo", "contractor"]);
+        assert_eq!(tree.span_of_control("cto"), 2);
+        assert_eq!(tree.span_of_control("intern"), 0);
+        assert_eq!(tree.all_reports("cto"), vec!["dev1", "dev2", "intern"]);
+        assert_eq!(tree.all_reports("ceo").len(), 5);
+        assert_eq!(tree.layer("intern"), Some(3));
+        assert_eq!(tree.layer_count(), 4);
+        assert_eq!(tree.chain_of_command("intern"), vec!["dev1", "cto", "ceo"]);
+        assert!(tree.is_under("intern", "ceo"));
+        assert!(!tree.is_under("cfo", "cto"));
+        assert_eq!(
+            tree.dangling_managers(),
+            [DanglingManager { employee_id: "contractor".to_string(), manager_id: "agency".to_string() }]
+        );
+        assert!(tree.cycles().is_empty());
+
+        let under_cto: Vec<&str> = employees_under(&employees, "cto").iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(under_cto, vec!["dev1", "dev2", "intern"]);
+    }
+
+    #[test]
+    fn test_cycles_are_detected_without_looping() {
+        let employees = vec![
+            employee("a", Some("c")),
+            employee("b", Some("a")),
+            employee("c", Some("b")),
+            employee("d", Some("a")),
+            employee("self", Some("self")),
+            employee("root", None),
+        ];
+        let tree = OrgTree::build(&employees);
+
+        assert_eq!(tree.cycles(), [vec!["a".to_string(), "c".to_string(), "b".to_string()]]);
+        assert_eq!(tree.roots(), ["self", "root"], "Managing yourself counts as having no manager");
+        assert_eq!(tree.layer("d"), None, "Employees below a cycle have no layer");
+        assert_eq!(tree.all_reports("a"), vec!["b", "d", "c"]);
+        assert_eq!(tree.chain_of_command("a"), vec!["c", "b"]);
+        assert_eq!(tree.layer_count(), 1);
+    }
+}
@@ -1,38 +1,101 @@
+use crate::grid::GridTemplate;
 use crate::models; // Changed to remove direct AppSettings import
+use crate::notes::{employee_id_from_note_file, note_file_name, NoteEntry, NoteHistory};
+use crate::succession::SuccessionPlan;
 use serde_json;
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-// Helper struct for JSON serialization of notes
+// Helper struct for JSON serialization of notes. `notes` repeats the current version so
+// files stay readable as single notes; files written before versions were kept only have it.
 #[derive(serde::Serialize, serde::Deserialize)]
 struct NoteData {
     notes: String,
+    #[serde(default)]
+    entries: Vec<NoteEntry>,
 }
 
-/// Saves an employee's note to a JSON file.
-/// The note is stored in a structure: {"notes": "content..."}
+// Where an employee's note file lives; see `note_file_name`
+fn note_file_path(notes_dir: &Path, employee_id: &str) -> Result<PathBuf, String> {
+    note_file_name(employee_id)
+        .map(|file_name| notes_dir.join(file_name))
+        .map_err(|e| format!("Cannot store a note for employee ID {:?}: {}", employee_id, e))
+}
+
+// Reads a note file, whichever way it was written
+fn read_note_file(note_file_path: &Path) -> Result<NoteHistory, String> {
+    let mut file = File::open(note_file_path)
+        .map_err(|e| format!("Failed to open note file {:?}: {}", note_file_path, e))?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|e| format!("Failed to read note file {:?}: {}", note_file_path, e))?;
+
+    let note_data: NoteData = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to deserialize note from JSON {:?}: {}", note_file_path, e))?;
+
+    Ok(note_data.into_history())
+}
+
+impl NoteData {
+    fn into_history(self) -> NoteHistory {
+        if self.entries.is_empty() && !self.notes.is_empty() {
+            NoteHistory::from_legacy(&self.notes)
+        } else {
+            NoteHistory { entries: self.entries }
+        }
+    }
+}
+
+/// Appends a version to an employee's note history, kept in the file `note_file_name` gives.
+/// Earlier versions are never overwritten; a version with the same text and category as
+/// the current one is not appended.
+/// The file is stored in a structure: {"notes": "current content...", "entries": [...]}
 ///
 /// # Arguments
 /// * `notes_dir` - The directory where notes should be stored (e.g., `<storage_path>/notes/`).
 /// * `employee_id` - The unique identifier for the employee.
-/// * `note_content` - The content of the note to save.
+/// * `entry` - The new version; an empty `text` records that the note was cleared.
 ///
 /// # Returns
-/// `Ok(())` on success, or an error message `String` on failure.
-pub fn save_employee_note(
+/// `Ok(NoteHistory)` with the history as saved, or an error message `String` on failure,
+/// including for an employee ID that cannot name a note file.
+pub fn append_employee_note(
     notes_dir: &Path,
     employee_id: &str,
-    note_content: &str,
-) -> Result<(), String> {
+    entry: NoteEntry,
+) -> Result<NoteHistory, String> {
+    let mut history = load_note_history(notes_dir, employee_id)?;
+    if history.append(entry) {
+        save_note_history(notes_dir, employee_id, &history)?;
+    }
+    Ok(history)
+}
+
+/// Writes an employee's whole note history, replacing the versions stored before, e.g. when
+/// a project carrying the history is opened.
+///
+/// # Arguments
+/// * `notes_dir` - The directory where notes should be stored (e.g., `<storage_path>/notes/`).
+/// * `employee_id` - The unique identifier for the employee.
+/// * `history` - Every version of the note, oldest first.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure,
+/// including for an employee ID that cannot name a note file.
+pub fn save_note_history(notes_dir: &Path, employee_id: &str, history: &NoteHistory) -> Result<(), String> {
+    let note_file_path = note_file_path(notes_dir, employee_id)?;
+
     if !notes_dir.exists() {
         fs::create_dir_all(notes_dir)
             .map_err(|e| format!("Failed to create notes directory: {}", e))?;
     }
 
-    let note_file_path = notes_dir.join(format!("{}.json", employee_id));
     let note_data = NoteData {
-        notes: note_content.to_string(),
+        notes: history.current_text().to_string(),
+        entries: history.entries.clone(),
     };
 
     let json_string = serde_json::to_string_pretty(&note_data)
@@ -42,13 +105,48 @@ pub fn save_employee_note(
         .map_err(|e| format!("Failed to create note file {:?}: {}", note_file_path, e))?;
 
     file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write to note file {:?}: {}", note_file_path, e))?;
+        .map_err(|e| format!("Failed to write to note file {:?}: {}", note_file_path, e))
+}
 
-    Ok(())
+/// Saves an employee's note as a new version with no author or category.
+/// See `append_employee_note`.
+///
+/// # Arguments
+/// * `notes_dir` - The directory where notes should be stored (e.g., `<storage_path>/notes/`).
+/// * `employee_id` - The unique identifier for the employee.
+/// * `note_content` - The content of the note to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_employee_note(
+    notes_dir: &Path,
+    employee_id: &str,
+    note_content: &str,
+) -> Result<(), String> {
+    append_employee_note(notes_dir, employee_id, NoteEntry::new(note_content, "", None)).map(|_| ())
 }
 
-/// Loads an employee's note from a JSON file.
-/// Expects the note to be stored in a structure: {"notes": "content..."}
+/// Loads every saved version of an employee's note, oldest first. A note file written
+/// before versions were kept loads as a single undated version.
+///
+/// # Arguments
+/// * `notes_dir` - The directory where notes are stored.
+/// * `employee_id` - The unique identifier for the employee.
+///
+/// # Returns
+/// `Ok(NoteHistory)`, empty if the note file does not exist,
+/// or an error message `String` on other failures.
+pub fn load_note_history(notes_dir: &Path, employee_id: &str) -> Result<NoteHistory, String> {
+    let note_file_path = note_file_path(notes_dir, employee_id)?;
+
+    if !note_file_path.exists() {
+        return Ok(NoteHistory::default());
+    }
+
+    read_note_file(&note_file_path)
+}
+
+/// Loads the current version of an employee's note.
 ///
 /// # Arguments
 /// * `notes_dir` - The directory where notes are stored.
@@ -56,98 +154,424 @@ pub fn save_employee_note(
 ///
 /// # Returns
 /// `Ok(Some(String))` if the note is found and loaded,
-/// `Ok(None)` if the note file does not exist,
+/// `Ok(None)` if the note file does not exist or the note was cleared,
 /// or an error message `String` on other failures.
 pub fn load_employee_note(
     notes_dir: &Path,
     employee_id: &str,
 ) -> Result<Option<String>, String> {
-    let note_file_path = notes_dir.join(format!("{}.json", employee_id));
+    let history = load_note_history(notes_dir, employee_id)?;
+    let current = history.current_text();
+    Ok((!current.is_empty()).then(|| current.to_string()))
+}
+
+/// Saves the application settings to a JSON file.
+///
+/// # Arguments
+/// * `settings_file` - The path to the settings JSON file.
+/// * `settings` - A reference to the `AppSettings` struct to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_app_settings(
+    settings_file: &Path,
+    settings: &models::AppSettings,
+) -> Result<(), String> {
+    write_json_file(settings_file, settings, "settings", "AppSettings")
+}
 
+/// Loads the application settings from a JSON file.
+/// If the file does not exist, returns `AppSettings::default()`.
+///
+/// # Arguments
+/// * `settings_file` - The path to the settings JSON file.
+///
+/// # Returns
+/// `Ok(AppSettings)` on success (either loaded or default),
+/// or an error message `String` on failure to read/parse an existing file.
+pub fn load_app_settings(settings_file: &Path) -> Result<models::AppSettings, String> {
+    read_json_file(settings_file, "settings", "AppSettings")
+}
+
+/// Saves the grid state (box assignments) to a JSON file.
+///
+/// # Arguments
+/// * `grid_file` - The path to the grid state JSON file.
+/// * `grid_state` - A reference to the `GridState` struct to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_grid_state(grid_file: &Path, grid_state: &models::GridState) -> Result<(), String> {
+    write_json_file(grid_file, grid_state, "grid state", "GridState")
+}
+
+/// Loads the grid state (box assignments) from a JSON file.
+/// If the file does not exist, returns an empty `GridState`.
+///
+/// # Arguments
+/// * `grid_file` - The path to the grid state JSON file.
+///
+/// # Returns
+/// `Ok(GridState)` on success (either loaded or empty),
+/// or an error message `String` on failure to read/parse an existing file.
+pub fn load_grid_state(grid_file: &Path) -> Result<models::GridState, String> {
+    read_json_file(grid_file, "grid state", "GridState")
+}
+
+/// Saves the skills catalog to a JSON file.
+///
+/// # Arguments
+/// * `skills_file` - The path to the skills catalog JSON file.
+/// * `skills` - The skills to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_skills_catalog(skills_file: &Path, skills: &[models::Skill]) -> Result<(), String> {
+    write_json_file(skills_file, &skills, "skills catalog", "skills catalog")
+}
+
+/// Loads the skills catalog from a JSON file.
+/// If the file does not exist, returns an empty catalog.
+///
+/// # Arguments
+/// * `skills_file` - The path to the skills catalog JSON file.
+///
+/// # Returns
+/// `Ok(Vec<Skill>)` on success (either loaded or empty),
+/// or an error message `String` on failure to read/parse an existing file.
+pub fn load_skills_catalog(skills_file: &Path) -> Result<Vec<models::Skill>, String> {
+    read_json_file(skills_file, "skills catalog", "skills catalog")
+}
+
+/// Saves the skill requirements of each role to a JSON file.
+///
+/// # Arguments
+/// * `roles_file` - The path to the role requirements JSON file.
+/// * `requirements` - The role requirements to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_role_requirements(roles_file: &Path, requirements: &[models::RoleRequirement]) -> Result<(), String> {
+    write_json_file(roles_file, &requirements, "role requirements", "role requirements")
+}
+
+/// Loads the skill requirements of each role from a JSON file.
+/// If the file does not exist, returns no requirements.
+///
+/// # Arguments
+/// * `roles_file` - The path to the role requirements JSON file.
+///
+/// # Returns
+/// `Ok(Vec<RoleRequirement>)` on success (either loaded or empty),
+/// or an error message `String` on failure to read/parse an existing file.
+pub fn load_role_requirements(roles_file: &Path) -> Result<Vec<models::RoleRequirement>, String> {
+    read_json_file(roles_file, "role requirements", "role requirements")
+}
+
+/// Saves the succession plan to a JSON file.
+///
+/// # Arguments
+/// * `succession_file` - The path to the succession plan JSON file.
+/// * `plan` - The succession plan to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_succession_plan(succession_file: &Path, plan: &SuccessionPlan) -> Result<(), String> {
+    write_json_file(succession_file, plan, "succession plan", "SuccessionPlan")
+}
+
+/// Loads the succession plan from a JSON file.
+/// If the file does not exist, returns an empty plan.
+///
+/// # Arguments
+/// * `succession_file` - The path to the succession plan JSON file.
+///
+/// # Returns
+/// `Ok(SuccessionPlan)` on success (either loaded or empty),
+/// or an error message `String` on failure to read/parse an existing file.
+pub fn load_succession_plan(succession_file: &Path) -> Result<SuccessionPlan, String> {
+    read_json_file(succession_file, "succession plan", "SuccessionPlan")
+}
+
+/// Deletes an employee's note file, including every earlier version. A note that does
+/// not exist is not an error.
+///
+/// # Arguments
+/// * `notes_dir` - The directory where notes are stored.
+/// * `employee_id` - The unique identifier for the employee.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn delete_employee_note(notes_dir: &Path, employee_id: &str) -> Result<(), String> {
+    let note_file_path = note_file_path(notes_dir, employee_id)?;
     if !note_file_path.exists() {
-        return Ok(None);
+        return Ok(());
     }
+    fs::remove_file(&note_file_path)
+        .map_err(|e| format!("Failed to delete note file {:?}: {}", note_file_path, e))
+}
 
-    let mut file = File::open(&note_file_path)
-        .map_err(|e| format!("Failed to open note file {:?}: {}", note_file_path, e))?;
+/// Loads the current version of every employee note stored in `notes_dir`, keyed by employee ID.
+/// A missing directory yields an empty map; notes that were cleared are left out.
+///
+/// # Arguments
+/// * `notes_dir` - The directory where notes are stored.
+///
+/// # Returns
+/// `Ok(HashMap<String, String>)` on success, or an error message `String`
+/// if the directory or one of its note files cannot be read.
+pub fn load_all_employee_notes(notes_dir: &Path) -> Result<HashMap<String, String>, String> {
+    let mut notes = HashMap::new();
+    if !notes_dir.exists() {
+        return Ok(notes);
+    }
 
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .map_err(|e| format!("Failed to read note file {:?}: {}", note_file_path, e))?;
+    let entries = fs::read_dir(notes_dir)
+        .map_err(|e| format!("Failed to read notes directory {:?}: {}", notes_dir, e))?;
 
-    let note_data: NoteData = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to deserialize note from JSON {:?}: {}", note_file_path, e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read notes directory {:?}: {}", notes_dir, e))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // Files not yet moved by `migrate_note_files` are named after the raw ID
+        let Some(employee_id) = employee_id_from_note_file(file_name)
+            .or_else(|| file_name.strip_suffix(".json").map(str::to_string))
+        else {
+            continue;
+        };
+        let current = read_note_file(&path)?.current_text().to_string();
+        if !current.is_empty() {
+            notes.insert(employee_id, current);
+        }
+    }
 
-    Ok(Some(note_data.notes))
+    Ok(notes)
 }
 
-/// Saves the application settings to a JSON file.
+/// What `migrate_note_files` did.
+#[derive(Debug, Default, PartialEq)]
+pub struct NoteFileMigration {
+    pub renamed: usize,
+    pub problems: Vec<String>, // Files left as they were, and why
+}
+
+/// Renames note files written before employee IDs were escaped (`{employee_id}.json`) to the
+/// names `note_file_name` gives. Files already named that way are left alone, as are files
+/// whose ID cannot name a note file or whose new name is taken; those are reported.
 ///
 /// # Arguments
-/// * `settings_file` - The path to the settings JSON file.
-/// * `settings` - A reference to the `AppSettings` struct to save.
+/// * `notes_dir` - The directory where notes are stored.
+///
+/// # Returns
+/// `Ok(NoteFileMigration)`, or an error message `String` if the directory cannot be read.
+pub fn migrate_note_files(notes_dir: &Path) -> Result<NoteFileMigration, String> {
+    let mut migration = NoteFileMigration::default();
+    if !notes_dir.exists() {
+        return Ok(migration);
+    }
+
+    let entries = fs::read_dir(notes_dir)
+        .map_err(|e| format!("Failed to read notes directory {:?}: {}", notes_dir, e))?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read notes directory {:?}: {}", notes_dir, e))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            migration.problems.push(format!("{:?}: the file name is not valid UTF-8", path));
+            continue;
+        };
+        if employee_id_from_note_file(file_name).is_some() {
+            continue;
+        }
+        let legacy_id = file_name.strip_suffix(".json").unwrap_or(file_name);
+        let new_path = match note_file_path(notes_dir, legacy_id) {
+            Ok(new_path) => new_path,
+            Err(e) => {
+                migration.problems.push(format!("{}: {}", file_name, e));
+                continue;
+            }
+        };
+        if new_path.exists() {
+            migration.problems.push(format!("{}: {:?} already exists; both files were kept", file_name, new_path));
+            continue;
+        }
+        match fs::rename(&path, &new_path) {
+            Ok(_) => migration.renamed += 1,
+            Err(e) => migration.problems.push(format!("{}: failed to rename to {:?}: {}", file_name, new_path, e)),
+        }
+    }
+
+    Ok(migration)
+}
+
+/// Saves a project document (employees, grid, notes, skills and settings) to a single file.
+///
+/// # Arguments
+/// * `project_file` - The path to the project file (conventionally ending in `.9box`).
+/// * `project` - A reference to the `ProjectDocument` to save.
 ///
 /// # Returns
 /// `Ok(())` on success, or an error message `String` on failure.
-pub fn save_app_settings(
-    settings_file: &Path,
-    settings: &models::AppSettings,
+pub fn save_project(project_file: &Path, project: &models::ProjectDocument) -> Result<(), String> {
+    write_json_file(project_file, project, "project", "ProjectDocument")
+}
+
+/// Loads a project document from a single file.
+/// Files written by a newer, unsupported format version are rejected.
+///
+/// # Arguments
+/// * `project_file` - The path to the project file.
+///
+/// # Returns
+/// `Ok(ProjectDocument)` on success, or an error message `String` if the file
+/// is missing, unreadable, malformed or of an unsupported version.
+pub fn load_project(project_file: &Path) -> Result<models::ProjectDocument, String> {
+    // Only the version is read first, so that the error for a newer file is clear
+    // even if the rest of its layout no longer matches ours.
+    #[derive(serde::Deserialize)]
+    struct VersionProbe {
+        format_version: u32,
+    }
+
+    if !project_file.exists() {
+        return Err(format!("Project file {:?} does not exist", project_file));
+    }
+
+    let contents = read_file_to_string(project_file, "project")?;
+
+    let probe: VersionProbe = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to read project format version from {:?}: {}", project_file, e))?;
+    if probe.format_version > models::PROJECT_FORMAT_VERSION {
+        return Err(format!(
+            "Project file {:?} uses format version {}, but only versions up to {} are supported",
+            project_file,
+            probe.format_version,
+            models::PROJECT_FORMAT_VERSION
+        ));
+    }
+
+    let project: models::ProjectDocument = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to deserialize ProjectDocument from JSON {:?}: {}", project_file, e))?;
+    project
+        .settings
+        .grid_template
+        .validate()
+        .map_err(|e| format!("Project file {:?} has an invalid grid template: {}", project_file, e))?;
+    Ok(project)
+}
+
+/// Saves a grid template (axes and per-box labels, descriptions, colors and icons) to a JSON file.
+///
+/// # Arguments
+/// * `template_file` - The path to the template JSON file.
+/// * `template` - A reference to the `GridTemplate` to save.
+///
+/// # Returns
+/// `Ok(())` on success, or an error message `String` on failure.
+pub fn save_grid_template(template_file: &Path, template: &GridTemplate) -> Result<(), String> {
+    write_json_file(template_file, template, "grid template", "GridTemplate")
+}
+
+/// Loads a grid template from a JSON file and checks that it describes a usable grid.
+///
+/// # Arguments
+/// * `template_file` - The path to the template JSON file.
+///
+/// # Returns
+/// `Ok(GridTemplate)` on success, or an error message `String` if the file
+/// is missing, unreadable, malformed or fails validation.
+pub fn load_grid_template(template_file: &Path) -> Result<GridTemplate, String> {
+    if !template_file.exists() {
+        return Err(format!("Grid template file {:?} does not exist", template_file));
+    }
+
+    let contents = read_file_to_string(template_file, "grid template")?;
+    let template: GridTemplate = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to deserialize GridTemplate from JSON {:?}: {}", template_file, e))?;
+    template.validate().map_err(|e| e.to_string())?;
+    Ok(template)
+}
+
+// Serializes `value` as pretty JSON into `file_path`, creating parent directories as needed.
+// `noun` names the file in error messages ("settings"), `type_name` names the serialized type.
+fn write_json_file<T: serde::Serialize>(
+    file_path: &Path,
+    value: &T,
+    noun: &str,
+    type_name: &str,
 ) -> Result<(), String> {
     // Ensure parent directory exists
-    if let Some(parent_dir) = settings_file.parent() {
-        if !parent_dir.exists() {
+    if let Some(parent_dir) = file_path.parent() {
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
             fs::create_dir_all(parent_dir)
-                .map_err(|e| format!("Failed to create parent directory for settings: {}", e))?;
+                .map_err(|e| format!("Failed to create parent directory for {}: {}", noun, e))?;
         }
     }
 
-    let json_string = serde_json::to_string_pretty(settings)
-        .map_err(|e| format!("Failed to serialize AppSettings to JSON: {}", e))?;
+    let json_string = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {} to JSON: {}", type_name, e))?;
 
-    let mut file = File::create(settings_file)
-        .map_err(|e| format!("Failed to create settings file {:?}: {}", settings_file, e))?;
+    let mut file = File::create(file_path)
+        .map_err(|e| format!("Failed to create {} file {:?}: {}", noun, file_path, e))?;
 
     file.write_all(json_string.as_bytes())
-        .map_err(|e| format!("Failed to write to settings file {:?}: {}", settings_file, e))?;
+        .map_err(|e| format!("Failed to write to {} file {:?}: {}", noun, file_path, e))?;
 
     Ok(())
 }
 
-/// Loads the application settings from a JSON file.
-/// If the file does not exist, returns `AppSettings::default()`.
-///
-/// # Arguments
-/// * `settings_file` - The path to the settings JSON file.
-///
-/// # Returns
-/// `Ok(AppSettings)` on success (either loaded or default),
-/// or an error message `String` on failure to read/parse an existing file.
-pub fn load_app_settings(settings_file: &Path) -> Result<models::AppSettings, String> {
-    if !settings_file.exists() {
-        return Ok(models::AppSettings::default());
+// Reads and deserializes a JSON file, returning `T::default()` if the file does not exist.
+fn read_json_file<T: serde::de::DeserializeOwned + Default>(
+    file_path: &Path,
+    noun: &str,
+    type_name: &str,
+) -> Result<T, String> {
+    if !file_path.exists() {
+        return Ok(T::default());
     }
 
-    let mut file = File::open(settings_file)
-        .map_err(|e| format!("Failed to open settings file {:?}: {}", settings_file, e))?;
+    let contents = read_file_to_string(file_path, noun)?;
+
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to deserialize {} from JSON {:?}: {}", type_name, file_path, e))
+}
+
+fn read_file_to_string(file_path: &Path, noun: &str) -> Result<String, String> {
+    let mut file = File::open(file_path)
+        .map_err(|e| format!("Failed to open {} file {:?}: {}", noun, file_path, e))?;
 
     let mut contents = String::new();
     file.read_to_string(&mut contents)
-        .map_err(|e| format!("Failed to read settings file {:?}: {}", settings_file, e))?;
-
-    let settings: models::AppSettings = serde_json::from_str(&contents)
-        .map_err(|e| format!("Failed to deserialize AppSettings from JSON {:?}: {}", settings_file, e))?;
+        .map_err(|e| format!("Failed to read {} file {:?}: {}", noun, file_path, e))?;
 
-    Ok(settings)
+    Ok(contents)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{AppSettings}; // Employee is not used here directly
-    use std::fs as std_fs; // Renamed to avoid conflict with persistence::fs
-    use std::path::PathBuf;
+    use crate::grid::{GridPosition, GridTemplate};
+    use crate::succession::{Criticality, Readiness};
+    use crate::models::{
+        AppSettings, Employee, GridState, ProjectDocument, RoleRequirement, Skill, SkillRequirement, PROJECT_FORMAT_VERSION,
+    };
+    use crate::notes::NoteCategory;
     use tempfile::tempdir;
 
+    fn position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
+    }
+
     #[test]
     fn test_save_and_load_employee_note() {
         let dir = tempdir().expect("Failed to create temp dir");
@@ -173,6 +597,98 @@ mod tests {
         let non_existent_load_result = load_employee_note(&notes_dir, "non_existent_emp");
         assert!(non_existent_load_result.is_ok(), "Error when loading non-existent note: {:?}", non_existent_load_result.err());
         assert_eq!(non_existent_load_result.unwrap(), None);
+
+        // Delete note, twice: a missing note is not an error
+        assert!(delete_employee_note(&notes_dir, employee_id).is_ok());
+        assert_eq!(load_employee_note(&notes_dir, employee_id).unwrap(), None);
+        assert!(delete_employee_note(&notes_dir, employee_id).is_ok());
+    }
+
+    #[test]
+    fn test_note_files_stay_inside_the_notes_directory() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let notes_dir = dir.path().join("notes");
+
+        for employee_id in ["../escaped", "a/b", "con", "E001", "e001"] {
+            save_employee_note(&notes_dir, employee_id, &format!("Note for {}", employee_id)).unwrap();
+        }
+        assert!(!dir.path().join("escaped.json").exists(), "A note must not be written outside the notes directory");
+        assert_eq!(fs::read_dir(&notes_dir).unwrap().count(), 5, "Each ID gets its own file");
+        assert_eq!(load_employee_note(&notes_dir, "E001").unwrap(), Some("Note for E001".to_string()));
+
+        let notes = load_all_employee_notes(&notes_dir).unwrap();
+        assert_eq!(notes.len(), 5);
+        assert_eq!(notes.get("../escaped").map(String::as_str), Some("Note for ../escaped"));
+
+        let error = save_employee_note(&notes_dir, "", "Orphan").unwrap_err();
+        assert!(error.contains("empty"), "Unexpected error: {}", error);
+        assert!(load_note_history(&notes_dir, &"x".repeat(300)).is_err());
+        delete_employee_note(&notes_dir, "a/b").unwrap();
+        assert_eq!(load_employee_note(&notes_dir, "a/b").unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_note_files_renames_raw_id_file_names() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let notes_dir = dir.path().join("notes");
+        fs::create_dir_all(&notes_dir).unwrap();
+        fs::write(notes_dir.join("E001.json"), r#"{"notes": "Old E001 note"}"#).unwrap();
+        fs::write(notes_dir.join("emp2.json"), r#"{"notes": "Already safe"}"#).unwrap();
+        fs::write(notes_dir.join("E002.json"), r#"{"notes": "Old E002 note"}"#).unwrap();
+        save_employee_note(&notes_dir, "E002", "New E002 note").unwrap();
+        fs::write(notes_dir.join(format!("{}.json", "Y".repeat(100))), r#"{"notes": "Long"}"#).unwrap();
+
+        // Unmigrated files still load under their raw ID
+        let notes = load_all_employee_notes(&notes_dir).unwrap();
+        assert_eq!(notes.get("E001").map(String::as_str), Some("Old E001 note"));
+
+        let migration = migrate_note_files(&notes_dir).unwrap();
+        assert_eq!(migration.renamed, 1);
+        assert_eq!(migration.problems.len(), 2, "Unexpected problems: {:?}", migration.problems);
+        assert!(migration.problems.iter().any(|problem| problem.starts_with("E002.json")));
+        assert!(!notes_dir.join("E001.json").exists());
+        assert_eq!(load_employee_note(&notes_dir, "E001").unwrap(), Some("Old E001 note".to_string()));
+        assert_eq!(load_employee_note(&notes_dir, "emp2").unwrap(), Some("Already safe".to_string()));
+        assert_eq!(load_employee_note(&notes_dir, "E002").unwrap(), Some("New E002 note".to_string()));
+
+        assert_eq!(migrate_note_files(&notes_dir).unwrap().renamed, 0, "Migrating twice changes nothing");
+        assert_eq!(migrate_note_files(&dir.path().join("missing")).unwrap(), NoteFileMigration::default());
+    }
+
+    #[test]
+    fn test_note_versions_are_appended_and_legacy_notes_still_load() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let notes_dir = dir.path().join("notes");
+
+        // A note file written before versions were kept
+        fs::create_dir_all(&notes_dir).unwrap();
+        fs::write(notes_dir.join("emp1.json"), r#"{"notes": "Old note"}"#).unwrap();
+        assert_eq!(load_employee_note(&notes_dir, "emp1").unwrap(), Some("Old note".to_string()));
+        let history = load_note_history(&notes_dir, "emp1").unwrap();
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].recorded_at, "");
+
+        let entry = NoteEntry::new("Ready for a stretch role", "Ann", Some(NoteCategory::Development));
+        let history = append_employee_note(&notes_dir, "emp1", entry).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0].text, "Old note", "Earlier versions are kept");
+
+        // Unchanged text and category add no version; clearing does
+        save_employee_note(&notes_dir, "emp1", "Ready for a stretch role").unwrap();
+        assert_eq!(load_note_history(&notes_dir, "emp1").unwrap().entries.len(), 3, "A new category is a new version");
+        save_employee_note(&notes_dir, "emp1", "Ready for a stretch role").unwrap();
+        save_employee_note(&notes_dir, "emp1", "").unwrap();
+        let history = load_note_history(&notes_dir, "emp1").unwrap();
+        assert_eq!(history.entries.len(), 4);
+        assert_eq!(history.entries[1].author, "Ann");
+        assert_eq!(history.entries[1].category, Some(NoteCategory::Development));
+        assert_eq!(load_employee_note(&notes_dir, "emp1").unwrap(), None);
+
+        // The file still carries the current text as a single note
+        let raw = fs::read_to_string(notes_dir.join("emp1.json")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(value["notes"], "");
+        assert_eq!(load_note_history(&notes_dir, "missing").unwrap(), NoteHistory::default());
     }
 
     #[test]
@@ -180,10 +696,14 @@ mod tests {
         let dir = tempdir().expect("Failed to create temp dir");
         let settings_file_path = dir.path().join("settings.json");
 
-        let mut settings = AppSettings::default();
-        settings.theme_preference = "dark".to_string();
-        settings.auto_save_enabled = true;
+        let mut settings = AppSettings {
+            theme_preference: "dark".to_string(),
+            auto_save_enabled: true,
+            ..AppSettings::default()
+        };
         settings.department_colors.insert("Engineering".to_string(), "#FF0000".to_string());
+        settings.upsert_mapping_profile(crate::mapping::MappingProfile::legacy_hris());
+        settings.active_mapping_profile = Some("Legacy HRIS".to_string());
 
         // Save settings
         let save_result = save_app_settings(&settings_file_path, &settings);
@@ -198,6 +718,21 @@ mod tests {
         assert_eq!(load_result.unwrap(), settings);
     }
 
+    #[test]
+    fn test_load_settings_without_mapping_profiles() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let settings_file = dir.path().join("settings.json");
+        std::fs::write(
+            &settings_file,
+            r#"{"theme_preference": "system", "department_colors": {}, "auto_save_enabled": false, "view_scale": 1.1}"#,
+        )
+        .expect("Failed to write settings file");
+
+        let settings = load_app_settings(&settings_file).expect("Older settings files should still load");
+        assert!(settings.mapping_profiles.is_empty());
+        assert_eq!(settings.active_mapping_profile().name, crate::mapping::STANDARD_PROFILE_NAME);
+    }
+
     #[test]
     fn test_load_default_app_settings_if_file_not_exists() {
         let dir = tempdir().expect("Failed to create temp dir");
@@ -238,4 +773,230 @@ mod tests {
         assert!(parent_dir.exists(), "Parent directory for settings was not created.");
         assert!(settings_file.exists(), "Settings file was not created in new parent directory.");
     }
+
+    #[test]
+    fn test_save_and_load_grid_state() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let grid_file_path = dir.path().join("grid_state.json");
+
+        let mut grid_state = GridState::default();
+        grid_state.assignments.insert(position("1A"), vec!["emp1".to_string(), "emp3".to_string()]);
+        grid_state.assignments.insert(position("2B"), vec!["emp2".to_string()]);
+
+        let save_result = save_grid_state(&grid_file_path, &grid_state);
+        assert!(save_result.is_ok(), "Failed to save grid state: {:?}", save_result.err());
+        assert!(grid_file_path.exists(), "Grid state file was not created");
+
+        let load_result = load_grid_state(&grid_file_path);
+        assert!(load_result.is_ok(), "Failed to load grid state: {:?}", load_result.err());
+        assert_eq!(load_result.unwrap(), grid_state);
+    }
+
+    #[test]
+    fn test_load_empty_grid_state_if_file_not_exists() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let non_existent_grid_file = dir.path().join("non_existent_grid_state.json");
+
+        let load_result = load_grid_state(&non_existent_grid_file);
+        assert!(load_result.is_ok(), "Failed to load empty grid state: {:?}", load_result.err());
+        assert_eq!(load_result.unwrap(), GridState::default());
+    }
+
+    #[test]
+    fn test_save_grid_state_creates_parent_directory() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let parent_dir = dir.path().join("new_grid_parent");
+        let grid_file = parent_dir.join("grid_state.json");
+
+        let save_result = save_grid_state(&grid_file, &GridState::default());
+        assert!(save_result.is_ok(), "Failed to save grid state: {:?}", save_result.err());
+        assert!(parent_dir.exists(), "Parent directory for grid state was not created.");
+        assert!(grid_file.exists(), "Grid state file was not created in new parent directory.");
+    }
+
+    #[test]
+    fn test_load_grid_state_rejects_malformed_json() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let grid_file = dir.path().join("grid_state.json");
+        std::fs::write(&grid_file, "{ not json").expect("Failed to write malformed file");
+
+        let load_result = load_grid_state(&grid_file);
+        assert!(load_result.is_err(), "Malformed grid state should not load");
+    }
+
+    #[test]
+    fn test_load_all_employee_notes() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let notes_dir = dir.path().join("notes");
+
+        save_employee_note(&notes_dir, "emp1", "First note").expect("Failed to save note");
+        save_employee_note(&notes_dir, "emp2", "Second note").expect("Failed to save note");
+        std::fs::write(notes_dir.join("readme.txt"), "not a note").expect("Failed to write stray file");
+
+        let notes = load_all_employee_notes(&notes_dir).expect("Failed to load all notes");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes.get("emp1"), Some(&"First note".to_string()));
+        assert_eq!(notes.get("emp2"), Some(&"Second note".to_string()));
+
+        let missing = load_all_employee_notes(&dir.path().join("missing")).expect("Missing dir should not fail");
+        assert!(missing.is_empty());
+    }
+
+    fn sample_project() -> ProjectDocument {
+        let mut employee = Employee {
+            user_id: "emp1".to_string(),
+            first_name: "John".to_string(),
+            last_name: "Doe".to_string(),
+            current_position: "Developer".to_string(),
+            department: Some("Engineering".to_string()),
+            ..Employee::default()
+        };
+        employee.cycle_mut("2024").rating = Some(4.5);
+        employee.cycle_mut("2024").nine_box = Some("1A".to_string());
+        let mut project = ProjectDocument {
+            employees: vec![employee],
+            skills: vec![Skill { id: "rust".to_string(), name: "Rust".to_string() }],
+            role_requirements: vec![RoleRequirement {
+                position: "Developer".to_string(),
+                skills: vec![SkillRequirement { skill: "Rust".to_string(), level: 4 }],
+            }],
+            ..ProjectDocument::default()
+        };
+        project.succession.add_role("Developer", Some(Criticality::High));
+        project.succession.add_successor("Developer", "emp2", Some(Readiness::OneToTwoYears));
+        project.grid_state.assignments.insert(position("1A"), vec!["emp1".to_string()]);
+        project.notes.insert("emp1".to_string(), "Ready for promotion".to_string());
+        let mut history = NoteHistory::from_legacy("Needs a stretch goal");
+        history.append(NoteEntry::new("Ready for promotion", "Ann", Some(NoteCategory::CalibrationComment)));
+        project.note_histories.insert("emp1".to_string(), history);
+        project.settings.department_colors.insert("Engineering".to_string(), "#00FF00".to_string());
+        project
+    }
+
+    #[test]
+    fn test_save_and_load_project() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let project_file = dir.path().join("calibration.9box");
+        let project = sample_project();
+
+        let save_result = save_project(&project_file, &project);
+        assert!(save_result.is_ok(), "Failed to save project: {:?}", save_result.err());
+
+        let load_result = load_project(&project_file);
+        assert!(load_result.is_ok(), "Failed to load project: {:?}", load_result.err());
+        let loaded = load_result.unwrap();
+        assert_eq!(loaded, project);
+        let history = &loaded.note_histories["emp1"];
+        assert_eq!(history.entries[0].text, "Needs a stretch goal", "Earlier note versions travel with the project");
+        assert_eq!(history.entries[1].author, "Ann");
+    }
+
+    #[test]
+    fn test_load_project_missing_file_is_error() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let load_result = load_project(&dir.path().join("missing.9box"));
+        assert!(load_result.is_err(), "Loading a missing project should fail");
+    }
+
+    #[test]
+    fn test_load_project_rejects_newer_format_version() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let project_file = dir.path().join("future.9box");
+        let contents = format!(r#"{{"format_version": {}, "employees": []}}"#, PROJECT_FORMAT_VERSION + 1);
+        std::fs::write(&project_file, contents).expect("Failed to write project file");
+
+        let err = load_project(&project_file).expect_err("Newer project versions should be rejected");
+        assert!(err.contains("format version"), "Unexpected error message: {}", err);
+    }
+
+    #[test]
+    fn test_load_project_fills_missing_sections_with_defaults() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let project_file = dir.path().join("minimal.9box");
+        std::fs::write(&project_file, r#"{"format_version": 1, "employees": []}"#).expect("Failed to write project file");
+
+        let project = load_project(&project_file).expect("Minimal project should load");
+        assert_eq!(project, ProjectDocument { format_version: 1, ..ProjectDocument::default() });
+    }
+
+    #[test]
+    fn test_save_and_load_skills_catalog() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let skills_file = dir.path().join("data").join("skills.json");
+        assert_eq!(load_skills_catalog(&skills_file), Ok(Vec::new()), "A missing catalog should load as empty");
+
+        let skills = vec![
+            Skill { id: "rust".to_string(), name: "Rust".to_string() },
+            Skill { id: "negotiation".to_string(), name: "Negotiation".to_string() },
+        ];
+        let save_result = save_skills_catalog(&skills_file, &skills);
+        assert!(save_result.is_ok(), "Failed to save skills catalog: {:?}", save_result.err());
+
+        let load_result = load_skills_catalog(&skills_file);
+        assert!(load_result.is_ok(), "Failed to load skills catalog: {:?}", load_result.err());
+        assert_eq!(load_result.unwrap(), skills);
+    }
+
+    #[test]
+    fn test_save_and_load_role_requirements() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let roles_file = dir.path().join("roles.json");
+        assert_eq!(load_role_requirements(&roles_file), Ok(Vec::new()), "Missing requirements should load as empty");
+
+        let requirements = vec![RoleRequirement {
+            position: "Team Lead".to_string(),
+            skills: vec![
+                SkillRequirement { skill: "Coaching".to_string(), level: 3 },
+                SkillRequirement { skill: "Rust".to_string(), level: 4 },
+            ],
+        }];
+        let save_result = save_role_requirements(&roles_file, &requirements);
+        assert!(save_result.is_ok(), "Failed to save role requirements: {:?}", save_result.err());
+        assert_eq!(load_role_requirements(&roles_file), Ok(requirements));
+    }
+
+    #[test]
+    fn test_save_and_load_succession_plan() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let succession_file = dir.path().join("succession.json");
+        assert_eq!(load_succession_plan(&succession_file), Ok(SuccessionPlan::default()));
+
+        let mut plan = SuccessionPlan::default();
+        plan.add_role("Head of Sales", Some(Criticality::High));
+        plan.add_successor("Head of Sales", "u1", Some(Readiness::ReadyNow));
+        let save_result = save_succession_plan(&succession_file, &plan);
+        assert!(save_result.is_ok(), "Failed to save succession plan: {:?}", save_result.err());
+        assert_eq!(load_succession_plan(&succession_file), Ok(plan));
+    }
+
+    #[test]
+    fn test_save_and_load_grid_template() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let template_file = dir.path().join("templates").join("impact_risk.json");
+        let template = GridTemplate::impact_risk();
+
+        let save_result = save_grid_template(&template_file, &template);
+        assert!(save_result.is_ok(), "Failed to save grid template: {:?}", save_result.err());
+
+        let load_result = load_grid_template(&template_file);
+        assert!(load_result.is_ok(), "Failed to load grid template: {:?}", load_result.err());
+        assert_eq!(load_result.unwrap(), template);
+    }
+
+    #[test]
+    fn test_load_grid_template_rejects_invalid_template() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let template_file = dir.path().join("broken.json");
+        let contents = r#"{
+            "name": "Broken",
+            "rows": {"name": "Impact", "levels": ["High", "Low"]},
+            "columns": {"name": "Risk", "levels": ["High", "Low"]},
+            "boxes": [{"id": "5E", "label": "Nowhere"}]
+        }"#;
+        std::fs::write(&template_file, contents).expect("Failed to write template file");
+
+        let err = load_grid_template(&template_file).expect_err("Templates with unknown boxes should be rejected");
+        assert!(err.contains("5E"), "Unexpected error message: {}", err);
+        assert!(load_grid_template(&dir.path().join("missing.json")).is_err(), "Loading a missing template should fail");
+    }
 }
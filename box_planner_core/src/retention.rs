@@ -0,0 +1,239 @@
+use crate::grid::{GridAxis, GridPosition, GridTemplate};
+use crate::models::{Employee, GridBox, GridState};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How likely an employee is to leave. Levels and horizons follow the risk dropdown on
+/// the employee card of the previous (Swift) version of the tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AttritionRisk {
+    Extreme,
+    High,
+    Moderate,
+    Low,
+}
+
+impl AttritionRisk {
+    /// Highest risk first, matching the column order of the risk matrix.
+    pub const ALL: [AttritionRisk; 4] = [AttritionRisk::Extreme, AttritionRisk::High, AttritionRisk::Moderate, AttritionRisk::Low];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AttritionRisk::Extreme => "Extreme",
+            AttritionRisk::High => "High",
+            AttritionRisk::Moderate => "Moderate",
+            AttritionRisk::Low => "Low",
+        }
+    }
+
+    /// The time horizon the old card attached to each level.
+    pub fn horizon(self) -> &'static str {
+        match self {
+            AttritionRisk::Extreme => ">6 months",
+            AttritionRisk::High => ">12 months",
+            AttritionRisk::Moderate => "1-2 years",
+            AttritionRisk::Low => "3+ years",
+        }
+    }
+
+    /// Display color as a hex string (red for the highest risk through green for the lowest).
+    pub fn color(self) -> &'static str {
+        severity_color(self.index())
+    }
+
+    /// Reads a risk value as written in source data, e.g. "High", "Extreme >6 months" or "med".
+    pub fn parse(text: &str) -> Option<Self> {
+        match first_word(text).as_str() {
+            "extreme" | "critical" | "severe" => Some(AttritionRisk::Extreme),
+            "high" | "hi" => Some(AttritionRisk::High),
+            "moderate" | "medium" | "med" | "mid" => Some(AttritionRisk::Moderate),
+            "low" | "lo" => Some(AttritionRisk::Low),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> u8 {
+        AttritionRisk::ALL.iter().position(|risk| *risk == self).unwrap_or_default() as u8
+    }
+}
+
+impl fmt::Display for AttritionRisk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.label(), self.horizon())
+    }
+}
+
+/// How hard an employee would be to replace. Levels follow the impact dropdown on the
+/// employee card of the previous (Swift) version of the tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ImpactOfLoss {
+    Critical,
+    High,
+    Moderate,
+    Low,
+}
+
+impl ImpactOfLoss {
+    /// Highest impact first, matching the row order of the risk matrix.
+    pub const ALL: [ImpactOfLoss; 4] = [ImpactOfLoss::Critical, ImpactOfLoss::High, ImpactOfLoss::Moderate, ImpactOfLoss::Low];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ImpactOfLoss::Critical => "Critical",
+            ImpactOfLoss::High => "High",
+            ImpactOfLoss::Moderate => "Moderate",
+            ImpactOfLoss::Low => "Low",
+        }
+    }
+
+    /// Display color as a hex string (red for the highest impact through green for the lowest).
+    pub fn color(self) -> &'static str {
+        severity_color(self.index())
+    }
+
+    /// Reads an impact value as written in source data, e.g. "Critical", "high" or "Medium".
+    pub fn parse(text: &str) -> Option<Self> {
+        match first_word(text).as_str() {
+            "critical" | "extreme" | "severe" => Some(ImpactOfLoss::Critical),
+            "high" | "hi" => Some(ImpactOfLoss::High),
+            "moderate" | "medium" | "med" | "mid" => Some(ImpactOfLoss::Moderate),
+            "low" | "lo" => Some(ImpactOfLoss::Low),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> u8 {
+        ImpactOfLoss::ALL.iter().position(|impact| *impact == self).unwrap_or_default() as u8
+    }
+}
+
+impl fmt::Display for ImpactOfLoss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+// The leading word of a value, lower-cased, so that "High >12 months" reads as "high".
+fn first_word(text: &str) -> String {
+    text.trim()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+// Red, orange, yellow and green as on the old card, by level index (0 is the most severe).
+fn severity_color(index: u8) -> &'static str {
+    match index {
+        0 => "#BF4228",
+        1 => "#F28C28",
+        2 => "#FECB86",
+        _ => "#597864",
+    }
+}
+
+/// The 4×4 retention grid: impact of loss (rows) × risk of loss (columns), most severe
+/// at the top left. Each box is labelled with its cell, e.g. "High impact / Extreme risk";
+/// its description and color give one of four action bands by combined severity.
+pub fn risk_matrix_template() -> GridTemplate {
+    let rows: Vec<&str> = ImpactOfLoss::ALL.iter().map(|impact| impact.label()).collect();
+    let columns: Vec<&str> = AttritionRisk::ALL.iter().map(|risk| risk.label()).collect();
+    let mut template = GridTemplate {
+        name: "Retention Risk (4×4)".to_string(),
+        rows: GridAxis::new("Impact of Loss", &rows),
+        columns: GridAxis::new("Risk of Loss", &columns),
+        boxes: Vec::new(),
+    };
+    template.boxes = template
+        .positions()
+        .into_iter()
+        .map(|position| {
+            let (band, advice, color) = match position.performance() + position.potential() {
+                0 | 1 => ("Act Now", "Hard to replace and likely to leave soon; agree a retention plan.", severity_color(0)),
+                2 | 3 => ("Retention Plan", "A real loss within the next year or two; address what would make them stay.", severity_color(1)),
+                4 => ("Monitor", "Keep an eye on engagement and prepare cover.", severity_color(2)),
+                _ => ("Stable", "Settled or replaceable; no action needed.", severity_color(3)),
+            };
+            GridBox {
+                id: position.code(),
+                label: format!("{} impact / {} risk", rows[position.performance() as usize], columns[position.potential() as usize]),
+                description: format!("{}: {}", band, advice),
+                color: Some(color.to_string()),
+                icon: None,
+            }
+        })
+        .collect();
+    template
+}
+
+/// The box on the risk matrix for `employee`, if both impact and risk are recorded.
+pub fn risk_position(employee: &Employee) -> Option<GridPosition> {
+    GridPosition::new(employee.impact_of_loss?.index(), employee.attrition_risk?.index())
+}
+
+/// Sets the employee's impact and risk from a box on the risk matrix.
+/// Returns whether `position` is on the matrix.
+pub fn set_risk_position(employee: &mut Employee, position: GridPosition) -> bool {
+    let impact = ImpactOfLoss::ALL.get(position.performance() as usize);
+    let risk = AttritionRisk::ALL.get(position.potential() as usize);
+    let (Some(impact), Some(risk)) = (impact, risk) else { return false };
+    employee.impact_of_loss = Some(*impact);
+    employee.attrition_risk = Some(*risk);
+    true
+}
+
+/// Places every employee with both impact and risk recorded on the risk matrix, in list order.
+pub fn risk_matrix(employees: &[Employee]) -> GridState {
+    let mut grid_state = GridState::default();
+    for employee in employees {
+        if let Some(position) = risk_position(employee) {
+            grid_state.assignments.entry(position).or_default().push(employee.user_id.clone());
+        }
+    }
+    grid_state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_swift_card_values() {
+        assert_eq!(AttritionRisk::parse("Extreme >6 months"), Some(AttritionRisk::Extreme));
+        assert_eq!(AttritionRisk::parse("High >12 months"), Some(AttritionRisk::High));
+        assert_eq!(AttritionRisk::parse(" moderate 1-2 years"), Some(AttritionRisk::Moderate));
+        assert_eq!(AttritionRisk::parse("Low 3+ years"), Some(AttritionRisk::Low));
+        assert_eq!(AttritionRisk::parse("Unknown"), None);
+        assert_eq!(ImpactOfLoss::parse("Critical"), Some(ImpactOfLoss::Critical));
+        assert_eq!(ImpactOfLoss::parse("Medium"), Some(ImpactOfLoss::Moderate));
+        assert_eq!(ImpactOfLoss::parse(""), None);
+    }
+
+    #[test]
+    fn test_risk_matrix_places_employees() {
+        let template = risk_matrix_template();
+        assert!(template.validate().is_ok());
+        assert_eq!(template.box_label(GridPosition::from_code("1A").unwrap()), "Critical impact / Extreme risk");
+        assert_eq!(template.box_label(GridPosition::from_code("4D").unwrap()), "Low impact / Low risk");
+        let labels: std::collections::HashSet<String> =
+            template.positions().into_iter().map(|position| template.box_label(position)).collect();
+        assert_eq!(labels.len(), 16, "Every box names its own cell");
+        assert!(template.grid_box(GridPosition::from_code("1B").unwrap()).unwrap().description.starts_with("Act Now:"));
+        assert_eq!(template.position("High impact / Extreme risk").unwrap(), GridPosition::from_code("2A").unwrap());
+
+        let mut flight_risk = Employee { user_id: "u1".to_string(), ..Employee::default() };
+        assert!(set_risk_position(&mut flight_risk, GridPosition::from_code("1B").unwrap()));
+        assert_eq!(flight_risk.impact_of_loss, Some(ImpactOfLoss::Critical));
+        assert_eq!(flight_risk.attrition_risk, Some(AttritionRisk::High));
+        assert!(!set_risk_position(&mut flight_risk, GridPosition::from_code("5A").unwrap()));
+
+        let unassessed = Employee {
+            user_id: "u2".to_string(),
+            attrition_risk: Some(AttritionRisk::Low),
+            ..Employee::default()
+        };
+        let grid_state = risk_matrix(&[flight_risk, unassessed]);
+        assert_eq!(grid_state.assignments.len(), 1, "Employees need both values to be placed");
+        assert_eq!(grid_state.assignments.get(&GridPosition::from_code("1B").unwrap()), Some(&vec!["u1".to_string()]));
+    }
+}
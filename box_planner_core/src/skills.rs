@@ -0,0 +1,318 @@
+use crate::models::{Employee, RoleRequirement, Skill, SkillRequirement, MAX_PROFICIENCY, MIN_PROFICIENCY};
+
+/// A stable identifier for the skill called `name`, e.g. "Project Management" → "project-management".
+pub fn skill_id(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds the catalog entry called `name` (ignoring case and surrounding whitespace).
+pub fn find_skill<'a>(catalog: &'a [Skill], name: &str) -> Option<&'a Skill> {
+    let name = name.trim();
+    catalog.iter().find(|skill| skill.name.eq_ignore_ascii_case(name))
+}
+
+/// Adds `name` to the catalog unless it is blank or already listed.
+/// Returns whether the catalog changed.
+pub fn add_skill_to_catalog(catalog: &mut Vec<Skill>, name: &str) -> bool {
+    let name = name.trim();
+    if name.is_empty() || find_skill(catalog, name).is_some() {
+        return false;
+    }
+    let base_id = skill_id(name);
+    let mut id = base_id.clone();
+    let mut suffix = 2;
+    while catalog.iter().any(|skill| skill.id == id) {
+        id = format!("{}-{}", base_id, suffix);
+        suffix += 1;
+    }
+    catalog.push(Skill {
+        id,
+        name: name.to_string(),
+    });
+    true
+}
+
+/// Adds every skill held by `employees` that the catalog does not list yet.
+/// Returns the number of skills added.
+pub fn register_employee_skills(catalog: &mut Vec<Skill>, employees: &[Employee]) -> usize {
+    employees
+        .iter()
+        .flat_map(|employee| &employee.skills)
+        .filter(|name| add_skill_to_catalog(catalog, name))
+        .count()
+}
+
+/// Removes the skill called `name` from the catalog and from every employee who has it.
+/// Returns the number of employees who lost the skill.
+pub fn remove_skill_from_catalog(catalog: &mut Vec<Skill>, employees: &mut [Employee], name: &str) -> usize {
+    let name = name.trim();
+    catalog.retain(|skill| !skill.name.eq_ignore_ascii_case(name));
+    employees
+        .iter_mut()
+        .map(|employee| employee.remove_skill(name))
+        .filter(|removed| *removed)
+        .count()
+}
+
+/// The IDs of the employees who have the skill called `name`.
+pub fn employees_with_skill<'a>(employees: &'a [Employee], name: &str) -> Vec<&'a str> {
+    employees
+        .iter()
+        .filter(|employee| employee.has_skill(name))
+        .map(|employee| employee.user_id.as_str())
+        .collect()
+}
+
+/// A short name for a proficiency level, e.g. 3 → "Competent".
+pub fn proficiency_label(level: u8) -> &'static str {
+    match level {
+        1 => "Novice",
+        2 => "Basic",
+        3 => "Competent",
+        4 => "Advanced",
+        5 => "Expert",
+        _ => "Unknown",
+    }
+}
+
+/// The requirements declared for `position` (ignoring case and surrounding whitespace).
+pub fn find_role_requirement<'a>(requirements: &'a [RoleRequirement], position: &str) -> Option<&'a RoleRequirement> {
+    let position = position.trim();
+    requirements
+        .iter()
+        .find(|requirement| requirement.position.trim().eq_ignore_ascii_case(position))
+}
+
+/// Sets the proficiency `position` requires for `skill`; `None` drops the requirement.
+/// Roles left without requirements are removed. Returns whether anything changed.
+pub fn set_role_requirement(requirements: &mut Vec<RoleRequirement>, position: &str, skill: &str, level: Option<u8>) -> bool {
+    let (position, skill) = (position.trim(), skill.trim());
+    if position.is_empty() || skill.is_empty() {
+        return false;
+    }
+    if level.is_some_and(|level| !(MIN_PROFICIENCY..=MAX_PROFICIENCY).contains(&level)) {
+        return false;
+    }
+    let index = match requirements
+        .iter()
+        .position(|requirement| requirement.position.trim().eq_ignore_ascii_case(position))
+    {
+        Some(index) => index,
+        None if level.is_none() => return false,
+        None => {
+            requirements.push(RoleRequirement {
+                position: position.to_string(),
+                skills: Vec::new(),
+            });
+            requirements.len() - 1
+        }
+    };
+
+    let role = &mut requirements[index];
+    let existing = role.skills.iter().position(|required| required.skill.eq_ignore_ascii_case(skill));
+    let changed = match (existing, level) {
+        (Some(existing), Some(level)) => {
+            let changed = role.skills[existing].level != level;
+            role.skills[existing].level = level;
+            changed
+        }
+        (Some(existing), None) => {
+            role.skills.remove(existing);
+            true
+        }
+        (None, Some(level)) => {
+            role.skills.push(SkillRequirement {
+                skill: skill.to_string(),
+                level,
+            });
+            true
+        }
+        (None, None) => false,
+    };
+    if requirements[index].skills.is_empty() {
+        requirements.remove(index);
+    }
+    changed
+}
+
+/// A required skill an employee lacks or holds below the required proficiency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillGap {
+    pub skill: String,
+    pub required: u8,
+    /// The employee's proficiency, or `None` if they do not have the skill at all.
+    pub actual: Option<u8>,
+}
+
+impl SkillGap {
+    /// How many levels short of the requirement the employee is.
+    pub fn shortfall(&self) -> u8 {
+        self.required.saturating_sub(self.actual.unwrap_or(0))
+    }
+}
+
+/// The gaps between what `employee` holds and what `requirement` asks for, in requirement order.
+/// A skill held without a recorded level counts as `MIN_PROFICIENCY`.
+pub fn skill_gaps(employee: &Employee, requirement: &RoleRequirement) -> Vec<SkillGap> {
+    requirement
+        .skills
+        .iter()
+        .filter_map(|required| {
+            let actual = employee
+                .has_skill(&required.skill)
+                .then(|| employee.skill_level(&required.skill).unwrap_or(MIN_PROFICIENCY));
+            (actual.unwrap_or(0) < required.level).then(|| SkillGap {
+                skill: required.skill.clone(),
+                required: required.level,
+                actual,
+            })
+        })
+        .collect()
+}
+
+/// The gaps between `employee` and the requirements of their current position;
+/// empty if the position has no requirements.
+pub fn employee_skill_gaps(employee: &Employee, requirements: &[RoleRequirement]) -> Vec<SkillGap> {
+    find_role_requirement(requirements, &employee.current_position)
+        .map(|requirement| skill_gaps(employee, requirement))
+        .unwrap_or_default()
+}
+
+/// One skill a team falls short on, summed over its members' own role requirements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamSkillGap {
+    pub skill: String,
+    /// IDs of the members below the proficiency their role requires.
+    pub employees_short: Vec<String>,
+    /// Levels missing across those members.
+    pub total_shortfall: u32,
+}
+
+/// The skills `team` falls short on, largest total shortfall first (ties by skill name).
+pub fn team_skill_gaps<'a>(
+    team: impl IntoIterator<Item = &'a Employee>,
+    requirements: &[RoleRequirement],
+) -> Vec<TeamSkillGap> {
+    let mut gaps: Vec<TeamSkillGap> = Vec::new();
+    for employee in team {
+        for gap in employee_skill_gaps(employee, requirements) {
+            let index = match gaps.iter().position(|team_gap| team_gap.skill.eq_ignore_ascii_case(&gap.skill)) {
+                Some(index) => index,
+                None => {
+                    gaps.push(TeamSkillGap {
+                        skill: gap.skill.clone(),
+                        employees_short: Vec::new(),
+                        total_shortfall: 0,
+                    });
+                    gaps.len() - 1
+                }
+            };
+            gaps[index].employees_short.push(employee.user_id.clone());
+            gaps[index].total_shortfall += u32::from(gap.shortfall());
+        }
+    }
+    gaps.sort_by(|a, b| b.total_shortfall.cmp(&a.total_shortfall).then_with(|| a.skill.cmp(&b.skill)));
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee(id: &str, skills: &[&str]) -> Employee {
+        Employee {
+            user_id: id.to_string(),
+            skills: skills.iter().map(|skill| skill.to_string()).collect(),
+            ..Employee::default()
+        }
+    }
+
+    #[test]
+    fn test_skill_ids_are_unique_slugs() {
+        let mut catalog = Vec::new();
+        assert!(add_skill_to_catalog(&mut catalog, " Project Management "));
+        assert!(!add_skill_to_catalog(&mut catalog, "project management"), "Names are matched ignoring case");
+        assert!(add_skill_to_catalog(&mut catalog, "Project-Management!"));
+        assert!(!add_skill_to_catalog(&mut catalog, "   "));
+
+        let ids: Vec<&str> = catalog.iter().map(|skill| skill.id.as_str()).collect();
+        assert_eq!(ids, vec!["project-management", "project-management-2"]);
+        assert_eq!(find_skill(&catalog, "PROJECT MANAGEMENT").map(|skill| skill.name.as_str()), Some("Project Management"));
+    }
+
+    #[test]
+    fn test_register_and_remove_employee_skills() {
+        let mut catalog = vec![Skill {
+            id: "rust".to_string(),
+            name: "Rust".to_string(),
+        }];
+        let mut employees = vec![employee("u1", &["rust", "SQL"]), employee("u2", &["SQL", "Negotiation"])];
+
+        assert_eq!(register_employee_skills(&mut catalog, &employees), 2);
+        let names: Vec<&str> = catalog.iter().map(|skill| skill.name.as_str()).collect();
+        assert_eq!(names, vec!["Rust", "SQL", "Negotiation"]);
+        assert_eq!(employees_with_skill(&employees, "sql"), vec!["u1", "u2"]);
+
+        assert_eq!(remove_skill_from_catalog(&mut catalog, &mut employees, "SQL"), 2);
+        assert!(find_skill(&catalog, "SQL").is_none());
+        assert_eq!(employees[0].skills, vec!["rust"]);
+        assert_eq!(employees[1].skills, vec!["Negotiation"]);
+    }
+
+    #[test]
+    fn test_role_requirements_are_edited_in_place() {
+        let mut requirements = Vec::new();
+        assert!(set_role_requirement(&mut requirements, "Team Lead", "Coaching", Some(3)));
+        assert!(set_role_requirement(&mut requirements, "team lead ", "Rust", Some(4)));
+        assert!(!set_role_requirement(&mut requirements, "Team Lead", "rust", Some(4)), "Same level is no change");
+        assert!(!set_role_requirement(&mut requirements, "Team Lead", "Rust", Some(9)), "Out-of-range levels are ignored");
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(find_role_requirement(&requirements, "TEAM LEAD").map(|role| role.skills.len()), Some(2));
+
+        assert!(set_role_requirement(&mut requirements, "Team Lead", "Coaching", None));
+        assert!(set_role_requirement(&mut requirements, "Team Lead", "Rust", None));
+        assert!(requirements.is_empty(), "Roles without requirements are dropped");
+    }
+
+    #[test]
+    fn test_employee_and_team_skill_gaps() {
+        let requirements = vec![RoleRequirement {
+            position: "Engineer".to_string(),
+            skills: vec![
+                SkillRequirement { skill: "Rust".to_string(), level: 4 },
+                SkillRequirement { skill: "SQL".to_string(), level: 2 },
+            ],
+        }];
+        let mut strong = Employee {
+            current_position: "Engineer".to_string(),
+            ..employee("u1", &[])
+        };
+        strong.set_skill_level("Rust", Some(5));
+        strong.set_skill_level("SQL", Some(2));
+        let mut junior = Employee {
+            current_position: "engineer".to_string(),
+            ..employee("u2", &["SQL"])
+        };
+        junior.set_skill_level("rust", Some(2));
+        let other_role = employee("u3", &[]);
+
+        assert!(employee_skill_gaps(&strong, &requirements).is_empty());
+        let gaps = employee_skill_gaps(&junior, &requirements);
+        assert_eq!(gaps.len(), 2, "An unlevelled skill counts as the lowest proficiency");
+        assert_eq!((gaps[0].actual, gaps[0].shortfall()), (Some(2), 2));
+        assert_eq!((gaps[1].actual, gaps[1].shortfall()), (Some(1), 1));
+        assert!(employee_skill_gaps(&other_role, &requirements).is_empty());
+
+        let team = [strong, junior, other_role];
+        let team_gaps = team_skill_gaps(&team, &requirements);
+        assert_eq!(team_gaps.len(), 2);
+        assert_eq!(team_gaps[0].skill, "Rust");
+        assert_eq!(team_gaps[0].employees_short, vec!["u2"]);
+        assert_eq!(team_gaps[0].total_shortfall, 2);
+        assert_eq!(team_gaps[1].skill, "SQL");
+    }
+}
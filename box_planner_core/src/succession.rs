@@ -0,0 +1,535 @@
+use crate::models::Employee;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Headers of the succession columns in the legacy HRIS export. The source file spells
+/// them inconsistently (curly apostrophes, stray spaces), so they are matched loosely.
+pub const CRITICALITY_HEADER: &str = "Criticality of Role";
+pub const SUCCESSOR_HEADER: &str = "Potential Successor";
+pub const READINESS_HEADER: &str = "Successor's Readiness";
+pub const DEVELOPMENT_HEADER: &str = "Successor Development";
+
+/// Separator between several successors (and their readiness values) in one cell.
+pub const SUCCESSORS_SEPARATOR: char = ';';
+
+/// How much the organisation depends on a role being filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Criticality {
+    High,
+    Medium,
+    Low,
+}
+
+impl Criticality {
+    pub const ALL: [Criticality; 3] = [Criticality::High, Criticality::Medium, Criticality::Low];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Criticality::High => "High",
+            Criticality::Medium => "Medium",
+            Criticality::Low => "Low",
+        }
+    }
+
+    /// Reads a criticality value as written in source data (e.g. "High", "critical", "Med").
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "high" | "hi" | "h" | "critical" | "very high" => Some(Criticality::High),
+            "medium" | "med" | "mid" | "m" | "moderate" => Some(Criticality::Medium),
+            "low" | "lo" | "l" => Some(Criticality::Low),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// When a successor could step into the role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Readiness {
+    ReadyNow,
+    OneToTwoYears,
+    ThreeToFiveYears,
+}
+
+impl Readiness {
+    pub const ALL: [Readiness; 3] = [Readiness::ReadyNow, Readiness::OneToTwoYears, Readiness::ThreeToFiveYears];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Readiness::ReadyNow => "Ready now",
+            Readiness::OneToTwoYears => "1-2 years",
+            Readiness::ThreeToFiveYears => "3-5 years",
+        }
+    }
+
+    /// Reads a readiness band as written in source data, e.g. "Ready Now", "1–2 yrs" or "3-5 Years".
+    pub fn parse(text: &str) -> Option<Self> {
+        let text: String = text
+            .trim()
+            .to_ascii_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        match text.as_str() {
+            "readynow" | "now" | "ready" | "immediate" => Some(Readiness::ReadyNow),
+            _ if text.starts_with("12") => Some(Readiness::OneToTwoYears),
+            _ if text.starts_with("35") => Some(Readiness::ThreeToFiveYears),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Readiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A candidate to take over a critical role.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Successor {
+    pub employee_id: String,
+    #[serde(default)]
+    pub readiness: Option<Readiness>,
+    /// What the candidate still needs to be ready, e.g. "Lead a project end to end".
+    #[serde(default)]
+    pub development: String,
+}
+
+/// A position the organisation plans successors for. Successors are ranked, first is preferred.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CriticalRole {
+    pub position: String,
+    #[serde(default)]
+    pub incumbent_id: Option<String>,
+    #[serde(default)]
+    pub criticality: Option<Criticality>,
+    #[serde(default)]
+    pub successors: Vec<Successor>,
+}
+
+/// How many successors a role has in each readiness band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BenchStrength {
+    pub ready_now: usize,
+    pub one_to_two_years: usize,
+    pub three_to_five_years: usize,
+    /// Successors whose readiness has not been assessed.
+    pub unassessed: usize,
+}
+
+impl BenchStrength {
+    pub fn total(&self) -> usize {
+        self.ready_now + self.one_to_two_years + self.three_to_five_years + self.unassessed
+    }
+}
+
+impl CriticalRole {
+    pub fn successor(&self, employee_id: &str) -> Option<&Successor> {
+        self.successors.iter().find(|successor| successor.employee_id == employee_id)
+    }
+
+    pub fn successor_mut(&mut self, employee_id: &str) -> Option<&mut Successor> {
+        self.successors.iter_mut().find(|successor| successor.employee_id == employee_id)
+    }
+
+    pub fn bench_strength(&self) -> BenchStrength {
+        let mut bench = BenchStrength::default();
+        for successor in &self.successors {
+            match successor.readiness {
+                Some(Readiness::ReadyNow) => bench.ready_now += 1,
+                Some(Readiness::OneToTwoYears) => bench.one_to_two_years += 1,
+                Some(Readiness::ThreeToFiveYears) => bench.three_to_five_years += 1,
+                None => bench.unassessed += 1,
+            }
+        }
+        bench
+    }
+}
+
+/// All critical roles of a project with their ranked successors.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SuccessionPlan {
+    #[serde(default)]
+    pub roles: Vec<CriticalRole>,
+}
+
+impl SuccessionPlan {
+    /// The role for `position` (ignoring case and surrounding whitespace).
+    pub fn role(&self, position: &str) -> Option<&CriticalRole> {
+        let position = position.trim();
+        self.roles.iter().find(|role| role.position.eq_ignore_ascii_case(position))
+    }
+
+    pub fn role_mut(&mut self, position: &str) -> Option<&mut CriticalRole> {
+        let position = position.trim();
+        self.roles.iter_mut().find(|role| role.position.eq_ignore_ascii_case(position))
+    }
+
+    /// Adds `position` as a critical role unless it is blank or already planned.
+    /// Returns whether the plan changed.
+    pub fn add_role(&mut self, position: &str, criticality: Option<Criticality>) -> bool {
+        let position = position.trim();
+        if position.is_empty() || self.role(position).is_some() {
+            return false;
+        }
+        self.roles.push(CriticalRole {
+            position: position.to_string(),
+            criticality,
+            ..CriticalRole::default()
+        });
+        true
+    }
+
+    /// Removes the role for `position`. Returns whether it was planned.
+    pub fn remove_role(&mut self, position: &str) -> bool {
+        let position = position.trim();
+        let before = self.roles.len();
+        self.roles.retain(|role| !role.position.eq_ignore_ascii_case(position));
+        self.roles.len() != before
+    }
+
+    /// Appends `employee_id` to the end of the role's successor list, unless the role is
+    /// unknown, the employee already is a successor or holds the role.
+    /// Returns whether the plan changed.
+    pub fn add_successor(&mut self, position: &str, employee_id: &str, readiness: Option<Readiness>) -> bool {
+        let Some(role) = self.role_mut(position) else { return false };
+        if role.successor(employee_id).is_some() || role.incumbent_id.as_deref() == Some(employee_id) {
+            return false;
+        }
+        role.successors.push(Successor {
+            employee_id: employee_id.to_string(),
+            readiness,
+            development: String::new(),
+        });
+        true
+    }
+
+    /// Removes `employee_id` from the role's successors. Returns whether they were listed.
+    pub fn remove_successor(&mut self, position: &str, employee_id: &str) -> bool {
+        let Some(role) = self.role_mut(position) else { return false };
+        let before = role.successors.len();
+        role.successors.retain(|successor| successor.employee_id != employee_id);
+        role.successors.len() != before
+    }
+
+    /// Moves `employee_id` to `rank` (0 is the preferred successor), clamped to the list.
+    /// Returns whether the order changed.
+    pub fn rank_successor(&mut self, position: &str, employee_id: &str, rank: usize) -> bool {
+        let Some(role) = self.role_mut(position) else { return false };
+        let Some(current) = role.successors.iter().position(|successor| successor.employee_id == employee_id) else {
+            return false;
+        };
+        let rank = rank.min(role.successors.len() - 1);
+        if rank == current {
+            return false;
+        }
+        let successor = role.successors.remove(current);
+        role.successors.insert(rank, successor);
+        true
+    }
+
+    /// Drops every successor and incumbent that is not in `employees`, e.g. after a new import.
+    /// Returns the number of successors removed.
+    pub fn retain_employees(&mut self, employees: &[Employee]) -> usize {
+        let known = |id: &str| employees.iter().any(|employee| employee.user_id == id);
+        let mut removed = 0;
+        for role in &mut self.roles {
+            if role.incumbent_id.as_deref().is_some_and(|id| !known(id)) {
+                role.incumbent_id = None;
+            }
+            let before = role.successors.len();
+            role.successors.retain(|successor| known(&successor.employee_id));
+            removed += before - role.successors.len();
+        }
+        removed
+    }
+
+    /// The roles `employee_id` is lined up for, with their rank on each.
+    pub fn roles_for_successor(&self, employee_id: &str) -> Vec<(&CriticalRole, usize)> {
+        self.roles
+            .iter()
+            .filter_map(|role| {
+                role.successors
+                    .iter()
+                    .position(|successor| successor.employee_id == employee_id)
+                    .map(|rank| (role, rank))
+            })
+            .collect()
+    }
+}
+
+/// A successor named in the source data that matches no employee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedSuccessor {
+    pub incumbent_id: String,
+    pub value: String,
+}
+
+/// The succession plan read from imported columns, with anything that could not be resolved.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SuccessionImport {
+    pub plan: SuccessionPlan,
+    pub unresolved: Vec<UnresolvedSuccessor>,
+    /// Readiness values that are not a known band, as (incumbent ID, value).
+    pub unknown_readiness: Vec<(String, String)>,
+}
+
+impl SuccessionImport {
+    /// Whether the employees carried any succession columns at all.
+    pub fn is_empty(&self) -> bool {
+        self.plan.roles.is_empty() && self.unresolved.is_empty()
+    }
+}
+
+// Compares headers ignoring case, curly apostrophes and repeated or surrounding spaces,
+// so " Successor’s Readiness" matches "Successor's Readiness".
+fn header_matches(header: &str, expected: &str) -> bool {
+    let normalize = |text: &str| {
+        text.replace(['\u{2019}', '\u{2018}'], "'")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_lowercase()
+    };
+    normalize(header) == normalize(expected)
+}
+
+fn succession_column<'a>(employee: &'a Employee, header: &str) -> Option<&'a str> {
+    employee
+        .custom_fields
+        .iter()
+        .find(|field| header_matches(&field.header, header))
+        .map(|field| field.value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+// Finds the employee a successor cell refers to: a user ID, else a "First Last" name.
+fn resolve_successor<'a>(employees: &'a [Employee], value: &str) -> Option<&'a Employee> {
+    let value = value.trim();
+    employees.iter().find(|employee| employee.user_id == value).or_else(|| {
+        employees.iter().find(|employee| {
+            format!("{} {}", employee.first_name.trim(), employee.last_name.trim()).eq_ignore_ascii_case(value)
+        })
+    })
+}
+
+/// Builds a succession plan from the legacy succession columns, which imports keep as
+/// custom fields on the incumbent's row. Each incumbent's current position becomes a
+/// critical role; several successors in one cell are separated by `SUCCESSORS_SEPARATOR`,
+/// with readiness values in the same order (a single readiness applies to all of them).
+/// Development notes are split the same way when the cell has one per successor; otherwise
+/// every successor gets the whole cell.
+pub fn succession_from_custom_fields(employees: &[Employee]) -> SuccessionImport {
+    let mut import = SuccessionImport::default();
+    for incumbent in employees {
+        let criticality_value = succession_column(incumbent, CRITICALITY_HEADER);
+        let successor_value = succession_column(incumbent, SUCCESSOR_HEADER);
+        if criticality_value.is_none() && successor_value.is_none() {
+            continue;
+        }
+        let position = incumbent.current_position.trim();
+        if position.is_empty() {
+            continue;
+        }
+        let criticality = criticality_value.and_then(Criticality::parse);
+        if import.plan.add_role(position, criticality) {
+            if let Some(role) = import.plan.role_mut(position) {
+                role.incumbent_id = Some(incumbent.user_id.clone());
+            }
+        } else if let Some(role) = import.plan.role_mut(position) {
+            // Several people in the same position: keep the highest criticality
+            role.criticality = role.criticality.into_iter().chain(criticality).min();
+        }
+
+        let readiness_values: Vec<&str> = succession_column(incumbent, READINESS_HEADER)
+            .map(|value| value.split(SUCCESSORS_SEPARATOR).map(str::trim).collect())
+            .unwrap_or_default();
+        let names: Vec<&str> = successor_value
+            .into_iter()
+            .flat_map(|value| value.split(SUCCESSORS_SEPARATOR))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+        let development = succession_column(incumbent, DEVELOPMENT_HEADER).unwrap_or_default();
+        let development_values: Vec<&str> = development.split(SUCCESSORS_SEPARATOR).map(str::trim).collect();
+        for (index, name) in names.iter().copied().enumerate() {
+            let Some(successor) = resolve_successor(employees, name) else {
+                import.unresolved.push(UnresolvedSuccessor {
+                    incumbent_id: incumbent.user_id.clone(),
+                    value: name.to_string(),
+                });
+                continue;
+            };
+            let readiness_value = match readiness_values.as_slice() {
+                [single] => Some(*single),
+                values => values.get(index).copied(),
+            };
+            let readiness = readiness_value.and_then(Readiness::parse);
+            if let (Some(value), None) = (readiness_value.filter(|value| !value.is_empty()), readiness) {
+                import.unknown_readiness.push((incumbent.user_id.clone(), value.to_string()));
+            }
+            if import.plan.add_successor(position, &successor.user_id, readiness) {
+                if let Some(added) = import.plan.role_mut(position).and_then(|role| role.successor_mut(&successor.user_id)) {
+                    added.development = match development_values.len() {
+                        count if count > 1 && count == names.len() => development_values[index].to_string(),
+                        _ => development.to_string(),
+                    };
+                }
+            }
+        }
+    }
+    import
+}
+
+/// Writes `plan` into the legacy succession columns, the reverse of
+/// `succession_from_custom_fields`, so an exported file carries the plan as edited. Each role
+/// goes on its incumbent's row or, without one, the first row holding its position; successors
+/// are written by user ID. The columns of every other row are emptied, and they are only added
+/// to rows that have something to write.
+pub fn write_succession_fields(plan: &SuccessionPlan, employees: &mut [Employee]) {
+    let mut role_rows: HashMap<usize, &CriticalRole> = HashMap::new();
+    for role in &plan.roles {
+        let row = role
+            .incumbent_id
+            .as_deref()
+            .and_then(|incumbent_id| employees.iter().position(|employee| employee.user_id == incumbent_id))
+            .or_else(|| {
+                employees
+                    .iter()
+                    .position(|employee| employee.current_position.trim().eq_ignore_ascii_case(role.position.trim()))
+            });
+        if let Some(row) = row {
+            role_rows.entry(row).or_insert(role);
+        }
+    }
+    let separator = format!("{} ", SUCCESSORS_SEPARATOR);
+    for (row, employee) in employees.iter_mut().enumerate() {
+        let values = match role_rows.get(&row) {
+            Some(role) => {
+                let successors = &role.successors;
+                let developments: Vec<&str> = successors.iter().map(|s| s.development.as_str()).collect();
+                // One shared note is written once, as imports give it to every successor
+                let development = match developments.first() {
+                    Some(first) if developments.iter().all(|development| development == first) => first.to_string(),
+                    _ => developments.join(&separator),
+                };
+                [
+                    role.criticality.map(Criticality::label).unwrap_or_default().to_string(),
+                    successors.iter().map(|s| s.employee_id.as_str()).collect::<Vec<_>>().join(&separator),
+                    successors.iter().map(|s| s.readiness.map(Readiness::label).unwrap_or_default()).collect::<Vec<_>>().join(&separator),
+                    development,
+                ]
+            }
+            None => Default::default(),
+        };
+        let headers = [CRITICALITY_HEADER, SUCCESSOR_HEADER, READINESS_HEADER, DEVELOPMENT_HEADER];
+        for (header, value) in headers.into_iter().zip(values) {
+            // Keep the source file's spelling of the header
+            match employee.custom_fields.iter_mut().find(|field| header_matches(&field.header, header)) {
+                Some(field) => field.value = value,
+                None if !value.is_empty() => employee.set_custom_field(header, &value),
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee(id: &str, name: &str, position: &str, succession: &[(&str, &str)]) -> Employee {
+        let (first_name, last_name) = name.split_once(' ').unwrap_or((name, ""));
+        let mut employee = Employee {
+            user_id: id.to_string(),
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            current_position: position.to_string(),
+            ..Employee::default()
+        };
+        for (header, value) in succession {
+            employee.set_custom_field(header, value);
+        }
+        employee
+    }
+
+    #[test]
+    fn test_parse_readiness_and_criticality() {
+        assert_eq!(Readiness::parse("Ready Now"), Some(Readiness::ReadyNow));
+        assert_eq!(Readiness::parse("1\u{2013}2 years"), Some(Readiness::OneToTwoYears));
+        assert_eq!(Readiness::parse(" 3-5 Yrs "), Some(Readiness::ThreeToFiveYears));
+        assert_eq!(Readiness::parse("Someday"), None);
+        assert_eq!(Criticality::parse("critical"), Some(Criticality::High));
+        assert_eq!(Criticality::parse("Med"), Some(Criticality::Medium));
+        assert_eq!(Criticality::parse("n/a"), None);
+    }
+
+    #[test]
+    fn test_plan_ranks_successors_and_counts_bench() {
+        let mut plan = SuccessionPlan::default();
+        assert!(plan.add_role("Head of Sales", Some(Criticality::High)));
+        assert!(!plan.add_role("head of sales ", None), "Roles are matched ignoring case");
+        assert!(plan.add_successor("Head of Sales", "u1", Some(Readiness::OneToTwoYears)));
+        assert!(plan.add_successor("Head of Sales", "u2", Some(Readiness::ReadyNow)));
+        assert!(plan.add_successor("Head of Sales", "u3", None));
+        assert!(!plan.add_successor("Head of Sales", "u2", None), "Successors are listed once");
+
+        assert!(plan.rank_successor("Head of Sales", "u2", 0));
+        let order: Vec<&str> = plan.roles[0].successors.iter().map(|s| s.employee_id.as_str()).collect();
+        assert_eq!(order, vec!["u2", "u1", "u3"]);
+        assert!(plan.rank_successor("Head of Sales", "u2", 99));
+        assert_eq!(plan.roles[0].successors[2].employee_id, "u2");
+
+        let bench = plan.roles[0].bench_strength();
+        assert_eq!((bench.ready_now, bench.one_to_two_years, bench.three_to_five_years, bench.unassessed), (1, 1, 0, 1));
+        assert_eq!(bench.total(), 3);
+        assert_eq!(plan.roles_for_successor("u1").len(), 1);
+
+        let remaining = [employee("u1", "Ann Lee", "Rep", &[])];
+        assert_eq!(plan.retain_employees(&remaining), 2);
+        assert!(plan.remove_successor("Head of Sales", "u1"));
+        assert!(plan.remove_role("HEAD OF SALES"));
+        assert!(plan.roles.is_empty());
+    }
+
+    #[test]
+    fn test_import_from_legacy_columns() {
+        let employees = vec![
+            employee(
+                "m1",
+                "Max Boss",
+                "Head of Sales",
+                &[
+                    ("Criticality of Role", "High"),
+                    ("Potential Successor", "Ann Lee; u2; Zed Unknown"),
+                    (" Successor\u{2019}s Readiness", "Ready Now;3-5 years"),
+                    ("Successor  Development", "Needs P&L exposure"),
+                ],
+            ),
+            employee("u1", "Ann Lee", "Sales Lead", &[("Criticality of Role", "Low")]),
+            employee("u2", "Bob Ray", "Rep", &[("Potential Successor", "")]),
+        ];
+        let import = succession_from_custom_fields(&employees);
+
+        assert_eq!(import.plan.roles.len(), 2, "Rows with only empty succession cells are skipped");
+        let role = import.plan.role("Head of Sales").expect("Role should be imported");
+        assert_eq!(role.incumbent_id.as_deref(), Some("m1"));
+        assert_eq!(role.criticality, Some(Criticality::High));
+        let successors: Vec<(&str, Option<Readiness>)> =
+            role.successors.iter().map(|s| (s.employee_id.as_str(), s.readiness)).collect();
+        assert_eq!(successors, vec![("u1", Some(Readiness::ReadyNow)), ("u2", Some(Readiness::ThreeToFiveYears))]);
+        assert_eq!(role.successors[0].development, "Needs P&L exposure");
+        assert_eq!(
+            import.unresolved,
+            vec![UnresolvedSuccessor { incumbent_id: "m1".to_string(), value: "Zed Unknown".to_string() }]
+        );
+        assert_eq!(import.plan.role("Sales Lead").and_then(|role| role.criticality), Some(Criticality::Low));
+    }
+}
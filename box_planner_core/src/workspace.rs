@@ -0,0 +1,718 @@
+use crate::events::{EventBus, WorkspaceEvent};
+use crate::grid::{nine_box_cycles, seed_grid_from_cycle, GridPosition, GridSeedReport, GridTemplate};
+use crate::history::{employee_changes, Change, Edit, EditTarget, History};
+use crate::labels::{normalize_nine_boxes, LabelScheme};
+use crate::models::{Employee, GridState, ProjectDocument, ProjectSettings, RoleRequirement, Skill, PROJECT_FORMAT_VERSION};
+use crate::skills::{add_skill_to_catalog, register_employee_skills, remove_skill_from_catalog, set_role_requirement};
+use crate::succession::{succession_from_custom_fields, write_succession_fields, SuccessionImport, SuccessionPlan};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum WorkspaceError {
+    #[error("Unknown employee '{0}'")]
+    UnknownEmployee(String),
+    #[error("Box {0} is not on the current grid")]
+    BoxNotOnGrid(GridPosition),
+}
+
+/// What `Workspace::import_employees` did besides replacing the employee list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportSummary {
+    pub new_skills: usize,                     // Skills added to the catalog from the imported employees
+    pub seed_report: Option<GridSeedReport>,   // Set when the grid was seeded from the latest 9-box column
+    pub succession: SuccessionImport,          // The succession columns, if the import had any
+    pub successors_removed: usize,             // Successors dropped because they are no longer employees
+    pub notes_imported: usize,                 // Notes column values copied to employees who had no note
+}
+
+/// An open planning session: employees, their grid placements, notes, the skills catalog,
+/// role requirements, the succession plan and project settings, plus the undo history.
+///
+/// Every mutation goes through a method here, which keeps two invariants: an employee is
+/// placed in at most one box, and every placement names a real employee and a box on the
+/// current grid. The UI and any other front end drive the session through this type, and
+/// can `subscribe` to a `WorkspaceEvent` for every change.
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    employees: Vec<Employee>,
+    grid_state: GridState,
+    notes: HashMap<String, String>,
+    skills: Vec<Skill>,
+    role_requirements: Vec<RoleRequirement>,
+    succession: SuccessionPlan,
+    settings: ProjectSettings,
+    history: History,
+    events: EventBus,
+}
+
+impl Workspace {
+    /// Opens a project document. Placements that break an invariant are dropped; their
+    /// employee IDs are returned alongside the workspace, sorted.
+    pub fn from_project(project: ProjectDocument) -> (Self, Vec<String>) {
+        let mut workspace = Workspace {
+            employees: project.employees,
+            grid_state: project.grid_state,
+            notes: project.notes,
+            skills: project.skills,
+            role_requirements: project.role_requirements,
+            succession: project.succession,
+            settings: project.settings,
+            history: History::default(),
+            events: EventBus::default(),
+        };
+        register_employee_skills(&mut workspace.skills, &workspace.employees);
+        let unplaced = workspace.enforce_placements();
+        (workspace, unplaced)
+    }
+
+    /// Replaces the session with a project document, keeping the listeners. Returns the IDs
+    /// whose placements broke an invariant and were dropped, as `from_project` does.
+    pub fn open_project(&mut self, project: ProjectDocument) -> Vec<String> {
+        let (workspace, unplaced) = Workspace::from_project(project);
+        let events = std::mem::take(&mut self.events);
+        *self = Workspace { events, ..workspace };
+        self.events.emit(WorkspaceEvent::ProjectOpened);
+        unplaced
+    }
+
+    /// Calls `listener` with every change made from now on.
+    pub fn subscribe(&mut self, listener: impl Fn(&WorkspaceEvent) + Send + Sync + 'static) {
+        self.events.subscribe(listener);
+    }
+
+    // The undo history, and the state its edits apply to.
+    fn history_and_target(&mut self) -> (&mut History, EditTarget<'_>) {
+        let target = EditTarget {
+            employees: &mut self.employees,
+            grid_state: &mut self.grid_state,
+            notes: &mut self.notes,
+            skills: &mut self.skills,
+            role_requirements: &mut self.role_requirements,
+            succession: &mut self.succession,
+        };
+        (&mut self.history, target)
+    }
+
+    // Applies (if `apply`) and records `edit`, then reports each change to the listeners.
+    fn commit(&mut self, edit: Edit, apply: bool) -> bool {
+        let events: Vec<WorkspaceEvent> =
+            edit.changes.iter().filter(|change| !change.is_noop()).map(WorkspaceEvent::from_change).collect();
+        let recorded = if apply {
+            let (history, mut target) = self.history_and_target();
+            history.apply(edit, &mut target)
+        } else {
+            self.history.record(edit)
+        };
+        if recorded {
+            events.into_iter().for_each(|event| self.events.emit(event));
+        }
+        recorded
+    }
+
+    /// Bundles the session into a project document.
+    pub fn to_project_document(&self) -> ProjectDocument {
+        ProjectDocument {
+            format_version: PROJECT_FORMAT_VERSION,
+            employees: self.employees.clone(),
+            grid_state: self.grid_state.clone(),
+            notes: self.notes.clone(),
+            note_histories: HashMap::new(), // Kept in the notes store; added by whoever saves the document
+            skills: self.skills.clone(),
+            role_requirements: self.role_requirements.clone(),
+            succession: self.succession.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+
+    pub fn employees(&self) -> &[Employee] {
+        &self.employees
+    }
+
+    pub fn employee(&self, employee_id: &str) -> Option<&Employee> {
+        self.employees.iter().find(|employee| employee.user_id == employee_id)
+    }
+
+    pub fn grid_state(&self) -> &GridState {
+        &self.grid_state
+    }
+
+    /// Employee notes keyed by employee ID.
+    pub fn notes(&self) -> &HashMap<String, String> {
+        &self.notes
+    }
+
+    /// The employees with their Notes column set from the notes, for export. Employees without
+    /// a note keep the Notes value they were imported with.
+    pub fn employees_with_notes(&self) -> Vec<Employee> {
+        let mut employees = self.employees.clone();
+        for employee in &mut employees {
+            if let Some(note) = self.notes.get(&employee.user_id).filter(|note| !note.trim().is_empty()) {
+                employee.notes = Some(note.clone());
+            }
+        }
+        employees
+    }
+
+    /// The employees as a CSV export should write them: the Notes column set from the notes,
+    /// as `employees_with_notes` does, and the succession columns from the succession plan.
+    pub fn employees_for_export(&self) -> Vec<Employee> {
+        let mut employees = self.employees_with_notes();
+        write_succession_fields(&self.succession, &mut employees);
+        employees
+    }
+
+    pub fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    pub fn role_requirements(&self) -> &[RoleRequirement] {
+        &self.role_requirements
+    }
+
+    /// Sets the level `position` requires in `skill`; `None` drops the requirement. Recorded
+    /// as one undoable step. Returns whether the requirements changed.
+    pub fn set_role_requirement(&mut self, position: &str, skill: &str, level: Option<u8>) -> bool {
+        let before = self.role_requirements.clone();
+        if !set_role_requirement(&mut self.role_requirements, position, skill, level) {
+            return false;
+        }
+        let change = Change::RoleRequirements {
+            from: before,
+            to: self.role_requirements.clone(),
+        };
+        let description = format!("Set {} requirement for {}", skill.trim(), position.trim());
+        self.commit(Edit::new(&description, vec![change]), false)
+    }
+
+    pub fn succession(&self) -> &SuccessionPlan {
+        &self.succession
+    }
+
+    /// Applies `edit` to the succession plan, which keeps its own rules, and records the
+    /// result as one undoable step called `description`. `edit` returns whether it changed
+    /// anything; that is passed back.
+    pub fn edit_succession(&mut self, description: &str, edit: impl FnOnce(&mut SuccessionPlan) -> bool) -> bool {
+        let before = self.succession.clone();
+        if !edit(&mut self.succession) {
+            return false;
+        }
+        let change = Change::Succession {
+            from: before,
+            to: self.succession.clone(),
+        };
+        self.commit(Edit::new(description, vec![change]), false)
+    }
+
+    pub fn settings(&self) -> &ProjectSettings {
+        &self.settings
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// The review cycle the grid mirrors: the project's choice, else the latest cycle with
+    /// 9-box data, else the latest cycle of any kind.
+    pub fn placement_cycle(&self) -> Option<String> {
+        self.settings
+            .placement_cycle
+            .clone()
+            .or_else(|| nine_box_cycles(&self.employees).pop())
+            .or_else(|| {
+                self.employees
+                    .iter()
+                    .flat_map(|employee| employee.performance_history.keys())
+                    .max()
+                    .cloned()
+            })
+    }
+
+    /// Assigns the employee to `position`, moving them out of any box they were in, as one
+    /// undoable step. The placement cycle's 9-box value follows so export reflects the move.
+    /// Returns whether anything changed.
+    pub fn assign(&mut self, employee_id: &str, position: GridPosition) -> Result<bool, WorkspaceError> {
+        if !self.settings.grid_template.contains(position) {
+            return Err(WorkspaceError::BoxNotOnGrid(position));
+        }
+        self.place(employee_id, Some(position))
+    }
+
+    /// Takes the employee off the grid as one undoable step. Returns whether they were placed.
+    pub fn unassign(&mut self, employee_id: &str) -> Result<bool, WorkspaceError> {
+        self.place(employee_id, None)
+    }
+
+    fn place(&mut self, employee_id: &str, to: Option<GridPosition>) -> Result<bool, WorkspaceError> {
+        let cycle = self.placement_cycle();
+        let employee = self
+            .employee(employee_id)
+            .ok_or_else(|| WorkspaceError::UnknownEmployee(employee_id.to_string()))?;
+        let name = format!("{} {}", employee.first_name, employee.last_name);
+        let mut changes = vec![Change::Placement {
+            employee_id: employee_id.to_string(),
+            from: self.grid_state.position_of(employee_id),
+            to,
+        }];
+        if let Some(cycle) = cycle {
+            changes.push(Change::NineBox {
+                employee_id: employee_id.to_string(),
+                from: employee.nine_box(&cycle).map(str::to_string),
+                to: to.map(|position| position.code()),
+                cycle,
+            });
+        }
+        let description = match to {
+            Some(position) => format!("Move {} to {}", name, position),
+            None => format!("Remove {} from the grid", name),
+        };
+        Ok(self.commit(Edit::new(&description, changes), true))
+    }
+
+    /// Applies `edit` to the employee and records whatever it changed as one undoable step,
+    /// described as "`description` for <name>". An employee's ID cannot be changed this way.
+    /// Returns whether anything changed.
+    pub fn edit_employee(
+        &mut self,
+        employee_id: &str,
+        description: &str,
+        edit: impl FnOnce(&mut Employee),
+    ) -> Result<bool, WorkspaceError> {
+        let employee = self
+            .employees
+            .iter_mut()
+            .find(|employee| employee.user_id == employee_id)
+            .ok_or_else(|| WorkspaceError::UnknownEmployee(employee_id.to_string()))?;
+        let before = employee.clone();
+        edit(employee);
+        employee.user_id = before.user_id.clone();
+        let changes = employee_changes(&before, employee);
+        let description = format!("{} for {} {}", description, before.first_name, before.last_name);
+        Ok(self.commit(Edit::new(&description, changes), false))
+    }
+
+    /// Tags the employee with the skill called `name`, adding it to the catalog if it is new, as
+    /// one undoable step described as "Add <skill> for <name>". Returns whether the employee
+    /// gained the skill.
+    pub fn add_employee_skill(&mut self, employee_id: &str, name: &str) -> Result<bool, WorkspaceError> {
+        let name = name.trim();
+        let employee = self
+            .employees
+            .iter_mut()
+            .find(|employee| employee.user_id == employee_id)
+            .ok_or_else(|| WorkspaceError::UnknownEmployee(employee_id.to_string()))?;
+        let before = employee.clone();
+        if !employee.add_skill(name) {
+            return Ok(false);
+        }
+        let mut changes = employee_changes(&before, employee);
+        let skills_before = self.skills.clone();
+        add_skill_to_catalog(&mut self.skills, name);
+        changes.push(Change::SkillCatalog {
+            from: skills_before,
+            to: self.skills.clone(),
+        });
+        let description = format!("Add {} for {} {}", name, before.first_name, before.last_name);
+        Ok(self.commit(Edit::new(&description, changes), false))
+    }
+
+    /// Sets or, for `None`, removes the employee's note as one undoable step.
+    /// Returns whether the note changed.
+    pub fn set_note(&mut self, employee_id: &str, note: Option<String>) -> Result<bool, WorkspaceError> {
+        let employee = self
+            .employee(employee_id)
+            .ok_or_else(|| WorkspaceError::UnknownEmployee(employee_id.to_string()))?;
+        let description = format!("Edit note for {} {}", employee.first_name, employee.last_name);
+        let change = Change::Note {
+            employee_id: employee_id.to_string(),
+            from: self.notes.get(employee_id).cloned(),
+            to: note,
+        };
+        Ok(self.commit(Edit::new(&description, vec![change]), true))
+    }
+
+    /// Reverts the most recent recorded edit and returns it.
+    pub fn undo(&mut self) -> Option<Edit> {
+        let (history, mut target) = self.history_and_target();
+        let edit = history.undo(&mut target).cloned()?;
+        for change in edit.changes.iter().rev() {
+            self.events.emit(WorkspaceEvent::from_change(&change.inverse()));
+        }
+        self.events.emit(WorkspaceEvent::EditUndone { description: edit.description.clone() });
+        Some(edit)
+    }
+
+    /// Re-applies the most recently undone edit and returns it.
+    pub fn redo(&mut self) -> Option<Edit> {
+        let (history, mut target) = self.history_and_target();
+        let edit = history.redo(&mut target).cloned()?;
+        for change in &edit.changes {
+            self.events.emit(WorkspaceEvent::from_change(change));
+        }
+        self.events.emit(WorkspaceEvent::EditRedone { description: edit.description.clone() });
+        Some(edit)
+    }
+
+    /// Adds a skill to the catalog as one undoable step. Returns `false` if it is blank or
+    /// already there.
+    pub fn add_catalog_skill(&mut self, name: &str) -> bool {
+        let before = self.skills.clone();
+        if !add_skill_to_catalog(&mut self.skills, name) {
+            return false;
+        }
+        let change = Change::SkillCatalog {
+            from: before,
+            to: self.skills.clone(),
+        };
+        self.commit(Edit::new(&format!("Add skill {}", name.trim()), vec![change]), false)
+    }
+
+    /// Removes a skill from the catalog and from every employee, as one undoable step.
+    /// Returns the number of employees who lost the skill.
+    pub fn remove_catalog_skill(&mut self, name: &str) -> usize {
+        let skills_before = self.skills.clone();
+        let employees_before = self.employees.clone();
+        let affected = remove_skill_from_catalog(&mut self.skills, &mut self.employees, name);
+        let mut changes = vec![Change::SkillCatalog {
+            from: skills_before,
+            to: self.skills.clone(),
+        }];
+        changes.extend(
+            employees_before
+                .iter()
+                .zip(&self.employees)
+                .flat_map(|(before, after)| employee_changes(before, after)),
+        );
+        self.commit(Edit::new(&format!("Remove skill {}", name.trim()), changes), false);
+        affected
+    }
+
+    /// Switches the grid to `template`. Anyone placed in a box the new grid does not have is
+    /// unplaced; their IDs are returned. The undo history is cleared, since its moves refer
+    /// to the old grid.
+    pub fn set_grid_template(&mut self, template: GridTemplate) -> Vec<String> {
+        self.settings.grid_template = template;
+        self.history.clear();
+        let unplaced = self.enforce_placements();
+        self.events.emit(WorkspaceEvent::GridTemplateChanged {
+            name: self.settings.grid_template.name.clone(),
+            unplaced: unplaced.clone(),
+        });
+        unplaced
+    }
+
+    pub fn set_label_scheme(&mut self, scheme: LabelScheme) {
+        self.settings.label_scheme = scheme;
+        self.events.emit(WorkspaceEvent::LabelSchemeChanged { name: self.settings.label_scheme.name.clone() });
+    }
+
+    /// Rebuilds the grid from `cycle`'s 9-box column and makes `cycle` the one grid moves
+    /// update. The undo history is cleared.
+    pub fn seed_grid_from(&mut self, cycle: &str) -> GridSeedReport {
+        let (grid_state, report) = seed_grid_from_cycle(&self.employees, cycle, &self.settings.grid_template);
+        self.grid_state = grid_state;
+        self.settings.placement_cycle = Some(cycle.to_string());
+        self.history.clear();
+        self.events.emit(WorkspaceEvent::GridReseeded { cycle: cycle.to_string() });
+        report
+    }
+
+    /// Replaces the employee list with an import. 9-box values are stored as codes, new
+    /// skills join the catalog, the succession plan is rebuilt from the succession columns
+    /// (or trimmed to the new employees if there are none) and the grid is reseeded from the
+    /// latest 9-box column, or left empty without one. Notes column values become the notes of
+    /// employees who have none; existing notes win. The undo history is cleared.
+    pub fn import_employees(&mut self, employees: Vec<Employee>) -> ImportSummary {
+        self.employees = employees;
+        normalize_nine_boxes(&mut self.employees, &self.settings.grid_template);
+        let mut summary = ImportSummary {
+            new_skills: register_employee_skills(&mut self.skills, &self.employees),
+            succession: succession_from_custom_fields(&self.employees),
+            ..ImportSummary::default()
+        };
+        if summary.succession.is_empty() {
+            summary.successors_removed = self.succession.retain_employees(&self.employees);
+        } else {
+            self.succession = summary.succession.plan.clone();
+        }
+        // The old placements refer to the previous employee list
+        self.settings.placement_cycle = None;
+        self.grid_state = GridState::default();
+        self.history.clear();
+        self.events.emit(WorkspaceEvent::EmployeesImported { count: self.employees.len() });
+        if summary.new_skills > 0 {
+            self.events.emit(WorkspaceEvent::SkillCatalogChanged);
+        }
+        if !summary.succession.is_empty() || summary.successors_removed > 0 {
+            self.events.emit(WorkspaceEvent::SuccessionPlanChanged);
+        }
+        summary.notes_imported = self.import_csv_notes();
+        if let Some(cycle) = nine_box_cycles(&self.employees).pop() {
+            summary.seed_report = Some(self.seed_grid_from(&cycle));
+        }
+        summary
+    }
+
+    // Copies the Notes column into the notes of employees without one. Returns how many.
+    fn import_csv_notes(&mut self) -> usize {
+        let mut imported = 0;
+        for employee in &self.employees {
+            let Some(csv_note) = employee.notes.as_ref().filter(|note| !note.trim().is_empty()) else { continue };
+            if self.notes.get(&employee.user_id).is_some_and(|note| !note.trim().is_empty()) {
+                continue;
+            }
+            self.notes.insert(employee.user_id.clone(), csv_note.clone());
+            self.events.emit(WorkspaceEvent::NoteChanged { employee_id: employee.user_id.clone() });
+            imported += 1;
+        }
+        imported
+    }
+
+    // Drops placements of unknown employees, placements in boxes off the current grid and
+    // second placements of the same employee, then any boxes left empty. Returns the IDs
+    // that were dropped, sorted.
+    fn enforce_placements(&mut self) -> Vec<String> {
+        let mut unplaced = self.grid_state.retain_template(&self.settings.grid_template);
+        let known: HashSet<&str> = self.employees.iter().map(|employee| employee.user_id.as_str()).collect();
+        let mut placed: HashSet<String> = HashSet::new();
+        let mut positions: Vec<GridPosition> = self.grid_state.assignments.keys().copied().collect();
+        positions.sort();
+        for position in positions {
+            if let Some(employee_ids) = self.grid_state.assignments.get_mut(&position) {
+                employee_ids.retain(|id| {
+                    let keep = known.contains(id.as_str()) && placed.insert(id.clone());
+                    if !keep {
+                        unplaced.push(id.clone());
+                    }
+                    keep
+                });
+            }
+        }
+        self.grid_state.assignments.retain(|_, employee_ids| !employee_ids.is_empty());
+        unplaced.sort();
+        unplaced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
+    }
+
+    fn sample_project() -> ProjectDocument {
+        let mut ann = Employee {
+            user_id: "u1".to_string(),
+            first_name: "Ann".to_string(),
+            last_name: "Lee".to_string(),
+            ..Employee::default()
+        };
+        ann.cycle_mut("2024").nine_box = Some("2B".to_string());
+        let bob = Employee {
+            user_id: "u2".to_string(),
+            first_name: "Bob".to_string(),
+            last_name: "Ray".to_string(),
+            ..Employee::default()
+        };
+        let mut project = ProjectDocument {
+            employees: vec![ann, bob],
+            ..ProjectDocument::default()
+        };
+        project.grid_state.assignments.insert(position("2B"), vec!["u1".to_string(), "ghost".to_string()]);
+        project.grid_state.assignments.insert(position("1A"), vec!["u1".to_string()]);
+        project.grid_state.assignments.insert(position("5E"), vec!["u2".to_string()]);
+        project
+    }
+
+    #[test]
+    fn test_opening_enforces_placement_invariants() {
+        let (workspace, unplaced) = Workspace::from_project(sample_project());
+        assert_eq!(unplaced, vec!["ghost", "u1", "u2"], "Unknown, duplicate and off-grid placements are dropped");
+        assert_eq!(workspace.grid_state().assignments.len(), 1);
+        assert_eq!(workspace.grid_state().position_of("u1"), Some(position("1A")), "The first box in grid order wins");
+        assert_eq!(workspace.grid_state().position_of("u2"), None);
+    }
+
+    #[test]
+    fn test_assign_move_unassign_with_undo() {
+        let (mut workspace, _) = Workspace::from_project(sample_project());
+        assert_eq!(workspace.assign("u2", position("3C")), Ok(true));
+        assert_eq!(workspace.assign("u2", position("1B")), Ok(true));
+        assert_eq!(workspace.assign("u2", position("1B")), Ok(false), "Assigning to the same box changes nothing");
+        assert_eq!(workspace.employee("u2").and_then(|e| e.nine_box("2024")), Some("1B"));
+        assert_eq!(workspace.assign("nobody", position("1B")), Err(WorkspaceError::UnknownEmployee("nobody".to_string())));
+        assert_eq!(workspace.assign("u2", position("4A")), Err(WorkspaceError::BoxNotOnGrid(position("4A"))));
+
+        assert_eq!(workspace.unassign("u2"), Ok(true));
+        assert_eq!(workspace.unassign("u2"), Ok(false));
+        assert_eq!(workspace.grid_state().position_of("u2"), None);
+
+        let undone = workspace.undo().map(|edit| edit.description);
+        assert_eq!(undone.as_deref(), Some("Remove Bob Ray from the grid"));
+        assert_eq!(workspace.grid_state().position_of("u2"), Some(position("1B")));
+        workspace.undo();
+        assert_eq!(workspace.grid_state().position_of("u2"), Some(position("3C")));
+        assert_eq!(workspace.grid_state().assignments.values().map(Vec::len).sum::<usize>(), 2, "Each employee is in one box");
+    }
+
+    #[test]
+    fn test_listeners_hear_every_change() {
+        use std::sync::{Arc, Mutex};
+        let (mut workspace, _) = Workspace::from_project(sample_project());
+        let heard: Arc<Mutex<Vec<WorkspaceEvent>>> = Arc::default();
+        let log = Arc::clone(&heard);
+        workspace.subscribe(move |event| log.lock().unwrap().push(event.clone()));
+
+        workspace.assign("u2", position("3C")).unwrap();
+        workspace.assign("u2", position("3C")).unwrap();
+        workspace.undo();
+        workspace.open_project(sample_project());
+        workspace.set_note("u1", Some("Promote".to_string())).unwrap();
+
+        let heard = heard.lock().unwrap();
+        let moved = |from: Option<&str>, to: Option<&str>| WorkspaceEvent::EmployeeMoved {
+            employee_id: "u2".to_string(),
+            from: from.map(position),
+            to: to.map(position),
+        };
+        let nine_box = WorkspaceEvent::NineBoxChanged { employee_id: "u2".to_string(), cycle: "2024".to_string() };
+        assert_eq!(
+            *heard,
+            vec![
+                moved(None, Some("3C")),
+                nine_box.clone(),
+                nine_box,
+                moved(Some("3C"), None),
+                WorkspaceEvent::EditUndone { description: "Move Bob Ray to 3C".to_string() },
+                WorkspaceEvent::ProjectOpened,
+                WorkspaceEvent::NoteChanged { employee_id: "u1".to_string() },
+            ],
+            "A no-op move is silent and listeners survive opening a project"
+        );
+    }
+
+    #[test]
+    fn test_edits_notes_and_import() {
+        let (mut workspace, _) = Workspace::from_project(sample_project());
+        assert_eq!(workspace.edit_employee("u1", "Rename", |e| {
+            e.first_name = "Anna".to_string();
+            e.user_id = "changed".to_string();
+        }), Ok(true));
+        assert!(workspace.employee("u1").is_some(), "IDs cannot be edited");
+        assert_eq!(workspace.set_note("u1", Some("Ready for promotion".to_string())), Ok(true));
+        assert_eq!(workspace.notes().get("u1").map(String::as_str), Some("Ready for promotion"));
+        workspace.undo();
+        workspace.undo();
+        assert_eq!(workspace.employee("u1").map(|e| e.first_name.as_str()), Some("Ann"));
+        assert!(workspace.notes().is_empty());
+
+        let mut carl = Employee { user_id: "u3".to_string(), skills: vec!["Rust".to_string()], ..Employee::default() };
+        carl.cycle_mut("2025").nine_box = Some("1A".to_string());
+        let summary = workspace.import_employees(vec![carl]);
+        assert_eq!(summary.new_skills, 1);
+        assert_eq!(summary.seed_report.map(|report| report.placed), Some(1));
+        assert_eq!(workspace.grid_state().position_of("u3"), Some(position("1A")));
+        assert_eq!(workspace.placement_cycle().as_deref(), Some("2025"));
+        assert!(workspace.history().next_undo().is_none(), "Import clears the history");
+    }
+
+    #[test]
+    fn test_notes_column_reconciles_with_notes() {
+        let (mut workspace, _) = Workspace::from_project(sample_project());
+        workspace.set_note("u1", Some("From the notes editor".to_string())).unwrap();
+        let mut employees = workspace.employees().to_vec();
+        employees[0].notes = Some("Old CSV note".to_string());
+        employees[1].notes = Some("Bob's CSV note".to_string());
+
+        let summary = workspace.import_employees(employees);
+        assert_eq!(summary.notes_imported, 1, "Only employees without a note take the Notes column");
+        assert_eq!(workspace.notes().get("u1").map(String::as_str), Some("From the notes editor"));
+        assert_eq!(workspace.notes().get("u2").map(String::as_str), Some("Bob's CSV note"));
+
+        let exported = workspace.employees_with_notes();
+        assert_eq!(exported[0].notes.as_deref(), Some("From the notes editor"));
+        assert_eq!(exported[1].notes.as_deref(), Some("Bob's CSV note"));
+    }
+
+    #[test]
+    fn test_catalog_requirement_and_succession_edits_are_undoable() {
+        use std::sync::{Arc, Mutex};
+        let mut project = sample_project();
+        project.employees[0].add_skill("Rust");
+        project.employees[1].add_skill("Rust");
+        let (mut workspace, _) = Workspace::from_project(project);
+        let heard: Arc<Mutex<Vec<WorkspaceEvent>>> = Arc::default();
+        let log = Arc::clone(&heard);
+        workspace.subscribe(move |event| log.lock().unwrap().push(event.clone()));
+
+        assert_eq!(workspace.remove_catalog_skill("Coaching"), 0);
+        assert!(!workspace.add_catalog_skill("rust"), "Already in the catalog");
+        assert!(!workspace.set_role_requirement("Team Lead", "Rust", None));
+        assert!(!workspace.edit_succession("Nothing", |_| false));
+        assert!(heard.lock().unwrap().is_empty(), "Edits that change nothing are silent");
+        assert!(workspace.history().next_undo().is_none());
+
+        assert!(workspace.set_role_requirement("Team Lead", "Rust", Some(3)));
+        assert!(workspace.edit_succession("Add succession role Team Lead", |plan| plan.add_role("Team Lead", None)));
+        assert_eq!(workspace.remove_catalog_skill("Rust"), 2);
+        assert!(workspace.skills().is_empty());
+        assert!(workspace.employees().iter().all(|employee| !employee.has_skill("Rust")));
+
+        let undone = workspace.undo().map(|edit| edit.description);
+        assert_eq!(undone.as_deref(), Some("Remove skill Rust"), "The catalog and every employee are one step");
+        assert_eq!(workspace.skills().len(), 1);
+        assert!(workspace.employees().iter().all(|employee| employee.has_skill("Rust")));
+        workspace.undo();
+        assert!(workspace.succession().role("Team Lead").is_none());
+        workspace.undo();
+        assert!(workspace.role_requirements().is_empty());
+
+        workspace.redo();
+        assert_eq!(workspace.role_requirements().len(), 1);
+        assert!(heard.lock().unwrap().contains(&WorkspaceEvent::SuccessionPlanChanged));
+        assert!(heard.lock().unwrap().contains(&WorkspaceEvent::SkillCatalogChanged));
+    }
+
+    #[test]
+    fn test_succession_edits_survive_csv_export_and_reimport() {
+        use crate::csv_processing::{export_employees_with_profile, import_employees_with_profile};
+        use crate::mapping::MappingProfile;
+        use crate::succession::Readiness;
+        let csv_data = "User ID,First Name,Last Name,Current Position,Criticality of Role,Potential Successor,Successor\u{2019}s Readiness,Successor Development\n\
+                        m1,Max,Boss,Head of Sales,High,Ann Lee;u3,Ready Now;1-2 years,Needs P&L exposure\n\
+                        u2,Ann,Lee,Sales Lead,,,,\n\
+                        u3,Bob,Ray,Rep,,,,\n";
+        let import = |workspace: &mut Workspace, csv: &[u8]| {
+            let employees = import_employees_with_profile(csv, &MappingProfile::standard()).expect("Import failed");
+            workspace.import_employees(employees);
+        };
+        let mut workspace = Workspace::default();
+        import(&mut workspace, csv_data.as_bytes());
+
+        workspace.edit_succession("Edit", |plan| {
+            assert!(plan.rank_successor("Head of Sales", "u3", 0));
+            assert!(plan.remove_successor("Head of Sales", "u2"));
+            assert!(plan.add_successor("Head of Sales", "u2", None));
+            let role = plan.role_mut("Head of Sales").expect("Role should be imported");
+            role.successors[0].readiness = Some(Readiness::ReadyNow);
+            role.successors[0].development = "Shadow the role".to_string();
+            plan.add_role("Sales Lead", None) && plan.add_successor("Sales Lead", "u3", None)
+        });
+        let edited = workspace.succession().clone();
+
+        let mut exported = Vec::new();
+        export_employees_with_profile(&workspace.employees_for_export(), &MappingProfile::standard(), &mut exported)
+            .expect("Export failed");
+        let header = String::from_utf8_lossy(&exported).lines().next().unwrap_or_default().to_string();
+        assert!(header.contains(",Criticality of Role,Potential Successor,Successor\u{2019}s Readiness,Successor Development"), "{}", header);
+
+        let mut reimported = Workspace::default();
+        import(&mut reimported, &exported);
+        let mut expected = edited;
+        // A role added on the Succession screen is written on the row holding its position
+        expected.role_mut("Sales Lead").expect("Role was added").incumbent_id = Some("u2".to_string());
+        assert_eq!(*reimported.succession(), expected);
+    }
+}
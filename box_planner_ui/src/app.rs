@@ -1,28 +1,201 @@
 use crate::messages::Message;
 use crate::views::view_app;
-// Corrected to import the function that expects a Reader
-use box_planner_core::csv_processing::import_employees_from_csv; 
-use box_planner_core::models::{AppSettings, Employee, GridState};
-use box_planner_core::persistence::{load_app_settings, save_app_settings};
-use iced::{Command, Element, Theme}; // Removed Executor and Subscription
+use box_planner_core::csv_processing::{
+    export_employees_with_profile, export_skills_matrix, import_employees_with_report, read_csv_preview,
+    CsvPreview, ImportOutcome,
+};
+use box_planner_core::grid::{GridPosition, GridSeedReport, GridTemplate};
+use box_planner_core::labels::{normalize_nine_boxes, relabel_nine_boxes, LabelScheme};
+use box_planner_core::mapping::MappingProfile;
+use box_planner_core::org::employees_under;
+use box_planner_core::retention::{risk_matrix_template, set_risk_position};
+use box_planner_core::succession::{SuccessionImport, SuccessionPlan};
+use box_planner_core::models::{
+    AppSettings, Employee, GridState, ProjectDocument, ProjectSettings, PROJECT_FILE_EXTENSION,
+};
+use box_planner_core::events::WorkspaceEvent;
+use box_planner_core::notes::{NoteCategory, NoteEntry, NoteHistory};
+use box_planner_core::workspace::{Workspace, WorkspaceError};
+use box_planner_core::persistence::{
+    append_employee_note, load_all_employee_notes, load_app_settings, load_grid_state, load_grid_template,
+    load_note_history, load_project, load_role_requirements, load_skills_catalog, load_succession_plan,
+    migrate_note_files, save_app_settings, save_grid_state, save_note_history, save_project,
+    save_role_requirements, save_skills_catalog, save_succession_plan,
+};
+use iced::futures::channel::mpsc::{self, UnboundedReceiver};
+use iced::futures::stream::{self, BoxStream, StreamExt};
+use iced::keyboard::{self, Key, Modifiers};
+use iced::widget::text_editor;
+use iced::{event, window, Command, Element, Event, Subscription, Theme};
+use std::collections::HashMap;
 use std::fs::File; // Added File
 use std::io::BufReader; // Added BufReader
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 const SETTINGS_FILE_PATH: &str = "box_planner_ui/app_settings.json";
 const SAMPLE_EMPLOYEES_CSV_PATH: &str = "box_planner_ui/sample_employees.csv";
+const GRID_STATE_FILE_PATH: &str = "box_planner_ui/grid_state.json";
+const NOTES_DIR_PATH: &str = "box_planner_ui/notes";
+const SKILLS_FILE_PATH: &str = "box_planner_ui/skills.json";
+const ROLES_FILE_PATH: &str = "box_planner_ui/roles.json";
+const SUCCESSION_FILE_PATH: &str = "box_planner_ui/succession.json";
+const MAPPING_PREVIEW_ROWS: usize = 5;
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2); // Quiet time after the last change before the project is saved
+const NOTE_SAVE_DELAY: Duration = Duration::from_secs(1); // Quiet time after the last keystroke before a note is saved
+const DEVELOPMENT_SAVE_DELAY: Duration = Duration::from_secs(1); // Likewise for a successor's development notes
+
+/// Where the app keeps its settings, the employee file it loads at startup, the notes store
+/// and the session files it restores at startup and autosaves to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppPaths {
+    pub settings: PathBuf,
+    pub sample_employees: PathBuf,
+    pub grid_state: PathBuf,
+    pub notes_dir: PathBuf, // The notes store: one file of note versions per employee
+    pub skills: PathBuf,
+    pub roles: PathBuf,
+    pub succession: PathBuf,
+}
+
+impl Default for AppPaths {
+    fn default() -> Self {
+        AppPaths {
+            settings: PathBuf::from(SETTINGS_FILE_PATH),
+            sample_employees: PathBuf::from(SAMPLE_EMPLOYEES_CSV_PATH),
+            grid_state: PathBuf::from(GRID_STATE_FILE_PATH),
+            notes_dir: PathBuf::from(NOTES_DIR_PATH),
+            skills: PathBuf::from(SKILLS_FILE_PATH),
+            roles: PathBuf::from(ROLES_FILE_PATH),
+            succession: PathBuf::from(SUCCESSION_FILE_PATH),
+        }
+    }
+}
+
+/// The top-level screen shown below the toolbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Planner,
+    RiskMatrix,
+    OrgChart,
+    Skills,
+    Succession,
+    MappingEditor,
+}
+
+/// Where a dragged employee card can be dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropTarget {
+    GridBox(GridPosition), // Places the employee in that 9-box box
+    RiskBox(GridPosition), // Sets impact and risk of loss from the retention matrix box
+    Unplaced, // The employee list; takes the employee off the 9-box
+}
+
+/// An employee card being dragged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drag {
+    pub employee_id: String,
+    pub hovered: Option<DropTarget>, // Highlighted target under the pointer
+}
+
+/// How far the note being edited has got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteStatus {
+    Editing, // Typed but not saved yet
+    Saved,
+    Failed(String),
+}
+
+/// The details panel's editor for one employee's note. Edits are saved once typing pauses.
+pub struct NoteEditor {
+    pub employee_id: String,
+    pub content: text_editor::Content,
+    pub category: Option<NoteCategory>, // Recorded with the next saved version
+    pub history: NoteHistory, // Versions saved to the notes store, oldest first
+    pub save_due: Option<Instant>, // When the draft is saved, if it has edits not saved yet
+    pub status: Option<NoteStatus>, // None until the note is edited
+}
+
+impl NoteEditor {
+    fn open(employee_id: &str, note: &str, history: NoteHistory) -> Self {
+        NoteEditor {
+            employee_id: employee_id.to_string(),
+            content: text_editor::Content::with_text(note),
+            category: history.current().and_then(|entry| entry.category),
+            history,
+            save_due: None,
+            status: None,
+        }
+    }
+
+    /// The note as typed. The editor always ends its text with a line break; that one is dropped.
+    pub fn text(&self) -> String {
+        let mut text = self.content.text();
+        text.pop();
+        text
+    }
+}
+
+/// A successor's development notes as typed. They go into the succession plan as one undoable
+/// edit when Enter is pressed, typing pauses or another successor's notes are edited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DevelopmentDraft {
+    pub position: String,
+    pub employee_id: String,
+    pub text: String,
+    pub save_due: Instant,
+}
+
+/// A CSV import that has been read but is waiting for the user to review its report.
+#[derive(Debug, Clone)]
+pub struct PendingImport {
+    pub source: PathBuf,
+    pub outcome: ImportOutcome,
+}
+
+/// Working copy of a mapping profile being edited, plus an optional CSV preview.
+#[derive(Debug, Clone, Default)]
+pub struct MappingEditor {
+    pub profile: MappingProfile,
+    pub preview: Option<CsvPreview>,
+    pub preview_path: Option<PathBuf>,
+}
 
 pub struct App {
-    pub employees: Vec<Employee>,
-    pub grid_state: GridState,
+    pub workspace: Workspace, // Employees, grid, notes, skills and the rest of the open project
     pub selected_employee_id: Option<String>,
     pub view_scale: f32,
     pub app_settings: AppSettings, // Added app_settings field
+    pub project_path: Option<PathBuf>, // Set once the session is opened from or saved to a project file
+    pub status_message: Option<String>,
+    pub screen: Screen,
+    pub mapping_editor: MappingEditor,
+    pub pending_import: Option<PendingImport>,
+    pub seed_report: Option<GridSeedReport>, // Employees left off the grid by the last seeding
+    pub skill_input: String, // Skill name being typed on the details panel or the Skills screen
+    pub selected_role: Option<String>, // Position whose requirements are edited on the Skills screen
+    pub succession_role_input: String, // Position being typed as a new critical role
+    pub succession_issues: Vec<String>, // Succession values the last import could not use
+    pub reports_to_filter: Option<String>, // Manager ID; the employee list then shows only their reports
+    pub note_editor: Option<NoteEditor>, // The selected employee's note, once they have been selected
+    pub development_draft: Option<DevelopmentDraft>, // Development notes typed but not in the succession plan yet
+    pub drag: Option<Drag>,
+    pub last_drop: Option<DropTarget>, // Highlighted until the next drag starts
+    pub unsaved_changes: bool, // Something changed that is not on disk yet: not in the project, nor, without one, in the session files
+    pub paths: AppPaths,
+    autosave_due: Option<Instant>, // When autosave next saves the project, if a save is pending
+    workspace_events: Arc<Mutex<Option<UnboundedReceiver<WorkspaceEvent>>>>, // Taken by the subscription that turns events into messages
 }
 
 impl App {
     pub fn new() -> Self {
-        let settings_path = Path::new(SETTINGS_FILE_PATH);
+        Self::with_paths(AppPaths::default())
+    }
+
+    /// Starts the app on the settings and session files at `paths`.
+    pub fn with_paths(paths: AppPaths) -> Self {
+        let settings_path = paths.settings.as_path();
         let mut app_settings = match load_app_settings(settings_path) {
             Ok(settings) => {
                 println!("Successfully loaded settings from {:?}", settings_path);
@@ -51,85 +224,854 @@ impl App {
             default_scale
         });
 
-        let employees_load_result = File::open(SAMPLE_EMPLOYEES_CSV_PATH)
-            .map_err(|e| format!("Failed to open CSV file '{}': {}", SAMPLE_EMPLOYEES_CSV_PATH, e))
+        let mut status_message = None;
+        let mut startup_problems: Vec<String> = Vec::new(); // Files that could not be read or migrated
+        let mut pending_import = None;
+        let employees_load_result = File::open(&paths.sample_employees)
+            .map_err(|e| format!("Failed to open CSV file '{}': {}", paths.sample_employees.display(), e))
             .and_then(|file| {
                 let reader = BufReader::new(file);
-                // Assuming import_employees_from_csv now correctly takes a Read implementor
-                // and returns Result<Vec<Employee>, E> where E can be converted to our error string.
-                import_employees_from_csv(reader)
-                    .map_err(|e| format!("Failed to parse CSV from '{}': {}", SAMPLE_EMPLOYEES_CSV_PATH, e.to_string()))
+                import_employees_with_report(reader, &app_settings.active_mapping_profile())
+                    .map_err(|e| format!("Failed to parse CSV from '{}': {}", paths.sample_employees.display(), e))
             });
 
-        let employees = match employees_load_result {
-            Ok(loaded_employees) => {
-                if loaded_employees.is_empty() {
+        let mut employees = match employees_load_result {
+            Ok(outcome) if !outcome.report.is_clean() => {
+                // Nothing is loaded until the user has seen what would be dropped.
+                pending_import = Some(PendingImport {
+                    source: paths.sample_employees.clone(),
+                    outcome,
+                });
+                Vec::new()
+            }
+            Ok(outcome) => {
+                if outcome.employees.is_empty() {
                     println!("No employees loaded from CSV, using dummy data.");
                     Self::load_dummy_employees() 
                 } else {
-                    println!("Successfully loaded {} employees from CSV.", loaded_employees.len());
-                    loaded_employees
+                    println!("Successfully loaded {} employees from CSV.", outcome.employees.len());
+                    outcome.employees
                 }
             }
             Err(e) => {
                 eprintln!("Error loading employees from CSV: {}. Using dummy data instead.", e);
+                status_message = Some(format!("{}. Showing sample data instead.", e));
                 Self::load_dummy_employees()
             }
         };
-        let mut grid_state = GridState::default();
+        // Restore the last saved grid; an empty grid is used if nothing was saved yet.
+        let grid_state = load_grid_state(&paths.grid_state).unwrap_or_else(|e| {
+            startup_problems.push(format!("{}. Starting with an empty grid.", e));
+            GridState::default()
+        });
 
-        // Sample assignments - ensure employees are loaded first
-        if !employees.is_empty() {
-            grid_state.assignments.insert("1A".to_string(), vec![employees[0].user_id.clone()]);
-            if employees.len() > 1 {
-                 grid_state.assignments.insert("2B".to_string(), vec![employees[1].user_id.clone()]);
-            }
-            if employees.len() > 2 {
-                grid_state.assignments.insert("1A".to_string(), vec![employees[0].user_id.clone(), employees[2].user_id.clone()]); // Add a second employee to 1A
-            }
-            if employees.len() > 3 {
-                grid_state.assignments.insert("3C".to_string(), vec![employees[3].user_id.clone()]);
-            }
+        let notes_dir = paths.notes_dir.as_path();
+        match migrate_note_files(notes_dir) {
+            Ok(migration) => startup_problems
+                .extend(migration.problems.into_iter().map(|problem| format!("Note file not renamed: {}", problem))),
+            Err(e) => startup_problems.push(e),
         }
+        let notes = load_all_employee_notes(notes_dir).unwrap_or_else(|e| {
+            startup_problems.push(format!("{}. Starting without notes.", e));
+            HashMap::new()
+        });
+
+        let skills = load_skills_catalog(&paths.skills).unwrap_or_else(|e| {
+            startup_problems.push(format!("{}. Starting with an empty catalog.", e));
+            Vec::new()
+        });
+
+        let role_requirements = load_role_requirements(&paths.roles).unwrap_or_else(|e| {
+            startup_problems.push(format!("{}. Starting without requirements.", e));
+            Vec::new()
+        });
+
+        let succession = load_succession_plan(&paths.succession).unwrap_or_else(|e| {
+            startup_problems.push(format!("{}. Starting with an empty plan.", e));
+            SuccessionPlan::default()
+        });
 
-        Self {
+        let app_settings_profile = app_settings.active_mapping_profile();
+        let project_settings = ProjectSettings {
+            department_colors: app_settings.department_colors.clone(),
+            grid_template: GridTemplate::default(),
+            label_scheme: LabelScheme::default(),
+            placement_cycle: None,
+        };
+        normalize_nine_boxes(&mut employees, &project_settings.grid_template);
+        let (mut workspace, unplaced) = Workspace::from_project(ProjectDocument {
             employees,
             grid_state,
+            notes,
+            skills,
+            role_requirements,
+            succession,
+            settings: project_settings,
+            ..ProjectDocument::default()
+        });
+        if !unplaced.is_empty() {
+            startup_problems.push(format!(
+                "Unplaced {} saved placement(s) that do not fit the default grid or employee list.",
+                unplaced.len()
+            ));
+        }
+        // Workspace listeners run inside `update`; the channel hands their events back as messages
+        let (event_sender, mut event_receiver) = mpsc::unbounded();
+        workspace.subscribe(move |event| {
+            let _ = event_sender.unbounded_send(event.clone());
+        });
+
+        let mut app = Self {
+            workspace,
             selected_employee_id: None,
             view_scale: initial_view_scale, // Use loaded or default scale
             app_settings, // Store loaded/default settings
+            project_path: None,
+            status_message,
+            screen: Screen::Planner,
+            mapping_editor: MappingEditor {
+                profile: app_settings_profile,
+                ..MappingEditor::default()
+            },
+            pending_import,
+            seed_report: None,
+            skill_input: String::new(),
+            selected_role: None,
+            succession_role_input: String::new(),
+            succession_issues: Vec::new(),
+            reports_to_filter: None,
+            note_editor: None,
+            development_draft: None,
+            drag: None,
+            last_drop: None,
+            unsaved_changes: false,
+            paths,
+            autosave_due: None,
+            workspace_events: Arc::new(Mutex::new(None)),
+        };
+        // Without a saved grid, start from the latest 9-box column in the data
+        if app.workspace.grid_state().assignments.is_empty() {
+            if let Some(cycle) = app.placement_cycle() {
+                app.seed_grid_from(&cycle);
+            }
+        }
+        // What was loaded and seeded at startup is not an edit to save
+        while let Ok(Some(_)) = event_receiver.try_next() {}
+        app.workspace_events = Arc::new(Mutex::new(Some(event_receiver)));
+        for problem in startup_problems {
+            app.report_failure(problem);
         }
+        app
+    }
+
+    /// The review cycle the grid mirrors; see `Workspace::placement_cycle`.
+    pub fn placement_cycle(&self) -> Option<String> {
+        self.workspace.placement_cycle()
+    }
+
+    /// Rebuilds the grid from `cycle`'s 9-box column and makes `cycle` the one grid moves update.
+    pub fn seed_grid_from(&mut self, cycle: &str) {
+        self.seed_report = Some(self.workspace.seed_grid_from(cycle));
+    }
+
+    /// Bundles the current session into a project document, with every version of each note
+    /// from the notes store so the history travels with the project.
+    pub fn to_project_document(&self) -> ProjectDocument {
+        let mut document = self.workspace.to_project_document();
+        document.note_histories = self
+            .workspace
+            .employees()
+            .iter()
+            .filter_map(|employee| {
+                let history = load_note_history(&self.paths.notes_dir, &employee.user_id).ok()?;
+                (!history.entries.is_empty()).then(|| (employee.user_id.clone(), history))
+            })
+            .collect();
+        document
+    }
+
+    /// Writes the current session to `path` and remembers it for subsequent saves.
+    pub fn save_project_to(&mut self, path: &Path) -> Result<(), String> {
+        // The notes store must hold the latest version of every note before its histories are bundled
+        self.save_note_draft();
+        self.save_development_draft();
+        self.persist_notes();
+        save_project(path, &self.to_project_document())?;
+        self.project_path = Some(path.to_path_buf());
+        self.unsaved_changes = false;
+        self.autosave_due = None;
+        Ok(())
+    }
+
+    /// Replaces the current session with the project stored at `path`.
+    pub fn open_project_from(&mut self, path: &Path) -> Result<(), String> {
+        let mut document = load_project(path)?;
+        // The project's note histories replace the stored ones; notes from projects saved before
+        // histories were kept are recorded as new versions once the project is open
+        let note_histories = std::mem::take(&mut document.note_histories);
+        let mut unstored_notes = Vec::new();
+        for (employee_id, history) in &note_histories {
+            if let Err(e) = save_note_history(&self.paths.notes_dir, employee_id, history) {
+                unstored_notes.push(e);
+            }
+        }
+        let notes_without_history: Vec<String> =
+            document.notes.keys().filter(|employee_id| !note_histories.contains_key(*employee_id)).cloned().collect();
+        let unplaced = self.workspace.open_project(document);
+        self.status_message = Some(format!("Opened {}", path.display()));
+        self.note_editor = None;
+        for employee_id in notes_without_history {
+            self.persist_note(&employee_id);
+        }
+        if let Some(first) = unstored_notes.first() {
+            self.report_failure(format!("{} note histories could not be stored: {}", unstored_notes.len(), first));
+        }
+        if !unplaced.is_empty() {
+            self.report_failure(format!(
+                "Unplaced {} placement(s) that do not fit the project's grid or employee list.",
+                unplaced.len()
+            ));
+        }
+        self.succession_issues.clear();
+        self.selected_employee_id = None;
+        self.seed_report = None;
+        self.reports_to_filter = None;
+        self.project_path = Some(path.to_path_buf());
+        self.unsaved_changes = false;
+        self.autosave_due = None;
+        Ok(())
+    }
+
+    /// Switches the grid to `template`; anyone placed in a box the new grid does not have is unplaced.
+    pub fn apply_grid_template(&mut self, template: GridTemplate) {
+        let name = template.name.clone();
+        let unplaced = self.workspace.set_grid_template(template);
+        self.status_message = Some(if unplaced.is_empty() {
+            format!("Grid template: {}", name)
+        } else {
+            format!("Grid template: {}. {} employee(s) no longer fit and were unplaced.", name, unplaced.len())
+        });
+    }
+
+    // The session file helpers below report a failed write and return whether the write succeeded.
+    fn persist_grid_state(&mut self) -> bool {
+        let result = save_grid_state(&self.paths.grid_state, self.workspace.grid_state());
+        if let Err(e) = &result {
+            self.report_failure(format!("{}. The grid is kept in memory.", e));
+        }
+        result.is_ok()
+    }
+
+    fn persist_skills_catalog(&mut self) -> bool {
+        let result = save_skills_catalog(&self.paths.skills, self.workspace.skills());
+        if let Err(e) = &result {
+            self.report_failure(e.clone());
+        }
+        result.is_ok()
+    }
+
+    fn persist_role_requirements(&mut self) -> bool {
+        let result = save_role_requirements(&self.paths.roles, self.workspace.role_requirements());
+        if let Err(e) = &result {
+            self.report_failure(e.clone());
+        }
+        result.is_ok()
+    }
+
+    fn persist_succession_plan(&mut self) -> bool {
+        let result = save_succession_plan(&self.paths.succession, self.workspace.succession());
+        if let Err(e) = &result {
+            self.report_failure(e.clone());
+        }
+        result.is_ok()
+    }
+
+    // Saves the app settings; `saved_what` names what changed, for the status line if it fails.
+    fn persist_app_settings(&mut self, saved_what: &str) {
+        if let Err(e) = save_app_settings(&self.paths.settings, &self.app_settings) {
+            self.report_failure(format!("Failed to save {}: {}", saved_what, e));
+        }
+    }
+
+    // Records the employee's note as a new version in their note file unless it is unchanged; a
+    // removed note is recorded as an empty version. The note editor shows the outcome and the
+    // updated history if it has the employee's note open. Returns whether the note was written.
+    fn persist_note(&mut self, employee_id: &str) -> bool {
+        let notes_dir = self.paths.notes_dir.clone();
+        let notes_dir = notes_dir.as_path();
+        let text = self.workspace.notes().get(employee_id).cloned().unwrap_or_default();
+        let open_editor = self.note_editor.as_ref().filter(|editor| editor.employee_id == employee_id);
+        // Notes not open in the editor keep the category of their latest version
+        let category = match open_editor {
+            Some(editor) => editor.category,
+            None => load_note_history(notes_dir, employee_id)
+                .ok()
+                .and_then(|history| history.current().and_then(|entry| entry.category)),
+        };
+        let entry = NoteEntry::new(&text, &self.note_author(), category);
+        let result = append_employee_note(notes_dir, employee_id, entry);
+        if let Err(e) = &result {
+            self.report_failure(format!("Note not saved: {}", e));
+        }
+        let saved = result.is_ok();
+        if let Some(editor) = self.note_editor.as_mut().filter(|editor| editor.employee_id == employee_id) {
+            match result {
+                Ok(history) => {
+                    editor.history = history;
+                    if editor.save_due.is_none() {
+                        editor.status = Some(NoteStatus::Saved);
+                    }
+                }
+                Err(e) if editor.save_due.is_none() => editor.status = Some(NoteStatus::Failed(e)),
+                Err(_) => {}
+            }
+        }
+        saved
+    }
+
+    /// The name recorded on note versions: the one set in the settings, else the login name.
+    pub fn note_author(&self) -> String {
+        if !self.app_settings.note_author.trim().is_empty() {
+            return self.app_settings.note_author.trim().to_string();
+        }
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "Unknown".to_string())
+    }
+
+    // Writes every session file, e.g. after changes made while autosave was off.
+    fn persist_session_files(&mut self) {
+        self.persist_grid_state();
+        self.persist_skills_catalog();
+        self.persist_role_requirements();
+        self.persist_succession_plan();
+        self.persist_notes();
+    }
+
+    // Records the current version of every note, in the workspace or the notes store, that has changed.
+    fn persist_notes(&mut self) {
+        let saved_notes = load_all_employee_notes(&self.paths.notes_dir).unwrap_or_default();
+        let mut employee_ids: Vec<String> = saved_notes.into_keys().chain(self.workspace.notes().keys().cloned()).collect();
+        employee_ids.sort();
+        employee_ids.dedup();
+        for employee_id in employee_ids {
+            self.persist_note(&employee_id);
+        }
+    }
+
+    /// Opens the employee's note in the details panel, first saving any draft of the note open before.
+    pub fn open_note_editor(&mut self, employee_id: &str) {
+        if self.note_editor.as_ref().is_some_and(|editor| editor.employee_id == employee_id) {
+            return;
+        }
+        self.save_note_draft();
+        let note = self.workspace.notes().get(employee_id).cloned().unwrap_or_default();
+        let (history, status) = match load_note_history(&self.paths.notes_dir, employee_id) {
+            Ok(history) => (history, None),
+            Err(e) => {
+                self.report_failure(format!("Note history not loaded: {}", e));
+                (NoteHistory::default(), Some(NoteStatus::Failed(e)))
+            }
+        };
+        let mut editor = NoteEditor::open(employee_id, &note, history);
+        // An ID that cannot name a note file shows why its note will not be saved
+        editor.status = status;
+        self.note_editor = Some(editor);
+    }
+
+    // Applies a keystroke, click or scroll to the note editor; edits restart the save delay.
+    fn edit_note(&mut self, action: text_editor::Action) {
+        let Some(editor) = &mut self.note_editor else { return };
+        let is_edit = action.is_edit();
+        editor.content.perform(action);
+        if is_edit {
+            editor.save_due = Some(Instant::now() + NOTE_SAVE_DELAY);
+            editor.status = Some(NoteStatus::Editing);
+        }
+    }
+
+    // Keeps typed development notes as a draft, first saving a draft for another successor.
+    fn edit_development(&mut self, position: String, employee_id: String, text: String) {
+        if self
+            .development_draft
+            .as_ref()
+            .is_some_and(|draft| draft.position != position || draft.employee_id != employee_id)
+        {
+            self.save_development_draft();
+        }
+        self.development_draft = Some(DevelopmentDraft {
+            position,
+            employee_id,
+            text,
+            save_due: Instant::now() + DEVELOPMENT_SAVE_DELAY,
+        });
+    }
+
+    /// Saves the development draft if typing has paused long enough by `now`.
+    pub fn save_development_draft_if_due(&mut self, now: Instant) {
+        if self.development_draft.as_ref().is_some_and(|draft| now >= draft.save_due) {
+            self.save_development_draft();
+        }
+    }
+
+    // Writes the development draft into the succession plan as one undoable step.
+    fn save_development_draft(&mut self) {
+        let Some(draft) = self.development_draft.take() else { return };
+        let description = format!("Edit successor development for {}", draft.position);
+        self.workspace.edit_succession(&description, |plan| {
+            match plan.role_mut(&draft.position).and_then(|role| role.successor_mut(&draft.employee_id)) {
+                Some(successor) => {
+                    successor.development = draft.text;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    /// Saves the note draft if typing has paused long enough by `now`.
+    pub fn save_note_draft_if_due(&mut self, now: Instant) {
+        if self.note_editor.as_ref().and_then(|editor| editor.save_due).is_some_and(|due| now >= due) {
+            self.save_note_draft();
+        }
+    }
+
+    // Stores the editor's draft as the employee's note (an empty draft removes it), as one undoable
+    // step, and writes it to the notes store whether or not autosave is on.
+    fn save_note_draft(&mut self) {
+        let Some(editor) = self.note_editor.as_mut().filter(|editor| editor.save_due.is_some()) else { return };
+        editor.save_due = None;
+        let employee_id = editor.employee_id.clone();
+        let text = editor.text();
+        let note = (!text.trim().is_empty()).then_some(text);
+        match self.workspace.set_note(&employee_id, note) {
+            Ok(_) => {
+                self.persist_note(&employee_id);
+            }
+            Err(e) => editor.status = Some(NoteStatus::Failed(e.to_string())),
+        }
+    }
+
+    // Shows a note changed elsewhere, e.g. by undo or an import, unless the editor has newer edits.
+    fn refresh_note_editor(&mut self, employee_id: &str) {
+        let note = self.workspace.notes().get(employee_id).cloned().unwrap_or_default();
+        if let Some(editor) = self.note_editor.as_mut().filter(|editor| editor.employee_id == employee_id) {
+            if editor.save_due.is_none() && editor.text() != note {
+                editor.content = text_editor::Content::with_text(&note);
+            }
+        }
+    }
+
+    // Sets the category recorded with the note's next version. A note that is not being typed in is
+    // recorded again straight away, so the category can be changed on its own.
+    fn set_note_category(&mut self, category: Option<NoteCategory>) {
+        let Some(editor) = self.note_editor.as_mut() else { return };
+        if editor.category == category {
+            return;
+        }
+        editor.category = category;
+        if editor.save_due.is_some() {
+            return;
+        }
+        let employee_id = editor.employee_id.clone();
+        self.persist_note(&employee_id);
+    }
+
+    /// Makes an earlier version of the open note the current one. The restore is saved at once,
+    /// as an edit that can be undone, and recorded as a new version.
+    pub fn restore_note_version(&mut self, index: usize) {
+        let Some(editor) = self.note_editor.as_mut() else { return };
+        let Some(entry) = editor.history.entries.get(index).cloned() else { return };
+        editor.content = text_editor::Content::with_text(&entry.text);
+        editor.category = entry.category;
+        editor.save_due = Some(Instant::now());
+        self.save_note_draft();
+        self.status_message = Some(format!("Restored the note version from {}", entry.recorded_at_label()));
+    }
+
+    /// Notes what an import did to the succession plan.
+    fn report_succession_import(&mut self, import: &SuccessionImport, successors_removed: usize) {
+        self.succession_issues = import
+            .unresolved
+            .iter()
+            .map(|unresolved| format!("{}: successor '{}' matches no employee", unresolved.incumbent_id, unresolved.value))
+            .chain(
+                import
+                    .unknown_readiness
+                    .iter()
+                    .map(|(incumbent_id, value)| format!("{}: unknown readiness '{}'", incumbent_id, value)),
+            )
+            .collect();
+        if successors_removed > 0 {
+            self.succession_issues
+                .push(format!("Removed {} successor(s) who are not in the imported employee list", successors_removed));
+        }
+    }
+
+    // Logs a failure and shows it in the status line.
+    fn report_failure(&mut self, message: String) {
+        eprintln!("{}", message);
+        self.status_message = Some(message);
+    }
+
+    // Logs a rejected workspace operation and shows it in the status line.
+    fn report_workspace_error(&mut self, error: WorkspaceError) {
+        self.report_failure(error.to_string());
+    }
+
+    /// Applies `edit` to the employee as one undoable step. Returns whether anything changed.
+    fn edit_employee(&mut self, employee_id: &str, description: &str, edit: impl FnOnce(&mut Employee)) -> bool {
+        match self.workspace.edit_employee(employee_id, description, edit) {
+            Ok(changed) => changed,
+            Err(e) => {
+                self.report_workspace_error(e);
+                false
+            }
+        }
+    }
+
+    /// Reverts the most recent grid move or employee edit.
+    pub fn undo(&mut self) {
+        match self.workspace.undo() {
+            Some(edit) => self.status_message = Some(format!("Undid: {}", edit.description)),
+            None => self.status_message = Some("Nothing to undo.".to_string()),
+        }
+    }
+
+    /// Re-applies the most recently undone grid move or employee edit.
+    pub fn redo(&mut self) {
+        match self.workspace.redo() {
+            Some(edit) => self.status_message = Some(format!("Redid: {}", edit.description)),
+            None => self.status_message = Some("Nothing to redo.".to_string()),
+        }
+    }
+
+    /// Logs a workspace change. With autosave on, saves whatever it touched that lives in its
+    /// own file right away and schedules a project save once changes settle. Without a project
+    /// file, a change autosave has written to its own file leaves nothing unsaved.
+    fn on_workspace_event(&mut self, event: WorkspaceEvent) {
+        if let WorkspaceEvent::NoteChanged { employee_id } = &event {
+            self.refresh_note_editor(employee_id);
+        }
+        let written = self.persist_session_file(&event);
+        // Opening a project is reported after `open_project_from` has marked it saved
+        if event != WorkspaceEvent::ProjectOpened && !(written && self.project_path.is_none()) {
+            self.unsaved_changes = true;
+        }
+        if self.app_settings.auto_save_enabled {
+            self.schedule_autosave();
+        }
+    }
+
+    // Writes what `event` changed to the session file that keeps it: notes always, like the
+    // editor's debounced saves, the rest only with autosave on. Returns whether everything the
+    // event changed is now in a session file.
+    fn persist_session_file(&mut self, event: &WorkspaceEvent) -> bool {
+        if let WorkspaceEvent::NoteChanged { employee_id } = event {
+            return self.persist_note(employee_id);
+        }
+        if !self.app_settings.auto_save_enabled {
+            return false;
+        }
+        match event {
+            WorkspaceEvent::EmployeeMoved { .. } | WorkspaceEvent::GridReseeded { .. } => self.persist_grid_state(),
+            WorkspaceEvent::SkillCatalogChanged => self.persist_skills_catalog(),
+            WorkspaceEvent::RoleRequirementsChanged => self.persist_role_requirements(),
+            WorkspaceEvent::SuccessionPlanChanged => self.persist_succession_plan(),
+            // Imports, project opens and template changes move people too, but also change
+            // what only a project file keeps
+            event if event.changes_grid() => {
+                self.persist_grid_state();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // Pushes the pending project save back so a burst of edits is saved once.
+    fn schedule_autosave(&mut self) {
+        if self.unsaved_changes && self.project_path.is_some() {
+            self.autosave_due = Some(Instant::now() + AUTOSAVE_DELAY);
+        }
+    }
+
+    /// Saves the project if autosave is due at `now`.
+    pub fn autosave(&mut self, now: Instant) {
+        if self.autosave_due.is_none_or(|due| now < due) {
+            return;
+        }
+        self.autosave_due = None;
+        let Some(path) = self.project_path.clone() else { return };
+        if let Err(e) = self.save_project_to(&path) {
+            self.report_failure(format!("Autosave failed: {}", e));
+        }
+    }
+
+    /// Turns autosave on or off and remembers the choice. Turning it on saves what is pending.
+    pub fn set_auto_save(&mut self, enabled: bool) {
+        self.app_settings.auto_save_enabled = enabled;
+        self.persist_app_settings("the autosave setting");
+        if enabled {
+            self.persist_session_files();
+            self.schedule_autosave();
+        } else {
+            self.autosave_due = None;
+        }
+    }
+
+    // Closes the window, first offering to save unsaved changes that autosave cannot take care of.
+    fn close_window(&mut self, id: window::Id) -> Command<Message> {
+        if let Some(path) = self.project_path.clone().filter(|_| self.unsaved_changes && self.app_settings.auto_save_enabled) {
+            self.save_project_with_status(path);
+        }
+        if self.unsaved_changes {
+            let description = match (&self.project_path, self.app_settings.auto_save_enabled) {
+                (None, true) => "Autosave has kept the grid, notes, skills and succession plan, but other changes need a project file. Save a project before closing?",
+                _ => "Save your changes before closing?",
+            };
+            let save = rfd::MessageDialog::new()
+                .set_title("Unsaved Changes")
+                .set_description(description)
+                .set_buttons(rfd::MessageButtons::YesNo)
+                .show();
+            if save {
+                let Some(path) = self.project_path.clone().or_else(Self::pick_project_save_path) else {
+                    return Command::none(); // Save dialog cancelled: keep the window open
+                };
+                self.save_project_with_status(path);
+                if self.unsaved_changes {
+                    return Command::none(); // Save failed; the status line says why
+                }
+            }
+        }
+        window::close(id)
+    }
+
+    /// Moves the employee to where their card was dropped and says what happened.
+    pub fn drop_employee(&mut self, employee_id: &str, target: DropTarget) {
+        self.drag = None;
+        let Some(name) = self.workspace.employee(employee_id).map(|e| format!("{} {}", e.first_name, e.last_name)) else {
+            return;
+        };
+        let settings = self.workspace.settings();
+        let (result, destination) = match target {
+            DropTarget::GridBox(position) => {
+                let label = settings.label_scheme.label(position, &settings.grid_template);
+                (self.workspace.assign(employee_id, position), label)
+            }
+            DropTarget::RiskBox(position) => {
+                let label = risk_matrix_template().box_label(position);
+                let mut on_matrix = false;
+                self.edit_employee(employee_id, "Set impact and risk", |employee| {
+                    on_matrix = set_risk_position(employee, position);
+                });
+                (Ok(on_matrix), label)
+            }
+            DropTarget::Unplaced => (self.workspace.unassign(employee_id), "the unplaced list".to_string()),
+        };
+        match result {
+            Ok(true) => {
+                self.status_message = Some(format!("Moved {} to {}", name, destination));
+                self.last_drop = Some(target);
+            }
+            Ok(false) => {}
+            Err(e) => self.report_workspace_error(e),
+        }
+    }
+
+    /// The hex color of the employee's department: the project's choice, else the app-wide one.
+    pub fn department_color(&self, employee: &Employee) -> Option<&str> {
+        let department = employee.department.as_deref()?.trim();
+        self.workspace
+            .settings()
+            .department_colors
+            .get(department)
+            .or_else(|| self.app_settings.department_colors.get(department))
+            .map(String::as_str)
+    }
+
+    /// Whether the employee has a note, either a note file or text in the Notes column.
+    pub fn has_note(&self, employee: &Employee) -> bool {
+        let note_file = self.workspace.notes().get(&employee.user_id).map(String::as_str);
+        [note_file, employee.notes.as_deref()].into_iter().flatten().any(|note| !note.trim().is_empty())
+    }
+
+    /// The employees shown in the list: everyone, or everyone under the reports-to filter's manager.
+    pub fn visible_employees(&self) -> Vec<&Employee> {
+        match &self.reports_to_filter {
+            Some(manager_id) => employees_under(self.workspace.employees(), manager_id),
+            None => self.workspace.employees().iter().collect(),
+        }
+    }
+
+    /// The employee's team: everyone sharing their manager, or just the employee if they have none.
+    pub fn team_of<'a>(&'a self, employee: &'a Employee) -> Vec<&'a Employee> {
+        match &employee.manager_id {
+            Some(manager_id) => self
+                .workspace
+                .employees()
+                .iter()
+                .filter(|colleague| colleague.manager_id.as_ref() == Some(manager_id))
+                .collect(),
+            None => vec![employee],
+        }
+    }
+
+    /// Positions that can be given requirements: those held by employees plus any already defined.
+    pub fn role_names(&self) -> Vec<String> {
+        let mut roles: Vec<String> = Vec::new();
+        let positions = self.workspace.employees().iter().map(|employee| &employee.current_position);
+        for position in positions.chain(self.workspace.role_requirements().iter().map(|role| &role.position)) {
+            let position = position.trim();
+            if !position.is_empty() && !roles.iter().any(|role| role.eq_ignore_ascii_case(position)) {
+                roles.push(position.to_string());
+            }
+        }
+        roles.sort();
+        roles
+    }
+
+    /// Writes the employees × skills matrix to `path`.
+    pub fn export_skills_matrix_to(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create CSV file {:?}: {}", path, e))?;
+        export_skills_matrix(self.workspace.employees(), self.workspace.skills(), file)
+            .map_err(|e| format!("Failed to write skills matrix to {:?}: {}", path, e))
+    }
+
+    /// Tags the selected employee with the typed skill, adding it to the catalog if it is new.
+    fn add_skill_to_selected_employee(&mut self) {
+        let name = self.skill_input.trim().to_string();
+        let Some(selected_id) = self.selected_employee_id.clone() else { return };
+        match self.workspace.add_employee_skill(&selected_id, &name) {
+            Ok(true) => self.skill_input.clear(),
+            Ok(false) => {}
+            Err(e) => self.report_workspace_error(e),
+        }
+    }
+
+    fn save_project_with_status(&mut self, path: PathBuf) {
+        match self.save_project_to(&path) {
+            Ok(_) => {
+                self.status_message = Some(format!("Saved {}", path.display()));
+                self.persist_session_files();
+            }
+            Err(e) => self.report_failure(format!("Save failed: {}", e)),
+        }
+    }
+
+    /// Reads the CSV at `path` through the active mapping profile and holds the result
+    /// for review; nothing replaces the employee list until `accept_pending_import`.
+    pub fn stage_csv_import(&mut self, path: &Path) -> Result<(), String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open CSV file {:?}: {}", path, e))?;
+        let outcome = import_employees_with_report(BufReader::new(file), &self.app_settings.active_mapping_profile())
+            .map_err(|e| format!("Failed to parse CSV from {:?}: {}", path, e))?;
+        self.pending_import = Some(PendingImport {
+            source: path.to_path_buf(),
+            outcome,
+        });
+        Ok(())
+    }
+
+    /// Replaces the employee list with the reviewed import, returning how many employees were loaded.
+    pub fn accept_pending_import(&mut self) -> Option<usize> {
+        let pending = self.pending_import.take()?;
+        let count = pending.outcome.employees.len();
+        self.save_note_draft();
+        self.note_editor = None;
+        let summary = self.workspace.import_employees(pending.outcome.employees);
+        self.report_succession_import(&summary.succession, summary.successors_removed);
+        self.seed_report = summary.seed_report;
+        self.selected_employee_id = None;
+        self.reports_to_filter = None;
+        Some(count)
+    }
+
+    /// Writes the employee list to `path` using the active mapping profile's headers,
+    /// with 9-box values in the project's label scheme.
+    pub fn export_csv_to(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create CSV file {:?}: {}", path, e))?;
+        let settings = self.workspace.settings();
+        let employees = relabel_nine_boxes(&self.workspace.employees_for_export(), &settings.grid_template, &settings.label_scheme);
+        export_employees_with_profile(&employees, &self.app_settings.active_mapping_profile(), file)
+            .map_err(|e| format!("Failed to write CSV to {:?}: {}", path, e))
+    }
+
+    /// Loads the header and first rows of the CSV at `path` into the mapping editor.
+    pub fn load_mapping_preview(&mut self, path: &Path) -> Result<(), String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open CSV file {:?}: {}", path, e))?;
+        let preview = read_csv_preview(BufReader::new(file), MAPPING_PREVIEW_ROWS)
+            .map_err(|e| format!("Failed to read CSV preview from {:?}: {}", path, e))?;
+        self.mapping_editor.preview = Some(preview);
+        self.mapping_editor.preview_path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Stores the edited profile in the settings, makes it the active profile and saves the settings.
+    fn save_mapping_profile(&mut self) {
+        let mut profile = self.mapping_editor.profile.clone();
+        profile.name = profile.name.trim().to_string();
+        if profile.name.is_empty() {
+            self.status_message = Some("Mapping profile needs a name.".to_string());
+            return;
+        }
+        for rule in profile.fields.values_mut().chain(profile.cycle_fields.values_mut()) {
+            rule.aliases.retain(|alias| !alias.trim().is_empty());
+        }
+        self.mapping_editor.profile = profile.clone();
+        self.app_settings.active_mapping_profile = Some(profile.name.clone());
+        self.app_settings.upsert_mapping_profile(profile);
+
+        self.status_message = Some("Mapping profile saved.".to_string());
+        self.persist_app_settings("the mapping profile");
+    }
+
+    fn pick_project_save_path() -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("9-Box Project", &[PROJECT_FILE_EXTENSION])
+            .save_file()
+            .map(|mut path| {
+                if path.extension().is_none() {
+                    path.set_extension(PROJECT_FILE_EXTENSION);
+                }
+                path
+            })
     }
 
     // Fallback function to load dummy employees
     fn load_dummy_employees() -> Vec<Employee> {
-        vec![
-            Employee {
-                user_id: "1".to_string(),
-                pr_group_2025: "GroupA".to_string(),
-                first_name: "John (Dummy)".to_string(),
-                last_name: "Doe".to_string(),
-                current_position: "Developer".to_string(),
-                current_temp_position: None,
-                pr_2021: None, pr_2022: None, pr_2023: None, pr_2024: Some(4.5),
-                user_9box_2024: Some("1A".to_string()), user_9box_2025: None,
-                notes: None, current_label: None, email: None, manager_id: None,
-                department: None, location: None, hire_date: None,
-            },
-            Employee {
-                user_id: "2".to_string(),
-                pr_group_2025: "GroupB".to_string(),
-                first_name: "Jane (Dummy)".to_string(),
-                last_name: "Smith".to_string(),
-                current_position: "Designer".to_string(),
-                current_temp_position: None,
-                pr_2021: None, pr_2022: None, pr_2023: None, pr_2024: Some(4.2),
-                user_9box_2024: Some("2B".to_string()), user_9box_2025: None,
-                notes: None, current_label: None, email: None, manager_id: None,
-                department: None, location: None, hire_date: None,
-            },
-        ]
+        let mut john = Employee {
+            user_id: "1".to_string(),
+            first_name: "John (Dummy)".to_string(),
+            last_name: "Doe".to_string(),
+            current_position: "Developer".to_string(),
+            ..Employee::default()
+        };
+        john.cycle_mut("2024").rating = Some(4.5);
+        john.cycle_mut("2024").nine_box = Some("1A".to_string());
+        john.cycle_mut("2025").group = Some("GroupA".to_string());
+
+        let mut jane = Employee {
+            user_id: "2".to_string(),
+            first_name: "Jane (Dummy)".to_string(),
+            last_name: "Smith".to_string(),
+            current_position: "Designer".to_string(),
+            ..Employee::default()
+        };
+        jane.cycle_mut("2024").rating = Some(4.2);
+        jane.cycle_mut("2024").nine_box = Some("2B".to_string());
+        jane.cycle_mut("2025").group = Some("GroupB".to_string());
+
+        vec![john, jane]
+    }
+}
+
+// Aliases are edited as one comma-separated string. Empty entries are kept while typing
+// (so a trailing comma survives) and dropped when the profile is saved.
+fn split_aliases(aliases: &str) -> Vec<String> {
+    if aliases.trim().is_empty() {
+        return Vec::new();
     }
+    aliases.split(',').map(|alias| alias.trim().to_string()).collect()
 }
 
 impl iced::Application for App {
@@ -143,7 +1085,13 @@ impl iced::Application for App {
     }
 
     fn title(&self) -> String {
-        String::from("Box Planner")
+        let file_name = self.project_path.as_ref().and_then(|path| path.file_name());
+        match (file_name, self.unsaved_changes) {
+            (Some(file_name), false) => format!("Box Planner - {} (saved)", file_name.to_string_lossy()),
+            (Some(file_name), true) => format!("Box Planner - {} (unsaved changes)", file_name.to_string_lossy()),
+            (None, false) => String::from("Box Planner"),
+            (None, true) => String::from("Box Planner (unsaved changes, no project file yet)"),
+        }
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
@@ -152,65 +1100,318 @@ impl iced::Application for App {
         match message {
             Message::EmployeeSelected(id) => {
                 println!("Employee selected: {}", id);
+                self.open_note_editor(&id);
                 self.selected_employee_id = Some(id);
             }
             Message::BoxClicked(box_id) => {
                 if let Some(employee_id) = self.selected_employee_id.clone() {
-                    // Remove employee from any previous box
-                    for (_b_id, emp_ids) in self.grid_state.assignments.iter_mut() {
-                        if let Some(pos) = emp_ids.iter().position(|id| *id == employee_id) {
-                            emp_ids.remove(pos);
-                        }
+                    match self.workspace.assign(&employee_id, box_id) {
+                        Ok(_) => self.selected_employee_id = None, // Clear selection
+                        Err(e) => self.report_workspace_error(e),
                     }
-                    // Remove boxes with empty assignments
-                    self.grid_state.assignments.retain(|_, emp_ids| !emp_ids.is_empty());
-
-                    // Add employee to the new box
-                    self.grid_state.assignments
-                        .entry(box_id.clone())
-                        .or_default()
-                        .push(employee_id.clone());
-                    
-                    println!("Assigned employee {} to box {}", employee_id, box_id);
-
-                    // Attempt to persist grid_state
-                    // let persistence_path = "box_planner_ui/grid_data.json"; // Example path
-                    // match box_planner_core::persistence::save_grid_state(&self.grid_state, persistence_path) {
-                    //     Ok(_) => println!("Grid state saved to {}", persistence_path),
-                    //     Err(e) => eprintln!("Failed to save grid state: {}. Proceeding with in-memory state.", e),
-                    // }
-                    println!("Persistence: 'save_grid_state' function not found in core/src/persistence.rs. Skipping file save. State is in-memory only.");
-
-
-                    self.selected_employee_id = None; // Clear selection
                 } else {
                     println!("Box {} clicked, but no employee selected.", box_id);
                 }
             }
+            Message::ReportsToFilterSelected(manager_id) => {
+                self.reports_to_filter = manager_id;
+            }
+            Message::RiskBoxClicked(position) => {
+                if let Some(employee_id) = self.selected_employee_id.clone() {
+                    let mut on_matrix = false;
+                    self.edit_employee(&employee_id, "Set impact and risk", |employee| {
+                        on_matrix = set_risk_position(employee, position);
+                    });
+                    if on_matrix {
+                        self.selected_employee_id = None;
+                    }
+                }
+            }
+            Message::ImpactOfLossSelected(employee_id, impact) => {
+                self.edit_employee(&employee_id, "Set impact of loss", |employee| employee.impact_of_loss = Some(impact));
+            }
+            Message::AttritionRiskSelected(employee_id, risk) => {
+                self.edit_employee(&employee_id, "Set attrition risk", |employee| employee.attrition_risk = Some(risk));
+            }
+            Message::DragStarted(employee_id) => {
+                self.drag = Some(Drag { employee_id, hovered: None });
+                self.last_drop = None;
+            }
+            Message::DragEntered(target) => {
+                if let Some(drag) = &mut self.drag {
+                    drag.hovered = Some(target);
+                }
+            }
+            Message::DragExited(target) => {
+                // Moving between neighbouring targets may report the new one first
+                if let Some(drag) = self.drag.as_mut().filter(|drag| drag.hovered == Some(target)) {
+                    drag.hovered = None;
+                }
+            }
+            Message::EmployeeDropped(employee_id, target) => self.drop_employee(&employee_id, target),
+            Message::DragEnded => self.drag = None,
+            Message::Undo => self.undo(),
+            Message::WorkspaceChanged(event) => self.on_workspace_event(event),
+            Message::AutoSaveToggled(enabled) => self.set_auto_save(enabled),
+            Message::NoteEdited(action) => self.edit_note(action),
+            Message::NoteCategorySelected(category) => self.set_note_category(category),
+            Message::NoteVersionRestored(index) => self.restore_note_version(index),
+            Message::NoteAuthorChanged(author) => {
+                self.app_settings.note_author = author;
+                self.persist_app_settings("the note author");
+            }
+            Message::Tick(now) => {
+                self.save_note_draft_if_due(now);
+                self.save_development_draft_if_due(now);
+                self.autosave(now);
+            }
+            Message::CloseRequested(id) => return self.close_window(id),
+            Message::Redo => self.redo(),
             Message::ScaleChanged(new_scale) => {
                 println!("Scale changed: {}", new_scale);
                 self.view_scale = new_scale;
                 self.app_settings.view_scale = Some(new_scale);
                 
-                let settings_path = Path::new(SETTINGS_FILE_PATH);
+                let settings_path = self.paths.settings.as_path();
                 match save_app_settings(settings_path, &self.app_settings) {
                     Ok(_) => println!("Successfully saved settings to {:?}", settings_path),
                     Err(e) => eprintln!("Failed to save settings to {:?}: {}", settings_path, e),
                 }
             }
+            Message::OpenProject => {
+                let picked = rfd::FileDialog::new()
+                    .add_filter("9-Box Project", &[PROJECT_FILE_EXTENSION])
+                    .pick_file();
+                if let Some(path) = picked {
+                    if let Err(e) = self.open_project_from(&path) {
+                        self.report_failure(format!("Open failed: {}", e));
+                    }
+                }
+            }
+            Message::SaveProject => {
+                if let Some(path) = self.project_path.clone().or_else(Self::pick_project_save_path) {
+                    self.save_project_with_status(path);
+                }
+            }
+            Message::SaveProjectAs => {
+                if let Some(path) = Self::pick_project_save_path() {
+                    self.save_project_with_status(path);
+                }
+            }
+            Message::GridTemplateSelected(name) => {
+                if let Some(template) = GridTemplate::built_in().into_iter().find(|template| template.name == name) {
+                    self.apply_grid_template(template);
+                }
+            }
+            Message::LabelSchemeSelected(name) => {
+                if let Some(scheme) = LabelScheme::built_in().into_iter().find(|scheme| scheme.name == name) {
+                    self.status_message = Some(format!("Box labels: {}", scheme.name));
+                    self.workspace.set_label_scheme(scheme);
+                }
+            }
+            Message::PlacementCycleSelected(cycle) => {
+                self.seed_grid_from(&cycle);
+                if let Some(report) = &self.seed_report {
+                    self.status_message = Some(format!(
+                        "Placed {} employees from {}; {} not placed.",
+                        report.placed,
+                        cycle,
+                        report.unplaced_count()
+                    ));
+                }
+            }
+            Message::LoadGridTemplate => {
+                let picked = rfd::FileDialog::new().add_filter("Grid Template", &["json"]).pick_file();
+                if let Some(path) = picked {
+                    match load_grid_template(&path) {
+                        Ok(template) => self.apply_grid_template(template),
+                        Err(e) => self.report_failure(format!("Template not loaded: {}", e)),
+                    }
+                }
+            }
+            Message::SkillInputChanged(name) => {
+                self.skill_input = name;
+            }
+            Message::SkillAdded => {
+                self.add_skill_to_selected_employee();
+            }
+            Message::SkillRemoved(employee_id, name) => {
+                self.edit_employee(&employee_id, &format!("Remove {}", name), |employee| {
+                    employee.remove_skill(&name);
+                });
+            }
+            Message::SkillLevelChanged(employee_id, name, level) => {
+                self.edit_employee(&employee_id, &format!("Set {} level", name), |employee| {
+                    employee.set_skill_level(&name, level);
+                });
+            }
+            Message::RoleSelected(role) => {
+                self.selected_role = Some(role);
+            }
+            Message::RoleRequirementChanged(skill, level) => {
+                if let Some(role) = self.selected_role.clone() {
+                    self.workspace.set_role_requirement(&role, &skill, level);
+                }
+            }
+            Message::ExportSkillsMatrix => {
+                let picked = rfd::FileDialog::new().add_filter("CSV", &["csv"]).save_file();
+                if let Some(path) = picked {
+                    match self.export_skills_matrix_to(&path) {
+                        Ok(_) => self.status_message = Some(format!("Exported skills matrix to {}", path.display())),
+                        Err(e) => self.report_failure(format!("Export failed: {}", e)),
+                    }
+                }
+            }
+            Message::SuccessionRoleInputChanged(position) => {
+                self.succession_role_input = position;
+            }
+            Message::SuccessionRoleAdded => {
+                let position = self.succession_role_input.clone();
+                self.workspace.edit_succession(&format!("Add succession role {}", position.trim()), |plan| plan.add_role(&position, None));
+                self.succession_role_input.clear();
+            }
+            Message::SuccessionRoleRemoved(position) => {
+                self.workspace.edit_succession(&format!("Remove succession role {}", position), |plan| plan.remove_role(&position));
+            }
+            Message::CriticalitySelected(position, criticality) => {
+                self.workspace.edit_succession(&format!("Set criticality of {}", position), |plan| match plan.role_mut(&position) {
+                    Some(role) => role.criticality.replace(criticality) != Some(criticality),
+                    None => false,
+                });
+            }
+            Message::SuccessorAdded(position, employee_id) => {
+                self.workspace.edit_succession(&format!("Add successor for {}", position), |plan| plan.add_successor(&position, &employee_id, None));
+            }
+            Message::SuccessorRemoved(position, employee_id) => {
+                self.workspace.edit_succession(&format!("Remove successor for {}", position), |plan| plan.remove_successor(&position, &employee_id));
+            }
+            Message::SuccessorRanked(position, employee_id, rank) => {
+                self.workspace.edit_succession(&format!("Rank successor for {}", position), |plan| plan.rank_successor(&position, &employee_id, rank));
+            }
+            Message::SuccessorReadinessSelected(position, employee_id, readiness) => {
+                self.workspace.edit_succession(&format!("Set successor readiness for {}", position), |plan| {
+                    match plan.role_mut(&position).and_then(|role| role.successor_mut(&employee_id)) {
+                        Some(successor) => successor.readiness.replace(readiness) != Some(readiness),
+                        None => false,
+                    }
+                });
+            }
+            Message::SuccessorDevelopmentChanged(position, employee_id, development) => {
+                self.edit_development(position, employee_id, development);
+            }
+            Message::SuccessorDevelopmentSubmitted => self.save_development_draft(),
+            Message::CatalogSkillAdded => {
+                if self.workspace.add_catalog_skill(&self.skill_input) {
+                    self.skill_input.clear();
+                }
+            }
+            Message::CatalogSkillRemoved(name) => {
+                let affected = self.workspace.remove_catalog_skill(&name);
+                self.status_message = Some(format!("Removed skill '{}' from the catalog and {} employee(s).", name, affected));
+            }
+            Message::ScreenSelected(screen) => {
+                self.screen = screen;
+            }
+            Message::ImportCsv => {
+                let picked = rfd::FileDialog::new().add_filter("CSV", &["csv"]).pick_file();
+                if let Some(path) = picked {
+                    if let Err(e) = self.stage_csv_import(&path) {
+                        self.report_failure(format!("Import failed: {}", e));
+                    }
+                }
+            }
+            Message::ImportAccepted => {
+                if let Some(count) = self.accept_pending_import() {
+                    self.status_message = Some(format!("Imported {} employees.", count));
+                }
+            }
+            Message::ImportDiscarded => {
+                self.pending_import = None;
+                self.status_message = Some("Import discarded.".to_string());
+            }
+            Message::ExportCsv => {
+                let picked = rfd::FileDialog::new().add_filter("CSV", &["csv"]).save_file();
+                if let Some(path) = picked {
+                    match self.export_csv_to(&path) {
+                        Ok(_) => {
+                            let count = self.workspace.employees().len();
+                            self.status_message = Some(format!("Exported {} employees to {}", count, path.display()));
+                        }
+                        Err(e) => self.report_failure(format!("Export failed: {}", e)),
+                    }
+                }
+            }
+            Message::MappingProfileSelected(name) => {
+                if let Some(profile) = self
+                    .app_settings
+                    .available_mapping_profiles()
+                    .into_iter()
+                    .find(|profile| profile.name == name)
+                {
+                    self.mapping_editor.profile = profile;
+                }
+            }
+            Message::MappingProfileNameChanged(name) => {
+                self.mapping_editor.profile.name = name;
+            }
+            Message::MappingFieldHeaderChanged(field, header) => {
+                let profile = &mut self.mapping_editor.profile;
+                let mut rule = profile.field_rule(field);
+                rule.header = header;
+                profile.fields.insert(field, rule);
+            }
+            Message::MappingFieldAliasesChanged(field, aliases) => {
+                let profile = &mut self.mapping_editor.profile;
+                let mut rule = profile.field_rule(field);
+                rule.aliases = split_aliases(&aliases);
+                profile.fields.insert(field, rule);
+            }
+            Message::MappingCycleHeaderChanged(cycle_field, header) => {
+                let profile = &mut self.mapping_editor.profile;
+                let mut rule = profile.cycle_rule(cycle_field);
+                rule.header = header;
+                profile.cycle_fields.insert(cycle_field, rule);
+            }
+            Message::MappingCycleAliasesChanged(cycle_field, aliases) => {
+                let profile = &mut self.mapping_editor.profile;
+                let mut rule = profile.cycle_rule(cycle_field);
+                rule.aliases = split_aliases(&aliases);
+                profile.cycle_fields.insert(cycle_field, rule);
+            }
+            Message::MappingPreviewRequested => {
+                let picked = rfd::FileDialog::new().add_filter("CSV", &["csv"]).pick_file();
+                if let Some(path) = picked {
+                    if let Err(e) = self.load_mapping_preview(&path) {
+                        self.report_failure(e);
+                    }
+                }
+            }
+            Message::MappingProfileSaved => {
+                self.save_mapping_profile();
+            }
         }
         Command::none()
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         // Delegate to the view_app function in views.rs
         view_app(self)
     }
 
-    // subscription method can be added later if needed for background tasks
-    // fn subscription(&self) -> Subscription<Message> {
-    //     Subscription::none()
-    // }
+    fn subscription(&self) -> Subscription<Message> {
+        // Ticks only while a debounced save is waiting
+        let note_save_due = self.note_editor.as_ref().is_some_and(|editor| editor.save_due.is_some());
+        let debounce = if self.autosave_due.is_some() || note_save_due || self.development_draft.is_some() {
+            iced::time::every(Duration::from_millis(250)).map(Message::Tick)
+        } else {
+            Subscription::none()
+        };
+        let close_requests = event::listen_with(|event, _status| match event {
+            Event::Window(id, window::Event::CloseRequested) => Some(Message::CloseRequested(id)),
+            _ => None,
+        });
+        Subscription::batch([self.keyboard_shortcuts(), self.workspace_changes(), debounce, close_requests])
+    }
 
     // theme method can be added if custom theming is desired
     // fn theme(&self) -> Self::Theme {
@@ -218,29 +1419,81 @@ impl iced::Application for App {
     // }
 }
 
+impl App {
+    // Undo with Ctrl+Z (Cmd+Z on macOS); redo with Ctrl+Shift+Z or Ctrl+Y
+    fn keyboard_shortcuts(&self) -> Subscription<Message> {
+        keyboard::on_key_press(|key: Key, modifiers: Modifiers| {
+            if !modifiers.command() {
+                return None;
+            }
+            match key.as_ref() {
+                Key::Character("z") | Key::Character("Z") if modifiers.shift() => Some(Message::Redo),
+                Key::Character("z") | Key::Character("Z") => Some(Message::Undo),
+                Key::Character("y") | Key::Character("Y") => Some(Message::Redo),
+                _ => None,
+            }
+        })
+    }
+
+    // Delivers workspace events as `Message::WorkspaceChanged`. The receiver is taken the first
+    // time; iced keeps that stream running for as long as the subscription ID stays the same.
+    fn workspace_changes(&self) -> Subscription<Message> {
+        let receiver = self.workspace_events.lock().ok().and_then(|mut receiver| receiver.take());
+        let events: BoxStream<'static, Message> = match receiver {
+            Some(receiver) => receiver.map(Message::WorkspaceChanged).boxed(),
+            None => stream::pending().boxed(),
+        };
+        iced::subscription::run_with_id("workspace-events", events)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*; 
+    use iced::Application; // Brings `update` into scope for the tests below
+    use box_planner_core::csv_processing::{CycleField, EmployeeField};
+    use box_planner_core::grid::GridPosition;
+    use box_planner_core::retention::{risk_position, AttritionRisk, ImpactOfLoss};
+    use box_planner_core::skills::employee_skill_gaps;
+    use box_planner_core::succession::{Criticality, Readiness};
+    use tempfile::TempDir;
     // AppSettings is already imported via super::* if App itself is, 
     // but being explicit for models can be clearer.
     // Employee and GridState are part of App struct, so super::* covers them.
     // use box_planner_core::models::{Employee, GridState, AppSettings}; // Not strictly needed if super::* is used well.
 
+    // The app's files, all kept in `dir`.
+    fn paths_in(dir: &Path) -> AppPaths {
+        AppPaths {
+            settings: dir.join("app_settings.json"),
+            sample_employees: dir.join("sample_employees.csv"),
+            grid_state: dir.join("grid_state.json"),
+            notes_dir: dir.join("notes"),
+            skills: dir.join("skills.json"),
+            roles: dir.join("roles.json"),
+            succession: dir.join("succession.json"),
+        }
+    }
+
     // Helper to create a basic App for testing.
     // It relies on App::new()'s existing behavior for handling missing files
     // (falling back to dummy employees and default settings).
-    fn setup_app() -> App {
-        // To prevent tests from creating/modifying actual files in the project directory,
-        // ideally, paths should be configurable or mocked.
-        // For now, we accept that App::new() might try to read/write
-        // "box_planner_ui/app_settings.json" and read "box_planner_ui/sample_employees.csv".
-        // The current App::new() logic falls back to defaults/dummies, which is testable.
-        App::new()
+    // The directory is removed when the returned `TempDir` is dropped, so keep it for the whole test.
+    fn setup_app() -> (App, TempDir) {
+        // Settings, notes and session files live in a fresh directory, so tests neither see nor
+        // change the project directory's or each other's. With no files there, the app starts
+        // from default settings and dummy employees.
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        (App::with_paths(paths_in(dir.path())), dir)
+    }
+
+    fn grid_position(code: &str) -> GridPosition {
+        GridPosition::from_code(code).expect("Invalid grid code in test")
     }
 
     #[test]
     fn test_initial_state() {
-        let app = setup_app();
+        let (app, _files) = setup_app();
         assert_eq!(app.selected_employee_id, None, "Selected employee ID should be None initially.");
         
         // Check view_scale based on AppSettings default or loaded settings.
@@ -249,34 +1502,28 @@ mod tests {
         assert_eq!(app.view_scale, app.app_settings.view_scale.unwrap_or(1.0), "View scale should match settings or default.");
         assert!(app.view_scale > 0.0, "View scale must be positive.");
 
-        assert!(!app.employees.is_empty(), "Employees list should not be empty (dummy data should load).");
+        assert!(!app.workspace.employees().is_empty(), "Employees list should not be empty (dummy data should load).");
         
         // Check default app_settings state (theme_preference is a good indicator)
         // This implicitly tests that AppSettings::default() was called if file was missing.
         let default_settings = AppSettings::default();
-        if !Path::new(SETTINGS_FILE_PATH).exists() {
-            assert_eq!(app.app_settings.theme_preference, default_settings.theme_preference, "Theme preference should be default if no settings file.");
-            assert_eq!(app.app_settings.view_scale, default_settings.view_scale, "View scale in settings should be default if no settings file.");
-        } else {
-            // If file exists, we can't easily know its content here without reading it again,
-            // but we know app.app_settings was populated.
-            println!("Note: test_initial_state assumes if settings file exists, it's valid or App::new handled errors.");
-        }
+        assert_eq!(app.app_settings.theme_preference, default_settings.theme_preference, "Theme preference should be default if no settings file.");
+        assert_eq!(app.app_settings.view_scale, default_settings.view_scale, "View scale in settings should be default if no settings file.");
     }
 
     #[test]
     fn test_employee_selected() {
-        let mut app = setup_app();
+        let (mut app, _files) = setup_app();
         let test_emp_id = "emp_test_id_selected".to_string();
-        app.update(Message::EmployeeSelected(test_emp_id.clone()));
+        let _ = app.update(Message::EmployeeSelected(test_emp_id.clone()));
         assert_eq!(app.selected_employee_id, Some(test_emp_id), "Selected employee ID was not set correctly.");
     }
 
     #[test]
     fn test_scale_changed() {
-        let mut app = setup_app();
+        let (mut app, _files) = setup_app();
         let new_scale = 1.75;
-        app.update(Message::ScaleChanged(new_scale));
+        let _ = app.update(Message::ScaleChanged(new_scale));
         assert_eq!(app.view_scale, new_scale, "View scale in App struct was not updated.");
         assert_eq!(app.app_settings.view_scale, Some(new_scale), "View scale in AppSettings was not updated.");
         // We expect App::new to create a default settings file if it doesn't exist,
@@ -287,60 +1534,746 @@ mod tests {
 
     #[test]
     fn test_assign_employee_to_box() {
-        let mut app = setup_app();
+        let (mut app, _files) = setup_app();
         // App::new() loads dummy employees if CSV fails or is empty.
         // This test relies on at least one employee being available.
-        assert!(!app.employees.is_empty(), "Prerequisite: Employee list is empty, cannot run test.");
-        let test_emp_id = app.employees[0].user_id.clone();
-        let target_box_id = "TestBox_Assign".to_string();
+        assert!(!app.workspace.employees().is_empty(), "Prerequisite: Employee list is empty, cannot run test.");
+        let test_emp_id = app.workspace.employees()[0].user_id.clone();
+        let target_box_id = grid_position("1A");
 
-        app.update(Message::EmployeeSelected(test_emp_id.clone()));
-        app.update(Message::BoxClicked(target_box_id.clone()));
+        let _ = app.update(Message::EmployeeSelected(test_emp_id.clone()));
+        let _ = app.update(Message::BoxClicked(target_box_id));
 
-        assert!(app.grid_state.assignments.get(&target_box_id).is_some(), "Target box should exist in assignments.");
-        assert!(app.grid_state.assignments.get(&target_box_id).unwrap().contains(&test_emp_id), "Employee was not assigned to the target box.");
+        assert!(app.workspace.grid_state().assignments.contains_key(&target_box_id), "Target box should exist in assignments.");
+        assert!(app.workspace.grid_state().assignments.get(&target_box_id).unwrap().contains(&test_emp_id), "Employee was not assigned to the target box.");
         assert_eq!(app.selected_employee_id, None, "Selected employee ID should be cleared after assignment.");
     }
     
     #[test]
     fn test_move_employee_between_boxes() {
-        let mut app = setup_app();
-        assert!(!app.employees.is_empty(), "Prerequisite: Employee list is empty, cannot run test.");
-        let test_emp_id = app.employees[0].user_id.clone();
-        let initial_box_id = "BoxAlpha_Move".to_string();
-        let target_box_id = "BoxBeta_Move".to_string();
+        let (mut app, _files) = setup_app();
+        assert!(!app.workspace.employees().is_empty(), "Prerequisite: Employee list is empty, cannot run test.");
+        let test_emp_id = app.workspace.employees()[0].user_id.clone();
+        let initial_box_id = grid_position("2B");
+        let target_box_id = grid_position("3C");
 
         // First assignment to initial_box_id
-        app.update(Message::EmployeeSelected(test_emp_id.clone()));
-        app.update(Message::BoxClicked(initial_box_id.clone()));
+        let _ = app.update(Message::EmployeeSelected(test_emp_id.clone()));
+        let _ = app.update(Message::BoxClicked(initial_box_id));
         
         // Now select again and move to target_box_id
-        app.update(Message::EmployeeSelected(test_emp_id.clone()));
-        app.update(Message::BoxClicked(target_box_id.clone()));
+        let _ = app.update(Message::EmployeeSelected(test_emp_id.clone()));
+        let _ = app.update(Message::BoxClicked(target_box_id));
 
-        assert!(app.grid_state.assignments.get(&target_box_id).is_some(), "Target box for move should exist.");
-        assert!(app.grid_state.assignments.get(&target_box_id).unwrap().contains(&test_emp_id), "Employee was not moved to the target box.");
+        assert!(app.workspace.grid_state().assignments.contains_key(&target_box_id), "Target box for move should exist.");
+        assert!(app.workspace.grid_state().assignments.get(&target_box_id).unwrap().contains(&test_emp_id), "Employee was not moved to the target box.");
         
         // Check if employee is removed from the old box or if the old box is removed if empty
-        let initial_box_assignments = app.grid_state.assignments.get(&initial_box_id);
-        assert!(initial_box_assignments.map_or(true, |ids| !ids.contains(&test_emp_id)), "Employee was not removed from the initial box.");
+        let initial_box_assignments = app.workspace.grid_state().assignments.get(&initial_box_id);
+        assert!(initial_box_assignments.is_none_or(|ids| !ids.contains(&test_emp_id)), "Employee was not removed from the initial box.");
         
         assert_eq!(app.selected_employee_id, None, "Selected employee ID should be cleared after moving.");
     }
     
     #[test]
     fn test_click_box_no_employee_selected() {
-        let mut app = setup_app();
+        let (mut app, _files) = setup_app();
         // Clone initial state of assignments. 
         // App::new() might populate assignments with dummy data, so we capture that.
-        let initial_assignments = app.grid_state.assignments.clone();
-        let target_box_id = "AnyBox_NoSelect".to_string();
+        let initial_assignments = app.workspace.grid_state().assignments.clone();
+        let target_box_id = grid_position("1C");
         
-        app.update(Message::BoxClicked(target_box_id.clone()));
+        let _ = app.update(Message::BoxClicked(target_box_id));
         
         // Assert that assignments are unchanged.
         // If the target_box_id was part of initial_assignments, its content should be the same.
         // If it wasn't, it should still not be there.
-        assert_eq!(app.grid_state.assignments, initial_assignments, "Assignments should not change if no employee is selected.");
+        assert_eq!(app.workspace.grid_state().assignments, initial_assignments, "Assignments should not change if no employee is selected.");
+    }
+
+    #[test]
+    fn test_switching_template_unplaces_employees_outside_the_grid() {
+        let (mut app, _files) = setup_app();
+        let ids: Vec<String> = app.workspace.employees().iter().map(|e| e.user_id.clone()).collect();
+        for id in &ids {
+            app.workspace.unassign(id).expect("Employee should exist");
+        }
+        let (kept, dropped) = (&ids[0], &ids[1]);
+        app.workspace.assign(kept, grid_position("1A")).expect("1A is on the 3x3 grid");
+        app.workspace.assign(dropped, grid_position("3C")).expect("3C is on the 3x3 grid");
+
+        let _ = app.update(Message::GridTemplateSelected(GridTemplate::impact_risk().name));
+
+        assert_eq!(app.workspace.settings().grid_template, GridTemplate::impact_risk());
+        assert_eq!(app.workspace.grid_state().assignments.len(), 1, "Only boxes on the 2x2 grid should remain.");
+        assert!(app.workspace.grid_state().assignments.contains_key(&grid_position("1A")));
+
+        // Boxes outside the current grid ignore clicks
+        let _ = app.update(Message::EmployeeSelected(kept.clone()));
+        let _ = app.update(Message::BoxClicked(grid_position("3C")));
+        assert!(!app.workspace.grid_state().assignments.contains_key(&grid_position("3C")));
+        assert_eq!(app.workspace.grid_state().position_of(kept), Some(grid_position("1A")));
+    }
+
+    #[test]
+    fn test_project_save_and_open_round_trip() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let project_file = dir.path().join("session.9box");
+
+        let (mut app, _files) = setup_app();
+        let test_emp_id = app.workspace.employees()[0].user_id.clone();
+        app.workspace.assign(&test_emp_id, grid_position("2B")).expect("2B is on the 3x3 grid");
+        app.workspace
+            .set_note(&test_emp_id, Some("Discussed in calibration".to_string()))
+            .expect("Employee should exist");
+        assert!(app.save_project_to(&project_file).is_ok(), "Saving the project failed.");
+        assert_eq!(app.project_path, Some(project_file.clone()));
+
+        let (mut reopened, _reopened_files) = setup_app();
+        reopened.workspace = Workspace::default();
+        assert!(reopened.open_project_from(&project_file).is_ok(), "Opening the project failed.");
+        assert_eq!(reopened.workspace.employees(), app.workspace.employees());
+        assert_eq!(reopened.workspace.grid_state(), app.workspace.grid_state());
+        assert_eq!(reopened.workspace.notes().get(&test_emp_id), Some(&"Discussed in calibration".to_string()));
+        assert_eq!(reopened.project_path, Some(project_file));
+        assert_eq!(reopened.title(), "Box Planner - session.9box (saved)", "Title should show the project file name.");
+    }
+
+    #[test]
+    fn test_note_history_travels_with_the_project() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let project_file = dir.path().join("handover.9box");
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        append_employee_note(&app.paths.notes_dir, &employee_id, NoteEntry::new("First impression", "Ann", Some(NoteCategory::Strengths)))
+            .unwrap();
+        app.workspace.set_note(&employee_id, Some("After calibration".to_string())).expect("Employee should exist");
+        assert!(app.save_project_to(&project_file).is_ok(), "Saving the project failed.");
+
+        // A colleague opens the project with a notes store of their own
+        let (mut colleague, _colleague_files) = setup_app();
+        assert!(colleague.open_project_from(&project_file).is_ok(), "Opening the project failed.");
+        let history = load_note_history(&colleague.paths.notes_dir, &employee_id).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0].text, "First impression", "The older version survives the handover.");
+        assert_eq!(history.entries[0].author, "Ann");
+        assert_eq!(history.entries[0].category, Some(NoteCategory::Strengths));
+        assert_eq!(history.current_text(), "After calibration");
+        assert_eq!(colleague.workspace.notes().get(&employee_id).map(String::as_str), Some("After calibration"));
+    }
+
+    #[test]
+    fn test_edit_and_save_mapping_profile() {
+        let (mut app, _files) = setup_app();
+        let _ = app.update(Message::ScreenSelected(Screen::MappingEditor));
+        assert_eq!(app.screen, Screen::MappingEditor);
+
+        let _ = app.update(Message::MappingProfileNameChanged("Test HRIS".to_string()));
+        let _ = app.update(Message::MappingFieldHeaderChanged(EmployeeField::ManagerId, "Reports to".to_string()));
+        let _ = app.update(Message::MappingFieldAliasesChanged(EmployeeField::ManagerId, "Manager ID, ".to_string()));
+        let _ = app.update(Message::MappingCycleHeaderChanged(CycleField::Rating, "PR {cycle}".to_string()));
+        let _ = app.update(Message::MappingProfileSaved);
+
+        assert_eq!(app.app_settings.active_mapping_profile, Some("Test HRIS".to_string()));
+        let active = app.app_settings.active_mapping_profile();
+        assert_eq!(active.field_rule(EmployeeField::ManagerId).header, "Reports to");
+        assert_eq!(active.field_rule(EmployeeField::ManagerId).aliases, vec!["Manager ID".to_string()]);
+        assert_eq!(active.cycle_rule(CycleField::Rating).header, "PR {cycle}");
+    }
+
+    #[test]
+    fn test_import_and_export_csv_with_active_profile() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let source = dir.path().join("hris.csv");
+        std::fs::write(
+            &source,
+            "User ID,First Name,Last Name,Current Position,Reports to,PR 2024\nu1,Ann,Lee,Analyst,m1,4.2\n",
+        )
+        .expect("Failed to write CSV");
+
+        let (mut app, _files) = setup_app();
+        app.app_settings.active_mapping_profile = Some(MappingProfile::legacy_hris().name);
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+        let _ = app.update(Message::ImportAccepted);
+        assert_eq!(app.workspace.employees().len(), 1);
+        assert_eq!(app.workspace.employees()[0].manager_id, Some("m1".to_string()));
+        assert_eq!(app.workspace.employees()[0].rating("2024"), Some(4.2));
+
+        let exported = dir.path().join("export.csv");
+        assert!(app.export_csv_to(&exported).is_ok(), "Export failed.");
+        let header = std::fs::read_to_string(&exported).expect("Failed to read export");
+        assert!(header.starts_with("User ID,First Name,Last Name,Current Position,Current Temp Position,PR 2024"), "Header was: {}", header);
+
+        assert!(app.load_mapping_preview(&source).is_ok(), "Preview failed.");
+        let preview = app.mapping_editor.preview.as_ref().expect("Preview should be loaded");
+        assert_eq!(preview.headers.len(), 6);
+        assert_eq!(preview.rows.len(), 1);
+    }
+
+    #[test]
+    fn test_mixed_box_labels_are_normalized_and_exported_in_chosen_scheme() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let source = dir.path().join("labels.csv");
+        std::fs::write(
+            &source,
+            "User ID,First Name,Last Name,Current Position,User 9Box 2024\n\
+             u1,Ann,Lee,Analyst,Top Talent\n\
+             u2,Bob,Ray,Engineer,2b\n\
+             u3,Cat,Fox,Engineer,Master/Mentor\n",
+        )
+        .expect("Failed to write CSV");
+
+        let (mut app, _files) = setup_app();
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+        let _ = app.update(Message::ImportAccepted);
+        let boxes: Vec<Option<&str>> = app.workspace.employees().iter().map(|e| e.nine_box("2024")).collect();
+        assert_eq!(boxes, vec![Some("1A"), Some("2B"), Some("3A")]);
+
+        let _ = app.update(Message::LabelSchemeSelected(LabelScheme::performer().name));
+        let exported = dir.path().join("export.csv");
+        assert!(app.export_csv_to(&exported).is_ok(), "Export failed.");
+        let contents = std::fs::read_to_string(&exported).expect("Failed to read export");
+        assert!(contents.contains(",Star,"), "Export was: {}", contents);
+        assert!(contents.contains(",Core Player,"), "Export was: {}", contents);
+        assert!(contents.contains(",Potential Gem,"), "Export was: {}", contents);
+    }
+
+    #[test]
+    fn test_import_seeds_grid_and_moves_update_current_cycle() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let source = dir.path().join("cycles.csv");
+        std::fs::write(
+            &source,
+            "User ID,First Name,Last Name,Current Position,User 9Box 2024,User 9Box 2025\n\
+             u1,Ann,Lee,Analyst,3C,Top Talent\n\
+             u2,Bob,Ray,Engineer,2B,Growth Potential\n\
+             u3,Cat,Fox,Engineer,1A,\n",
+        )
+        .expect("Failed to write CSV");
+
+        let (mut app, _files) = setup_app();
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+        let _ = app.update(Message::ImportAccepted);
+
+        // The latest cycle seeds the grid; the rest are reported
+        assert_eq!(app.placement_cycle(), Some("2025".to_string()));
+        assert_eq!(app.workspace.grid_state().assignments.get(&grid_position("1A")), Some(&vec!["u1".to_string()]));
+        let report = app.seed_report.clone().expect("Seeding should leave a report");
+        assert_eq!(report.missing, vec!["u3"]);
+        assert_eq!(report.unrecognized.len(), 1);
+        assert_eq!(report.unrecognized[0].value, "Growth Potential");
+
+        // Moving someone rewrites their current-cycle value only
+        let _ = app.update(Message::EmployeeSelected("u3".to_string()));
+        let _ = app.update(Message::BoxClicked(grid_position("2C")));
+        let cat = app.workspace.employees().iter().find(|e| e.user_id == "u3").unwrap();
+        assert_eq!(cat.nine_box("2025"), Some("2C"));
+        assert_eq!(cat.nine_box("2024"), Some("1A"));
+
+        // Re-seeding from an earlier cycle replaces the placements
+        let _ = app.update(Message::PlacementCycleSelected("2024".to_string()));
+        assert_eq!(app.placement_cycle(), Some("2024".to_string()));
+        assert_eq!(app.workspace.grid_state().assignments.get(&grid_position("3C")), Some(&vec!["u1".to_string()]));
+        assert_eq!(app.seed_report.as_ref().map(|report| report.unplaced_count()), Some(0));
+    }
+
+    #[test]
+    fn test_edit_employee_skills_and_catalog() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+
+        let _ = app.update(Message::SkillInputChanged(" Negotiation ".to_string()));
+        let _ = app.update(Message::SkillAdded);
+        let _ = app.update(Message::SkillInputChanged("negotiation".to_string()));
+        let _ = app.update(Message::SkillAdded);
+        assert_eq!(app.workspace.employees()[0].skills, vec!["Negotiation"], "Duplicates must be ignored.");
+        assert!(app.workspace.skills().iter().any(|skill| skill.name == "Negotiation"), "New skills join the catalog.");
+        let _ = app.update(Message::Undo);
+        assert!(app.workspace.employees()[0].skills.is_empty());
+        assert!(app.workspace.skills().iter().all(|skill| skill.name != "Negotiation"), "One undo takes back tag and catalog entry.");
+        let _ = app.update(Message::Redo);
+        assert_eq!(app.workspace.employees()[0].skills, vec!["Negotiation"]);
+        assert!(app.workspace.skills().iter().any(|skill| skill.name == "Negotiation"));
+
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let exported = dir.path().join("export.csv");
+        assert!(app.export_csv_to(&exported).is_ok(), "Export failed.");
+        let contents = std::fs::read_to_string(&exported).expect("Failed to read export");
+        assert!(contents.lines().next().unwrap_or_default().contains(",Skills,"), "Export was: {}", contents);
+        assert!(contents.contains(",Negotiation,"), "Export was: {}", contents);
+
+        let _ = app.update(Message::SkillRemoved(employee_id.clone(), "NEGOTIATION".to_string()));
+        assert!(app.workspace.employees()[0].skills.is_empty());
+        assert!(app.workspace.skills().iter().any(|skill| skill.name == "Negotiation"), "The catalog keeps unused skills.");
+
+        let _ = app.update(Message::SkillInputChanged("Negotiation".to_string()));
+        let _ = app.update(Message::SkillAdded);
+        let _ = app.update(Message::CatalogSkillRemoved("Negotiation".to_string()));
+        assert!(app.workspace.skills().iter().all(|skill| skill.name != "Negotiation"));
+        assert!(app.workspace.employees()[0].skills.is_empty(), "Removing from the catalog untags employees.");
+    }
+
+    #[test]
+    fn test_skill_levels_role_gaps_and_matrix_export() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        let position = app.workspace.employees()[0].current_position.clone();
+        let _ = app.update(Message::SkillLevelChanged(employee_id.clone(), "Rust".to_string(), Some(2)));
+        assert_eq!(app.workspace.employees()[0].skill_level("rust"), Some(2), "Setting a level adds the skill.");
+
+        let _ = app.update(Message::RoleSelected(position.clone()));
+        let _ = app.update(Message::RoleRequirementChanged("Rust".to_string(), Some(4)));
+        let _ = app.update(Message::RoleRequirementChanged("SQL".to_string(), Some(1)));
+        let gaps = employee_skill_gaps(&app.workspace.employees()[0], app.workspace.role_requirements());
+        let summary: Vec<(&str, u8)> = gaps.iter().map(|gap| (gap.skill.as_str(), gap.shortfall())).collect();
+        assert_eq!(summary, vec![("Rust", 2), ("SQL", 1)]);
+
+        let _ = app.update(Message::SkillLevelChanged(employee_id, "Rust".to_string(), Some(4)));
+        let _ = app.update(Message::RoleRequirementChanged("SQL".to_string(), None));
+        assert!(employee_skill_gaps(&app.workspace.employees()[0], app.workspace.role_requirements()).is_empty());
+        assert!(app.to_project_document().role_requirements.iter().any(|role| role.position == position));
+
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let matrix_path = dir.path().join("matrix.csv");
+        assert!(app.export_skills_matrix_to(&matrix_path).is_ok(), "Matrix export failed.");
+        let matrix = std::fs::read_to_string(&matrix_path).expect("Failed to read matrix");
+        assert!(matrix.lines().next().unwrap_or_default().starts_with("User ID,First Name,Last Name,Current Position"));
+        assert!(matrix.lines().nth(1).unwrap_or_default().contains(",4"), "Matrix was: {}", matrix);
+    }
+
+    #[test]
+    fn test_import_builds_succession_plan_and_edits_persist_in_project() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let source = dir.path().join("succession.csv");
+        std::fs::write(
+            &source,
+            "User ID,First Name,Last Name,Current Position,Criticality of Role,Potential Successor, Successor\u{2019}s Readiness,Successor  Development\n\
+             m1,Max,Boss,Head of Sales,High,Ann Lee;u3,Ready Now;1-2 years,Needs P&L exposure\n\
+             u2,Ann,Lee,Sales Lead,,,,\n\
+             u3,Bob,Ray,Rep,,Nobody Known,,\n",
+        )
+        .expect("Failed to write CSV");
+
+        let (mut app, _files) = setup_app();
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+        let _ = app.update(Message::ImportAccepted);
+
+        let role = app.workspace.succession().role("Head of Sales").expect("Critical role should be imported");
+        assert_eq!(role.criticality, Some(Criticality::High));
+        let ids: Vec<&str> = role.successors.iter().map(|s| s.employee_id.as_str()).collect();
+        assert_eq!(ids, vec!["u2", "u3"]);
+        assert_eq!(role.bench_strength().ready_now, 1);
+        assert_eq!(app.succession_issues.len(), 1, "Unknown successors are reported: {:?}", app.succession_issues);
+
+        let position = "Head of Sales".to_string();
+        let _ = app.update(Message::SuccessorRanked(position.clone(), "u3".to_string(), 0));
+        let _ = app.update(Message::SuccessorReadinessSelected(position.clone(), "u3".to_string(), Readiness::ReadyNow));
+        let _ = app.update(Message::SuccessorRemoved(position.clone(), "u2".to_string()));
+        let _ = app.update(Message::SuccessionRoleInputChanged("Sales Lead".to_string()));
+        let _ = app.update(Message::SuccessionRoleAdded);
+        let _ = app.update(Message::SuccessorAdded("Sales Lead".to_string(), "u3".to_string()));
+
+        let project_path = dir.path().join("plan.9box");
+        assert!(app.save_project_to(&project_path).is_ok(), "Saving the project failed.");
+        let (mut reopened, _reopened_files) = setup_app();
+        assert!(reopened.open_project_from(&project_path).is_ok(), "Opening the project failed.");
+        let role = reopened.workspace.succession().role("Head of Sales").expect("Role should survive the round trip");
+        assert_eq!(role.successors.len(), 1);
+        assert_eq!(role.successors[0].readiness, Some(Readiness::ReadyNow));
+        assert_eq!(reopened.workspace.succession().roles_for_successor("u3").len(), 2);
+    }
+
+    #[test]
+    fn test_development_notes_are_saved_once_typing_pauses() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        let position = "Team Lead".to_string();
+        let _ = app.update(Message::SuccessionRoleInputChanged(position.clone()));
+        let _ = app.update(Message::SuccessionRoleAdded);
+        let _ = app.update(Message::SuccessorAdded(position.clone(), employee_id.clone()));
+        let development = |app: &App| {
+            let role = app.workspace.succession().role("Team Lead").expect("Role should exist");
+            role.successors[0].development.clone()
+        };
+
+        for typed in ["M", "Me", "Mentor"] {
+            let _ = app.update(Message::SuccessorDevelopmentChanged(position.clone(), employee_id.clone(), typed.to_string()));
+        }
+        let _ = app.update(Message::Tick(Instant::now()));
+        assert_eq!(development(&app), "", "Saving waits for typing to pause.");
+        let _ = app.update(Message::Tick(Instant::now() + DEVELOPMENT_SAVE_DELAY));
+        assert_eq!(development(&app), "Mentor");
+        assert!(app.development_draft.is_none());
+
+        let _ = app.update(Message::Undo);
+        assert_eq!(development(&app), "", "The typing is undone as one step.");
+        let next_undo = app.workspace.history().next_undo().map(|edit| edit.description.clone());
+        assert_eq!(next_undo.as_deref(), Some("Add successor for Team Lead"));
+
+        let _ = app.update(Message::SuccessorDevelopmentChanged(position.clone(), employee_id.clone(), "Stretch".to_string()));
+        let _ = app.update(Message::SuccessorDevelopmentSubmitted);
+        assert_eq!(development(&app), "Stretch", "Enter saves straight away.");
+    }
+
+    #[test]
+    fn test_risk_matrix_clicks_and_pickers_set_retention_fields() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let _ = app.update(Message::RiskBoxClicked(grid_position("2A")));
+        assert_eq!(app.workspace.employees()[0].impact_of_loss, Some(ImpactOfLoss::High));
+        assert_eq!(app.workspace.employees()[0].attrition_risk, Some(AttritionRisk::Extreme));
+        assert_eq!(app.selected_employee_id, None, "Selection is cleared after placing.");
+
+        let _ = app.update(Message::AttritionRiskSelected(employee_id.clone(), AttritionRisk::Low));
+        assert_eq!(risk_position(&app.workspace.employees()[0]), Some(grid_position("2D")));
+
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let exported = dir.path().join("export.csv");
+        assert!(app.export_csv_to(&exported).is_ok(), "Export failed.");
+        let contents = std::fs::read_to_string(&exported).expect("Failed to read export");
+        assert!(contents.contains(",Low,High,"), "Export was: {}", contents);
+    }
+
+    #[test]
+    fn test_reports_to_filter_lists_everyone_under_manager() {
+        let (mut app, _files) = setup_app();
+        let manager_id = app.workspace.employees()[0].user_id.clone();
+        let report_id = app.workspace.employees()[1].user_id.clone();
+        app.workspace
+            .edit_employee(&report_id, "Set manager", |e| e.manager_id = Some(manager_id.clone()))
+            .expect("Employee should exist");
+        assert_eq!(app.visible_employees().len(), app.workspace.employees().len());
+
+        let _ = app.update(Message::ReportsToFilterSelected(Some(manager_id)));
+        let visible: Vec<&str> = app.visible_employees().iter().map(|e| e.user_id.as_str()).collect();
+        assert_eq!(visible, vec![app.workspace.employees()[1].user_id.as_str()], "Only the manager's reports are listed.");
+
+        let _ = app.update(Message::ReportsToFilterSelected(None));
+        assert_eq!(app.visible_employees().len(), app.workspace.employees().len());
+    }
+
+    #[test]
+    fn test_org_chart_selects_and_survives_reporting_cycles() {
+        let (mut app, _files) = setup_app();
+        let (first_id, second_id) = (app.workspace.employees()[0].user_id.clone(), app.workspace.employees()[1].user_id.clone());
+        for id in [&first_id, &second_id] {
+            app.workspace.unassign(id).expect("Employee should exist");
+        }
+        app.workspace.assign(&first_id, grid_position("1A")).expect("1A is on the 3x3 grid");
+        assert_eq!(app.workspace.grid_state().position_of(&first_id), Some(grid_position("1A")));
+        assert_eq!(app.workspace.grid_state().position_of(&second_id), None);
+
+        app.workspace
+            .edit_employee(&first_id, "Set manager", |e| e.manager_id = Some(second_id.clone()))
+            .expect("Employee should exist");
+        app.workspace
+            .edit_employee(&second_id, "Set manager", |e| e.manager_id = Some(first_id.clone()))
+            .expect("Employee should exist");
+        let _ = app.update(Message::ScreenSelected(Screen::OrgChart));
+        let _ = view_app(&app); // Must not loop on the cycle
+        let _ = app.update(Message::EmployeeSelected(second_id.clone()));
+        assert_eq!(app.selected_employee_id, Some(second_id));
+        assert_eq!(app.screen, Screen::OrgChart, "Selecting a node stays on the org chart.");
+    }
+
+    #[test]
+    fn test_undo_redo_grid_moves_and_edits() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        let cycle = app.placement_cycle().expect("Dummy data has a placement cycle");
+        let original_box = app.workspace.grid_state().position_of(&employee_id);
+        let original_nine_box = app.workspace.employees()[0].nine_box(&cycle).map(str::to_string);
+
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let _ = app.update(Message::BoxClicked(grid_position("3C")));
+        let _ = app.update(Message::ImpactOfLossSelected(employee_id.clone(), ImpactOfLoss::Critical));
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), Some(grid_position("3C")));
+
+        let _ = app.update(Message::Undo);
+        assert_eq!(app.workspace.employees()[0].impact_of_loss, None);
+        let _ = app.update(Message::Undo);
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), original_box, "Undo puts the employee back.");
+        assert_eq!(app.workspace.employees()[0].nine_box(&cycle).map(str::to_string), original_nine_box);
+        assert!(app.workspace.history().next_undo().is_none());
+
+        let _ = app.update(Message::Redo);
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), Some(grid_position("3C")));
+        assert_eq!(app.workspace.employees()[0].nine_box(&cycle), Some("3C"));
+        assert!(app.status_message.as_deref().is_some_and(|status| status.starts_with("Redid: Move")));
+        let _ = app.update(Message::Redo);
+        assert_eq!(app.workspace.employees()[0].impact_of_loss, Some(ImpactOfLoss::Critical));
+    }
+
+    #[test]
+    fn test_workspace_events_reach_the_app_as_messages() {
+        let (mut app, _files) = setup_app();
+        let mut receiver = app.workspace_events.lock().unwrap().take().expect("The receiver is taken by the subscription");
+        let drain = |receiver: &mut UnboundedReceiver<WorkspaceEvent>| {
+            std::iter::from_fn(|| receiver.try_next().ok().flatten()).collect::<Vec<_>>()
+        };
+        let employee_id = app.workspace.employees()[1].user_id.clone();
+
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let _ = app.update(Message::BoxClicked(grid_position("3C")));
+        let events = drain(&mut receiver);
+        assert!(
+            events.iter().any(|event| matches!(event, WorkspaceEvent::EmployeeMoved { employee_id: id, to, .. }
+                if *id == employee_id && *to == Some(grid_position("3C")))),
+            "The move was announced: {:?}",
+            events
+        );
+
+        let _ = app.update(Message::Undo);
+        let events = drain(&mut receiver);
+        assert!(matches!(events.last(), Some(WorkspaceEvent::EditUndone { .. })), "{:?}", events);
+        for event in events {
+            let _ = app.update(Message::WorkspaceChanged(event));
+        }
+        assert!(app.workspace.history().next_redo().is_some_and(|edit| edit.description.ends_with("to 3C")));
+    }
+
+    #[test]
+    fn test_autosave_saves_the_project_once_changes_settle() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let project_path = dir.path().join("team.9box");
+        let (mut app, _files) = setup_app();
+        let mut receiver = app.workspace_events.lock().unwrap().take().expect("Receiver not taken yet");
+        let mut deliver_events = |app: &mut App| {
+            while let Ok(Some(event)) = receiver.try_next() {
+                let _ = app.update(Message::WorkspaceChanged(event));
+            }
+        };
+        app.app_settings.auto_save_enabled = false;
+        assert!(app.save_project_to(&project_path).is_ok(), "Saving the project failed.");
+        assert_eq!(app.title(), "Box Planner - team.9box (saved)");
+        let employee_id = app.workspace.employees()[1].user_id.clone();
+
+        // Autosave off: the change waits for an explicit save
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let _ = app.update(Message::BoxClicked(grid_position("3C")));
+        deliver_events(&mut app);
+        assert!(app.unsaved_changes);
+        assert_eq!(app.title(), "Box Planner - team.9box (unsaved changes)");
+        app.autosave(Instant::now() + AUTOSAVE_DELAY);
+        assert!(app.unsaved_changes, "Nothing is autosaved while autosave is off.");
+
+        // Autosave on: saved once the delay has passed since the last change
+        app.app_settings.auto_save_enabled = true;
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let _ = app.update(Message::BoxClicked(grid_position("1C")));
+        deliver_events(&mut app);
+        let _ = app.update(Message::Tick(Instant::now()));
+        assert!(app.unsaved_changes, "Autosave waits for changes to settle.");
+        let _ = app.update(Message::Tick(Instant::now() + AUTOSAVE_DELAY));
+        assert!(!app.unsaved_changes);
+        assert_eq!(app.title(), "Box Planner - team.9box (saved)");
+
+        let saved = load_project(&project_path).expect("Failed to load the autosaved project");
+        let (reopened, _) = Workspace::from_project(saved);
+        assert_eq!(reopened.grid_state().position_of(&employee_id), Some(grid_position("1C")));
+    }
+
+    #[test]
+    fn test_autosave_without_a_project_file_leaves_only_unkept_changes_unsaved() {
+        let (mut app, _files) = setup_app();
+        let mut receiver = app.workspace_events.lock().unwrap().take().expect("Receiver not taken yet");
+        let mut deliver_events = |app: &mut App| {
+            while let Ok(Some(event)) = receiver.try_next() {
+                let _ = app.update(Message::WorkspaceChanged(event));
+            }
+        };
+        app.app_settings.auto_save_enabled = true;
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+
+        app.workspace.set_note(&employee_id, Some("Ready for a stretch role".to_string())).expect("Employee should exist");
+        deliver_events(&mut app);
+        assert!(!app.unsaved_changes, "The note is in the notes store, so nothing is left to save.");
+        assert_eq!(app.title(), "Box Planner");
+
+        app.workspace
+            .edit_employee(&employee_id, "Rename", |employee| employee.first_name = "Renamed".to_string())
+            .expect("Employee should exist");
+        deliver_events(&mut app);
+        assert!(app.unsaved_changes, "Employee edits are only kept in a project file.");
+        assert_eq!(app.title(), "Box Planner (unsaved changes, no project file yet)");
+    }
+
+    #[test]
+    fn test_drag_and_drop_moves_cards_between_boxes_and_off_the_grid() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[1].user_id.clone();
+        let target = DropTarget::GridBox(grid_position("3C"));
+
+        let _ = app.update(Message::DragStarted(employee_id.clone()));
+        let _ = app.update(Message::DragEntered(DropTarget::GridBox(grid_position("2C"))));
+        let _ = app.update(Message::DragEntered(target));
+        let _ = app.update(Message::DragExited(DropTarget::GridBox(grid_position("2C"))));
+        assert_eq!(app.drag.as_ref().and_then(|drag| drag.hovered), Some(target), "Leaving a neighbour keeps the new target highlighted.");
+
+        let _ = app.update(Message::EmployeeDropped(employee_id.clone(), target));
+        let _ = app.update(Message::DragEnded);
+        assert!(app.drag.is_none());
+        assert_eq!(app.last_drop, Some(target));
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), Some(grid_position("3C")));
+        assert!(app.status_message.as_deref().is_some_and(|status| status.starts_with("Moved Jane (Dummy) Smith to")));
+
+        // Released away from any target: the drag is cancelled and nothing moves
+        let _ = app.update(Message::DragStarted(employee_id.clone()));
+        let _ = app.update(Message::DragEnded);
+        assert!(app.drag.is_none());
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), Some(grid_position("3C")));
+
+        let _ = app.update(Message::DragStarted(employee_id.clone()));
+        let _ = app.update(Message::EmployeeDropped(employee_id.clone(), DropTarget::Unplaced));
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), None);
+        let _ = app.update(Message::Undo);
+        assert_eq!(app.workspace.grid_state().position_of(&employee_id), Some(grid_position("3C")), "Drops are undoable.");
+    }
+
+    #[test]
+    fn test_card_department_color_and_note_marker() {
+        let (mut app, _files) = setup_app();
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        app.workspace
+            .edit_employee(&employee_id, "Set department", |employee| {
+                employee.department = Some("Engineering".to_string());
+                employee.notes = None;
+            })
+            .expect("Employee should exist");
+        app.workspace.set_note(&employee_id, None).expect("Employee should exist");
+        let employee = app.workspace.employee(&employee_id).unwrap().clone();
+        assert_eq!(app.department_color(&employee), None);
+        assert!(!app.has_note(&employee));
+
+        app.app_settings.department_colors.insert("Engineering".to_string(), "#FF0000".to_string());
+        assert_eq!(app.department_color(&employee), Some("#FF0000"));
+
+        app.workspace.set_note(&employee_id, Some("Ready for a stretch role".to_string())).expect("Employee should exist");
+        assert!(app.has_note(&employee), "A note file marks the card.");
+    }
+
+    #[test]
+    fn test_note_editor_saves_after_typing_pauses() {
+        let (mut app, _files) = setup_app();
+        app.app_settings.auto_save_enabled = false; // Notes are stored whether or not autosave is on
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        app.workspace.set_note(&employee_id, Some("Mentor".to_string())).expect("Employee should exist");
+
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        assert_eq!(app.note_editor.as_ref().map(NoteEditor::text).as_deref(), Some("Mentor"), "The editor opens on the stored note.");
+
+        let _ = app.update(Message::NoteEdited(text_editor::Action::Move(text_editor::Motion::DocumentEnd)));
+        for c in " juniors".chars() {
+            let _ = app.update(Message::NoteEdited(text_editor::Action::Edit(text_editor::Edit::Insert(c))));
+        }
+        assert_eq!(app.note_editor.as_ref().and_then(|editor| editor.status.clone()), Some(NoteStatus::Editing));
+        let _ = app.update(Message::Tick(Instant::now()));
+        assert_eq!(app.workspace.notes().get(&employee_id).map(String::as_str), Some("Mentor"), "Saving waits for typing to pause.");
+
+        let _ = app.update(Message::Tick(Instant::now() + NOTE_SAVE_DELAY));
+        assert_eq!(app.workspace.notes().get(&employee_id).map(String::as_str), Some("Mentor juniors"));
+        assert_eq!(app.note_editor.as_ref().and_then(|editor| editor.status.clone()), Some(NoteStatus::Saved));
+        assert_eq!(
+            load_note_history(&app.paths.notes_dir, &employee_id).unwrap().current_text(),
+            "Mentor juniors",
+            "The debounced save writes the notes store."
+        );
+
+        // Undo restores the note in the open editor
+        let mut receiver = app.workspace_events.lock().unwrap().take().expect("Receiver not taken yet");
+        let _ = app.update(Message::Undo);
+        while let Ok(Some(event)) = receiver.try_next() {
+            let _ = app.update(Message::WorkspaceChanged(event));
+        }
+        assert_eq!(app.note_editor.as_ref().map(NoteEditor::text).as_deref(), Some("Mentor"));
+
+        // A draft is saved before another employee's note is opened
+        let _ = app.update(Message::NoteEdited(text_editor::Action::Edit(text_editor::Edit::Insert('!'))));
+        let other_id = app.workspace.employees()[1].user_id.clone();
+        let _ = app.update(Message::EmployeeSelected(other_id.clone()));
+        assert!(app.workspace.notes().get(&employee_id).is_some_and(|note| note.contains('!')));
+        assert_eq!(app.note_editor.as_ref().map(|editor| editor.employee_id.as_str()), Some(other_id.as_str()));
+    }
+
+    #[test]
+    fn test_note_versions_can_be_restored_with_their_category() {
+        let (mut app, _files) = setup_app();
+        app.app_settings.auto_save_enabled = false;
+        app.app_settings.note_author = " Dana ".to_string();
+        assert_eq!(app.note_author(), "Dana");
+        let employee_id = app.workspace.employees()[0].user_id.clone();
+        app.workspace.set_note(&employee_id, Some("Second take".to_string())).expect("Employee should exist");
+        append_employee_note(&app.paths.notes_dir, &employee_id, NoteEntry::new("First take", "Ann", None)).unwrap();
+        let entry = NoteEntry::new("Second take", "Dana", Some(NoteCategory::Development));
+        append_employee_note(&app.paths.notes_dir, &employee_id, entry).unwrap();
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        assert_eq!(app.note_editor.as_ref().map(|editor| editor.history.entries.len()), Some(2));
+
+        let _ = app.update(Message::NoteVersionRestored(0));
+        assert_eq!(app.workspace.notes().get(&employee_id).map(String::as_str), Some("First take"));
+        let editor = app.note_editor.as_ref().expect("Editor stays open");
+        assert_eq!(editor.text(), "First take");
+        assert_eq!(editor.category, None, "The restored version's category comes with it.");
+        assert_eq!(editor.status, Some(NoteStatus::Saved));
+        assert_eq!(editor.history.entries.len(), 3, "The restore is recorded as one new version.");
+
+        // A category change alone is recorded as a version too
+        let _ = app.update(Message::NoteCategorySelected(Some(NoteCategory::CalibrationComment)));
+        let history = load_note_history(&app.paths.notes_dir, &employee_id).unwrap();
+        assert_eq!(history.entries.len(), 4);
+        assert_eq!(history.current().and_then(|entry| entry.category), Some(NoteCategory::CalibrationComment));
+
+        // Restoring is one undoable edit
+        let _ = app.update(Message::Undo);
+        assert_eq!(app.workspace.notes().get(&employee_id).map(String::as_str), Some("Second take"));
+    }
+
+    #[test]
+    fn test_note_editor_shows_why_a_note_cannot_be_stored() {
+        let (mut app, _files) = setup_app();
+        let employee_id = "E".repeat(100);
+        let mut employees = app.workspace.employees().to_vec();
+        employees.push(Employee { user_id: employee_id.clone(), ..Employee::default() });
+        app.workspace.import_employees(employees);
+
+        let _ = app.update(Message::EmployeeSelected(employee_id.clone()));
+        let status = app.note_editor.as_ref().and_then(|editor| editor.status.clone());
+        assert!(matches!(&status, Some(NoteStatus::Failed(e)) if e.contains("too long")), "Unexpected status: {:?}", status);
+
+        let _ = app.update(Message::NoteEdited(text_editor::Action::Edit(text_editor::Edit::Insert('x'))));
+        let _ = app.update(Message::Tick(Instant::now() + NOTE_SAVE_DELAY));
+        let status = app.note_editor.as_ref().and_then(|editor| editor.status.clone());
+        assert!(matches!(status, Some(NoteStatus::Failed(_))), "A failed save is shown too: {:?}", status);
+    }
+
+    #[test]
+    fn test_import_with_problems_waits_for_review() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let source = dir.path().join("broken.csv");
+        std::fs::write(
+            &source,
+            "User ID,First Name,Last Name,Current Position,PR2024\nu1,Ann,Lee,Analyst,4.2\nu2,Bob,Ray,Engineer,bad\n",
+        )
+        .expect("Failed to write CSV");
+
+        let (mut app, _files) = setup_app();
+        let original_employees = app.workspace.employees().to_vec();
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+
+        let pending = app.pending_import.as_ref().expect("Import should wait for review");
+        assert_eq!(pending.outcome.employees.len(), 1);
+        assert_eq!(pending.outcome.report.rejected_rows.len(), 1);
+        assert_eq!(app.workspace.employees(), original_employees, "Employees must not change before the import is accepted.");
+
+        let _ = app.update(Message::ImportDiscarded);
+        assert!(app.pending_import.is_none());
+        assert_eq!(app.workspace.employees(), original_employees);
+
+        assert!(app.stage_csv_import(&source).is_ok(), "Staging the import failed.");
+        let _ = app.update(Message::ImportAccepted);
+        assert_eq!(app.workspace.employees().len(), 1);
+        assert_eq!(app.workspace.employees()[0].user_id, "u1");
     }
 }
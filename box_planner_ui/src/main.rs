@@ -9,8 +9,11 @@ use iced::Application; // Required for App::run
 pub fn main() -> iced::Result {
     println!("Box Planner UI starting...");
     App::run(iced::Settings {
-        // Default settings are fine for now
-        // You can customize window size, resizability, etc. here
+        // Closing is handled by the app so it can offer to save unsaved changes
+        window: iced::window::Settings {
+            exit_on_close_request: false,
+            ..iced::window::Settings::default()
+        },
         ..iced::Settings::default()
     })
 }
\ No newline at end of file
@@ -1,7 +1,79 @@
+use crate::app::{DropTarget, Screen};
+use box_planner_core::csv_processing::{CycleField, EmployeeField};
+use box_planner_core::events::WorkspaceEvent;
+use box_planner_core::grid::GridPosition;
+use box_planner_core::notes::NoteCategory;
+use box_planner_core::retention::{AttritionRisk, ImpactOfLoss};
+use box_planner_core::succession::{Criticality, Readiness};
+use iced::widget::text_editor;
+use iced::window;
+use std::time::Instant;
+
 #[derive(Debug, Clone)]
 pub enum Message {
     EmployeeSelected(String), // Parameter is employee_id
-    BoxClicked(String),       // Parameter is box_id (e.g., "1A", "2B")
+    ReportsToFilterSelected(Option<String>), // Manager ID, or None to list everyone
+    BoxClicked(GridPosition), // Box that was clicked (e.g., "1A", "2B")
+    DragStarted(String), // Employee whose card was picked up
+    DragEntered(DropTarget),
+    DragExited(DropTarget),
+    EmployeeDropped(String, DropTarget), // Employee ID and where their card was released
+    DragEnded, // The drag was released, on a target or not
     ScaleChanged(f32),
+    Undo, // Revert the last grid move or employee edit
+    Redo,
+    WorkspaceChanged(WorkspaceEvent), // Emitted by the workspace after every change
+    AutoSaveToggled(bool),
+    Tick(Instant), // Runs debounced saves (the note being edited, autosave) that are due
+    NoteEdited(text_editor::Action), // Typing, clicks and scrolling in the note editor
+    NoteCategorySelected(Option<NoteCategory>), // Category for the open note's next version
+    NoteVersionRestored(usize), // Index into the open note's history
+    NoteAuthorChanged(String), // Name recorded on note versions
+    CloseRequested(window::Id), // The window's close button; may prompt about unsaved changes
+    RiskBoxClicked(GridPosition),                 // Box on the risk matrix; sets the selected employee's impact and risk
+    ImpactOfLossSelected(String, ImpactOfLoss),   // employee_id, impact
+    AttritionRiskSelected(String, AttritionRisk), // employee_id, risk
+    OpenProject,
+    SaveProject,
+    SaveProjectAs,
+    GridTemplateSelected(String), // Name of a built-in grid template
+    LoadGridTemplate,             // Pick a grid template JSON file
+    LabelSchemeSelected(String),  // Name of a built-in box label scheme
+    PlacementCycleSelected(String), // Re-seed the grid from this cycle's 9-box column
+    ScreenSelected(Screen),
+    // Skills
+    SkillInputChanged(String),
+    SkillAdded,                   // Tag the selected employee with the typed skill
+    SkillRemoved(String, String), // Parameters are employee_id and skill name
+    CatalogSkillAdded,            // Add the typed skill to the catalog
+    CatalogSkillRemoved(String),  // Remove from the catalog and from every employee
+    SkillLevelChanged(String, String, Option<u8>), // employee_id, skill name, proficiency (None clears it)
+    RoleSelected(String),                          // Position whose requirements are being edited
+    RoleRequirementChanged(String, Option<u8>),    // Skill name and required level for the selected role
+    ExportSkillsMatrix,
+    // Succession planning; the first String is always the critical role's position
+    SuccessionRoleInputChanged(String),
+    SuccessionRoleAdded,
+    SuccessionRoleRemoved(String),
+    CriticalitySelected(String, Criticality),
+    SuccessorAdded(String, String),                      // position, employee_id
+    SuccessorRemoved(String, String),                    // position, employee_id
+    SuccessorRanked(String, String, usize),              // position, employee_id, new rank (0 = first)
+    SuccessorReadinessSelected(String, String, Readiness),
+    SuccessorDevelopmentChanged(String, String, String), // position, employee_id, development notes
+    SuccessorDevelopmentSubmitted, // Save the typed development notes now
+    ImportCsv,
+    ImportAccepted,  // Load the good rows of the reviewed import
+    ImportDiscarded,
+    ExportCsv,
+    // Mapping editor
+    MappingProfileSelected(String), // Parameter is the profile name
+    MappingProfileNameChanged(String),
+    MappingFieldHeaderChanged(EmployeeField, String),
+    MappingFieldAliasesChanged(EmployeeField, String), // Comma-separated aliases
+    MappingCycleHeaderChanged(CycleField, String),
+    MappingCycleAliasesChanged(CycleField, String), // Comma-separated aliases
+    MappingPreviewRequested,
+    MappingProfileSaved,
     // Add other messages as UI interactions are defined
 }
\ No newline at end of file
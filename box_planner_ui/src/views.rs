@@ -1,16 +1,170 @@
-use crate::app::App;
+use crate::app::{App, DropTarget, NoteEditor, NoteStatus, PendingImport, Screen};
 use crate::messages::Message;
+use crate::widgets::{
+    drop_target_box, parse_hex_color, tinted_box, Draggable, DropZone, EmployeeCard, DROP_HIGHLIGHT,
+};
+use box_planner_core::csv_processing::{CycleField, EmployeeField};
+use box_planner_core::grid::{nine_box_cycles, GridPosition, GridTemplate};
+use box_planner_core::labels::LabelScheme;
+use box_planner_core::org::OrgTree;
+use box_planner_core::models::{Employee, GridState, MAX_PROFICIENCY, MIN_PROFICIENCY};
+use box_planner_core::notes::NoteCategory;
+use box_planner_core::retention::{risk_matrix, risk_matrix_template, AttritionRisk, ImpactOfLoss};
+use box_planner_core::succession::{Criticality, CriticalRole, Readiness};
+use box_planner_core::skills::{
+    employee_skill_gaps, employees_with_skill, find_role_requirement, proficiency_label, team_skill_gaps,
+};
 // Added Rule to the import list, removed redundant Button, Column, Container, Row, Text which are covered by explicit imports later or not used.
 // The explicit individual imports like `Button, Column, Container, Row, Text` are fine,
 // but `rule` (the module) was being imported, not `Rule` (the struct).
 // Removed unused lowercase 'button' and 'text' module aliases.
-use iced::widget::{column, container, row, scrollable, Button, Column, Container, Row, Rule, Text}; 
+use iced::widget::{
+    checkbox, column, container, pick_list, row, scrollable, text_editor, text_input, Button, Column, Container, Row,
+    Rule, Text,
+};
 use iced::{Element, Length};
+use std::collections::HashSet;
+use std::fmt;
+
+// A proficiency choice in a pick list; `None` reads as "not set"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LevelChoice(Option<u8>);
+
+impl LevelChoice {
+    fn all() -> Vec<LevelChoice> {
+        std::iter::once(LevelChoice(None))
+            .chain((MIN_PROFICIENCY..=MAX_PROFICIENCY).map(|level| LevelChoice(Some(level))))
+            .collect()
+    }
+}
+
+// An employee in a pick list, shown by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmployeeChoice {
+    id: String,
+    name: String,
+}
+
+impl EmployeeChoice {
+    fn new(employee: &Employee) -> Self {
+        EmployeeChoice {
+            id: employee.user_id.clone(),
+            name: format!("{} {}", employee.first_name, employee.last_name),
+        }
+    }
+}
+
+impl fmt::Display for EmployeeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+// A note category in a pick list; `None` reads as "No category"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CategoryChoice(Option<NoteCategory>);
+
+impl CategoryChoice {
+    fn all() -> Vec<CategoryChoice> {
+        std::iter::once(CategoryChoice(None))
+            .chain(NoteCategory::ALL.into_iter().map(|category| CategoryChoice(Some(category))))
+            .collect()
+    }
+}
+
+impl fmt::Display for CategoryChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(category) => write!(f, "{}", category),
+            None => write!(f, "No category"),
+        }
+    }
+}
+
+impl fmt::Display for LevelChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(level) => write!(f, "{} - {}", level, proficiency_label(level)),
+            None => write!(f, "Not set"),
+        }
+    }
+}
+
+pub fn view_app(app: &App) -> Element<'_, Message> {
+    let toolbar = view_toolbar(app);
+    // A pending import report takes over the screen until it is accepted or discarded
+    let screen = match (&app.pending_import, app.screen) {
+        (Some(pending), _) => view_import_report(pending),
+        (None, Screen::Planner) => view_planner(app, view_9box_grid(app)),
+        (None, Screen::RiskMatrix) => view_planner(app, view_risk_matrix(app)),
+        (None, Screen::OrgChart) => view_planner(app, view_org_chart(app)),
+        (None, Screen::Skills) => view_skills(app),
+        (None, Screen::Succession) => view_succession(app),
+        (None, Screen::MappingEditor) => view_mapping_editor(app),
+    };
+
+    // Wrap content in a container for the main window
+    container(column![toolbar, screen].spacing(10))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into()
+}
+
+// Employee list, a grid (9-box or risk matrix) and the details panel
+fn view_planner<'a>(app: &'a App, grid: Element<'a, Message>) -> Element<'a, Message> {
+    let org = OrgTree::build(app.workspace.employees());
+    let employee_name = |id: &str| {
+        app.workspace.employees()
+            .iter()
+            .find(|e| e.user_id == id)
+            .map_or(format!("(ID: {})", id), |e| format!("{} {}", e.first_name, e.last_name))
+    };
+
+    // Reports-to filter: everyone, or every direct and indirect report of one manager
+    let everyone = EmployeeChoice {
+        id: String::new(),
+        name: "Everyone".to_string(),
+    };
+    let mut manager_choices: Vec<EmployeeChoice> = app
+        .workspace
+        .employees()
+        .iter()
+        .filter(|e| org.span_of_control(&e.user_id) > 0)
+        .map(EmployeeChoice::new)
+        .collect();
+    manager_choices.sort_by(|a, b| a.name.cmp(&b.name));
+    let selected_manager = match &app.reports_to_filter {
+        Some(manager_id) => manager_choices.iter().find(|choice| choice.id == *manager_id).cloned(),
+        None => Some(everyone.clone()),
+    };
+    manager_choices.insert(0, everyone);
+    let reports_to_picker = row![
+        Text::new("Reports to:").size(12),
+        pick_list(manager_choices, selected_manager, |choice: EmployeeChoice| {
+            Message::ReportsToFilterSelected((!choice.id.is_empty()).then_some(choice.id))
+        })
+        .text_size(12),
+    ]
+    .spacing(5)
+    .align_items(iced::Alignment::Center);
+
+    // Hierarchy problems that make the filter and reporting lines unreliable
+    let mut org_issues = Column::new().spacing(2);
+    for cycle in org.cycles() {
+        let names: Vec<String> = cycle.iter().map(|id| employee_name(id)).collect();
+        org_issues = org_issues.push(Text::new(format!("Reporting cycle: {}", names.join(" -> "))).size(11));
+    }
+    if !org.dangling_managers().is_empty() {
+        org_issues = org_issues.push(
+            Text::new(format!("{} employee(s) report to an unknown manager.", org.dangling_managers().len())).size(11),
+        );
+    }
 
-pub fn view_app(app: &App) -> Element<Message> {
     // Employee List display
     let mut employee_list_content = Column::new().spacing(5);
-    for employee in &app.employees {
+    for employee in app.visible_employees() {
         let mut full_name = format!("{} {}", employee.first_name, employee.last_name);
         if Some(employee.user_id.clone()) == app.selected_employee_id {
             full_name = format!("> {}", full_name); // Add prefix for selected employee
@@ -18,33 +172,52 @@ pub fn view_app(app: &App) -> Element<Message> {
         let button = Button::new(Text::new(full_name.clone()))
             .on_press(Message::EmployeeSelected(employee.user_id.clone()))
             .width(Length::Fill);
+        let card = Draggable::new(button, Message::DragStarted(employee.user_id.clone())).on_release(Message::DragEnded);
+
+        employee_list_content = employee_list_content.push(card);
+    }
+    let unplaced_count = app
+        .workspace
+        .employees()
+        .iter()
+        .filter(|e| app.workspace.grid_state().position_of(&e.user_id).is_none())
+        .count();
 
-        employee_list_content = employee_list_content.push(button);
+    let mut employee_list = Container::new(
+        scrollable(
+            column![
+                Text::new("Employee List").size(20),
+                Text::new(format!("{} not on the grid. Drop a card here to take it off.", unplaced_count)).size(11),
+                reports_to_picker,
+                org_issues,
+                employee_list_content // Display the list of buttons
+            ]
+            .spacing(10)
+        )
+    )
+    .width(Length::FillPortion(1)) // Takes 1/4 of the space
+    .height(Length::Fill)
+    .padding(10);
+    if app.drag.as_ref().is_some_and(|drag| drag.hovered == Some(DropTarget::Unplaced)) {
+        employee_list = employee_list.style(drop_target_box(DROP_HIGHLIGHT));
     }
+    // Only the 9-box has an unplaced list; the risk matrix places everyone with impact and risk set
+    let employee_list: Element<'a, Message> = if app.screen == Screen::Planner {
+        drop_zone(app, employee_list, DropTarget::Unplaced).into()
+    } else {
+        employee_list.into()
+    };
 
     // Main content: A row with three columns
     let content = row![
         // Employee List Section
-        Container::new(
-            scrollable(
-                column![
-                    Text::new("Employee List").size(20),
-                    employee_list_content // Display the list of buttons
-                ]
-                .spacing(10)
-            )
-        )
-        .width(Length::FillPortion(1)) // Takes 1/4 of the space
-        .height(Length::Fill)
-        .padding(10),
+        employee_list,
 
         // Vertical Separator
         Rule::vertical(10), // Corrected call
 
-        // 9-Box Grid Section
-        Container::new(
-            view_9box_grid(app) // Call the new function to render the grid
-        )
+        // Grid Section
+        Container::new(grid)
         .width(Length::FillPortion(2)) // Takes 2/4 of the space
         .height(Length::Fill)
         .center_x()
@@ -57,13 +230,111 @@ pub fn view_app(app: &App) -> Element<Message> {
         // Details Panel Section
         Container::new({
             let details_content = if let Some(selected_id) = &app.selected_employee_id {
-                if let Some(employee) = app.employees.iter().find(|e| e.user_id == *selected_id) {
+                if let Some(employee) = app.workspace.employees().iter().find(|e| e.user_id == *selected_id) {
                     let name = format!("{} {}", employee.first_name, employee.last_name);
-                    let pr_2024_str = employee.pr_2024.map_or("N/A".to_string(), |p| p.to_string());
-                    let box_2024_str = employee.user_9box_2024.clone().unwrap_or_else(|| "N/A".to_string());
                     let temp_pos_str = employee.current_temp_position.clone().unwrap_or_else(|| "N/A".to_string());
-                    let notes_str = employee.notes.clone().unwrap_or_else(|| "".to_string());
+                    let group_str = employee
+                        .latest_group()
+                        .map_or("N/A".to_string(), |(cycle, group)| format!("{} ({})", group, cycle));
+                    let manager_str = match (org.manager_of(&employee.user_id), &employee.manager_id) {
+                        (Some(manager_id), _) => employee_name(manager_id),
+                        (None, Some(manager_id)) if !manager_id.trim().is_empty() => format!("{} (unknown)", manager_id),
+                        _ => "N/A".to_string(),
+                    };
+                    let layer_str = org.layer(&employee.user_id).map_or("N/A (reporting cycle)".to_string(), |layer| layer.to_string());
+
+                    // One line per review cycle, most recent first
+                    let mut history_column = Column::new().spacing(2);
+                    for (cycle, record) in employee.performance_history.iter().rev() {
+                        let rating_str = record.rating.map_or("N/A".to_string(), |r| r.to_string());
+                        let box_str = record.nine_box.clone().unwrap_or_else(|| "N/A".to_string());
+                        history_column = history_column.push(
+                            Text::new(format!("{}: PR {} | 9-Box {}", cycle, rating_str, box_str)).size(12),
+                        );
+                    }
+                    if employee.performance_history.is_empty() {
+                        history_column = history_column.push(Text::new("No review history.").size(12));
+                    }
+
+                    // Extra columns carried over from the source CSV, in file order
+                    let mut custom_column = Column::new().spacing(2);
+                    for field in &employee.custom_fields {
+                        let value_str = if field.value.is_empty() { "N/A" } else { field.value.as_str() };
+                        custom_column = custom_column.push(Text::new(format!("{}: {}", field.header, value_str)).size(12));
+                    }
 
+                    // One line per skill with its proficiency and a remove button
+                    let mut skills_column = Column::new().spacing(3);
+                    for skill in &employee.skills {
+                        let (employee_id, skill_name) = (employee.user_id.clone(), skill.clone());
+                        skills_column = skills_column.push(
+                            row![
+                                Text::new(skill.clone()).size(12).width(Length::Fill),
+                                pick_list(
+                                    LevelChoice::all(),
+                                    Some(LevelChoice(employee.skill_level(skill))),
+                                    move |choice: LevelChoice| {
+                                        Message::SkillLevelChanged(employee_id.clone(), skill_name.clone(), choice.0)
+                                    }
+                                )
+                                .text_size(12),
+                                Button::new(Text::new("x").size(12))
+                                    .on_press(Message::SkillRemoved(employee.user_id.clone(), skill.clone())),
+                            ]
+                            .spacing(5)
+                            .align_items(iced::Alignment::Center),
+                        );
+                    }
+                    if employee.skills.is_empty() {
+                        skills_column = skills_column.push(Text::new("No skills recorded.").size(12));
+                    }
+
+                    // What the employee's role asks for that they do not have yet
+                    let mut gaps_column = Column::new().spacing(2);
+                    if find_role_requirement(app.workspace.role_requirements(), &employee.current_position).is_none() {
+                        gaps_column = gaps_column.push(
+                            Text::new(format!("No requirements defined for {}.", employee.current_position)).size(12),
+                        );
+                    } else {
+                        let gaps = employee_skill_gaps(employee, app.workspace.role_requirements());
+                        for gap in &gaps {
+                            let has = gap.actual.map_or("missing".to_string(), |level| format!("has {}", level));
+                            gaps_column = gaps_column.push(
+                                Text::new(format!("{}: needs {}, {}", gap.skill, gap.required, has)).size(12),
+                            );
+                        }
+                        if gaps.is_empty() {
+                            gaps_column = gaps_column.push(Text::new("Meets all role requirements.").size(12));
+                        }
+                    }
+                    // Critical roles this employee is lined up for
+                    let mut succession_column = Column::new().spacing(2);
+                    for (role, rank) in app.workspace.succession().roles_for_successor(&employee.user_id) {
+                        let readiness = role.successors[rank].readiness.map_or("not assessed", Readiness::label);
+                        succession_column = succession_column.push(
+                            Text::new(format!("#{} for {} ({})", rank + 1, role.position, readiness)).size(12),
+                        );
+                    }
+
+                    let mut team_gaps_column = Column::new().spacing(2);
+                    for gap in team_skill_gaps(app.team_of(employee), app.workspace.role_requirements()) {
+                        team_gaps_column = team_gaps_column.push(
+                            Text::new(format!(
+                                "{}: {} member(s) short, {} level(s) in total",
+                                gap.skill,
+                                gap.employees_short.len(),
+                                gap.total_shortfall
+                            ))
+                            .size(12),
+                        );
+                    }
+                    let add_skill_row = row![
+                        text_input("Add skill", &app.skill_input)
+                            .on_input(Message::SkillInputChanged)
+                            .on_submit(Message::SkillAdded),
+                        Button::new(Text::new("Add")).on_press(Message::SkillAdded),
+                    ]
+                    .spacing(5);
 
                     column![
                         Text::new("Employee Details").size(20),
@@ -72,11 +343,51 @@ pub fn view_app(app: &App) -> Element<Message> {
                         Text::new(format!("ID: {}", employee.user_id)),
                         Text::new(format!("Position: {}", employee.current_position)),
                         Text::new(format!("Temporary Position: {}", temp_pos_str)),
-                        Text::new(format!("PR Group 2025: {}", employee.pr_group_2025)),
-                        Text::new(format!("PR 2024: {}", pr_2024_str)),
-                        Text::new(format!("9-Box 2024: {}", box_2024_str)),
-                        Text::new("Notes:"),
-                        scrollable(Text::new(notes_str).width(Length::Fill)) // Make notes scrollable if long
+                        Text::new(format!("PR Group: {}", group_str)),
+                        Text::new(format!("Reports To: {}", manager_str)),
+                        Text::new(format!(
+                            "Direct Reports: {} | All Reports: {} | Layer: {}",
+                            org.span_of_control(&employee.user_id),
+                            org.all_reports(&employee.user_id).len(),
+                            layer_str
+                        ))
+                        .size(12),
+                        row![
+                            Text::new("Impact of Loss:").size(12),
+                            pick_list(ImpactOfLoss::ALL.to_vec(), employee.impact_of_loss, {
+                                let employee_id = employee.user_id.clone();
+                                move |impact| Message::ImpactOfLossSelected(employee_id.clone(), impact)
+                            })
+                            .placeholder("Not set")
+                            .text_size(12),
+                        ]
+                        .spacing(5)
+                        .align_items(iced::Alignment::Center),
+                        row![
+                            Text::new("Attrition Risk:").size(12),
+                            pick_list(AttritionRisk::ALL.to_vec(), employee.attrition_risk, {
+                                let employee_id = employee.user_id.clone();
+                                move |risk| Message::AttritionRiskSelected(employee_id.clone(), risk)
+                            })
+                            .placeholder("Not set")
+                            .text_size(12),
+                        ]
+                        .spacing(5)
+                        .align_items(iced::Alignment::Center),
+                        Text::new("Performance History:"),
+                        history_column,
+                        Text::new("Skills:"),
+                        skills_column,
+                        add_skill_row,
+                        Text::new(format!("Role Gaps ({}):", employee.current_position)),
+                        gaps_column,
+                        Text::new("Team Gaps:"),
+                        team_gaps_column,
+                        Text::new("Successor For:"),
+                        succession_column,
+                        Text::new("Other Fields:"),
+                        custom_column,
+                        view_note_editor(app, &employee.user_id),
                     ]
                     .spacing(5)
                     .padding(5)
@@ -105,82 +416,803 @@ pub fn view_app(app: &App) -> Element<Message> {
     .spacing(10) // Spacing between the main sections and rules
     .align_items(iced::Alignment::Start); // Align items to the top
 
-    // Wrap content in a container for the main window
-    container(content)
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .center_x()
-        .center_y()
+    content.into()
+}
+
+// Project file actions and the latest status message
+fn view_toolbar(app: &App) -> Element<'_, Message> {
+    let status = app.status_message.clone().unwrap_or_default();
+    row![
+        Button::new(Text::new("Open...")).on_press(Message::OpenProject),
+        Button::new(Text::new("Save")).on_press(Message::SaveProject),
+        Button::new(Text::new("Save As...")).on_press(Message::SaveProjectAs),
+        Button::new(Text::new("Import CSV...")).on_press(Message::ImportCsv),
+        Button::new(Text::new("Export CSV...")).on_press(Message::ExportCsv),
+        checkbox("Autosave", app.app_settings.auto_save_enabled).on_toggle(Message::AutoSaveToggled),
+        history_button("Undo", app.workspace.history().next_undo().map(|edit| edit.description.as_str()), Message::Undo),
+        history_button("Redo", app.workspace.history().next_redo().map(|edit| edit.description.as_str()), Message::Redo),
+        screen_button(app, "Grid", Screen::Planner),
+        screen_button(app, "Risk Matrix", Screen::RiskMatrix),
+        screen_button(app, "Org Chart", Screen::OrgChart),
+        screen_button(app, "Skills", Screen::Skills),
+        screen_button(app, "Succession", Screen::Succession),
+        screen_button(app, "Column Mapping", Screen::MappingEditor),
+        Text::new(status).size(12),
+    ]
+    .spacing(10)
+    .padding(5)
+    .align_items(iced::Alignment::Center)
+    .into()
+}
+
+// The employee's note, editable in place; saved once typing pauses
+fn view_note_editor<'a>(app: &'a App, employee_id: &str) -> Element<'a, Message> {
+    let Some(editor) = app.note_editor.as_ref().filter(|editor| editor.employee_id == employee_id) else {
+        return Text::new("Notes:").into();
+    };
+    let status = match &editor.status {
+        None => String::new(),
+        Some(NoteStatus::Editing) => "Editing...".to_string(),
+        Some(NoteStatus::Saved) => "Saved".to_string(),
+        Some(NoteStatus::Failed(e)) => format!("Not saved: {}", e),
+    };
+    let details = row![
+        pick_list(CategoryChoice::all(), Some(CategoryChoice(editor.category)), |choice: CategoryChoice| {
+            Message::NoteCategorySelected(choice.0)
+        }),
+        Text::new("Author:"),
+        text_input(&app.note_author(), &app.app_settings.note_author)
+            .on_input(Message::NoteAuthorChanged)
+            .width(Length::Fixed(150.0)),
+    ]
+    .spacing(5)
+    .align_items(iced::Alignment::Center);
+    column![
+        row![Text::new("Notes:"), Text::new(status).size(11)].spacing(10).align_items(iced::Alignment::Center),
+        details,
+        text_editor(&editor.content).on_action(Message::NoteEdited).height(Length::Fixed(120.0)),
+        view_note_history(editor),
+    ]
+    .spacing(3)
+    .into()
+}
+
+// Saved versions of the open note, newest first; each earlier one can be restored
+fn view_note_history(editor: &NoteEditor) -> Element<'_, Message> {
+    if editor.history.entries.is_empty() {
+        return Text::new("No saved versions yet.").size(11).into();
+    }
+    let latest = editor.history.entries.len() - 1;
+    let versions = editor.history.entries.iter().enumerate().rev().fold(Column::new().spacing(4), |versions, (index, entry)| {
+        let mut heading = entry.recorded_at_label();
+        if !entry.author.is_empty() {
+            heading.push_str(&format!(" · {}", entry.author));
+        }
+        if let Some(category) = entry.category {
+            heading.push_str(&format!(" · {}", category));
+        }
+        let snippet = match entry.text.lines().next() {
+            None => "(note cleared)".to_string(),
+            Some(line) if line.chars().count() > 60 => format!("{}...", line.chars().take(60).collect::<String>()),
+            Some(line) if entry.text.lines().nth(1).is_some() => format!("{}...", line),
+            Some(line) => line.to_string(),
+        };
+        let action: Element<'_, Message> = if index == latest {
+            Text::new("(current)").size(11).into()
+        } else {
+            Button::new(Text::new("Restore").size(11)).on_press(Message::NoteVersionRestored(index)).into()
+        };
+        versions.push(
+            row![
+                column![Text::new(heading).size(11), Text::new(snippet).size(12)].width(Length::Fill),
+                action,
+            ]
+            .spacing(5)
+            .align_items(iced::Alignment::Center),
+        )
+    });
+    column![
+        Text::new(format!("History ({} versions)", editor.history.entries.len())).size(12),
+        scrollable(versions).height(Length::Fixed(120.0)),
+    ]
+    .spacing(3)
+    .into()
+}
+
+// Accepts the dragged employee card, if there is one, at `target`
+fn drop_zone<'a>(app: &App, content: impl Into<Element<'a, Message>>, target: DropTarget) -> DropZone<'a, Message> {
+    let zone = DropZone::new(content);
+    match &app.drag {
+        Some(drag) => zone
+            .on_drop(Message::EmployeeDropped(drag.employee_id.clone(), target))
+            .on_enter(Message::DragEntered(target))
+            .on_exit(Message::DragExited(target)),
+        None => zone,
+    }
+}
+
+// Undo or Redo, naming the edit it would act on; disabled when there is none
+fn history_button<'a>(action: &str, description: Option<&str>, message: Message) -> Button<'a, Message> {
+    match description {
+        Some(description) => Button::new(Text::new(format!("{} {}", action, description))).on_press(message),
+        None => Button::new(Text::new(action.to_string())),
+    }
+}
+
+// Switches to `screen`; disabled while that screen is already shown
+fn screen_button<'a>(app: &App, label: &'a str, screen: Screen) -> Button<'a, Message> {
+    let button = Button::new(Text::new(label));
+    if app.screen == screen {
+        button
+    } else {
+        button.on_press(Message::ScreenSelected(screen))
+    }
+}
+
+// The skills catalog with who holds each skill
+fn view_skills(app: &App) -> Element<'_, Message> {
+    let add_row = row![
+        text_input("New skill", &app.skill_input)
+            .on_input(Message::SkillInputChanged)
+            .on_submit(Message::CatalogSkillAdded)
+            .width(Length::Fixed(250.0)),
+        Button::new(Text::new("Add to Catalog")).on_press(Message::CatalogSkillAdded),
+        Text::new("Role:"),
+        pick_list(app.role_names(), app.selected_role.clone(), Message::RoleSelected),
+        Button::new(Text::new("Export Skills Matrix...")).on_press(Message::ExportSkillsMatrix),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+    let role_requirement = app
+        .selected_role
+        .as_deref()
+        .and_then(|role| find_role_requirement(app.workspace.role_requirements(), role));
+
+    let mut catalog_column = Column::new().spacing(4);
+    for skill in app.workspace.skills() {
+        let holders: Vec<String> = employees_with_skill(app.workspace.employees(), &skill.name)
+            .into_iter()
+            .filter_map(|id| app.workspace.employees().iter().find(|e| e.user_id == id))
+            .map(|e| format!("{} {}", e.first_name, e.last_name))
+            .collect();
+        let holders_str = if holders.is_empty() { "nobody".to_string() } else { holders.join(", ") };
+        let mut skill_row = row![
+            Text::new(skill.name.clone()).width(Length::Fixed(200.0)),
+            Text::new(format!("{} employee(s): {}", holders.len(), holders_str)).size(12).width(Length::Fill),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center);
+        // The level the selected role requires, editable in place
+        if app.selected_role.is_some() {
+            let required = role_requirement.and_then(|role| {
+                role.skills
+                    .iter()
+                    .find(|required| required.skill.eq_ignore_ascii_case(&skill.name))
+                    .map(|required| required.level)
+            });
+            let skill_name = skill.name.clone();
+            skill_row = skill_row.push(Text::new("Required:").size(12)).push(
+                pick_list(LevelChoice::all(), Some(LevelChoice(required)), move |choice: LevelChoice| {
+                    Message::RoleRequirementChanged(skill_name.clone(), choice.0)
+                })
+                .text_size(12),
+            );
+        }
+        catalog_column = catalog_column.push(
+            skill_row.push(Button::new(Text::new("Remove")).on_press(Message::CatalogSkillRemoved(skill.name.clone()))),
+        );
+    }
+    if app.workspace.skills().is_empty() {
+        catalog_column = catalog_column.push(Text::new("The catalog is empty.").size(12));
+    }
+
+    column![
+        Text::new("Skills Catalog").size(24),
+        add_row,
+        Rule::horizontal(5),
+        scrollable(catalog_column).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+// Critical roles with their bench strength and ranked successors
+fn view_succession(app: &App) -> Element<'_, Message> {
+    let add_row = row![
+        text_input("Critical position", &app.succession_role_input)
+            .on_input(Message::SuccessionRoleInputChanged)
+            .on_submit(Message::SuccessionRoleAdded)
+            .width(Length::Fixed(250.0)),
+        Button::new(Text::new("Add Critical Role")).on_press(Message::SuccessionRoleAdded),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let mut roles_column = Column::new().spacing(15);
+    for role in &app.workspace.succession().roles {
+        roles_column = roles_column.push(view_critical_role(app, role));
+    }
+    if app.workspace.succession().roles.is_empty() {
+        roles_column = roles_column.push(Text::new("No critical roles yet.").size(12));
+    }
+
+    // Values from the last import that did not make it into the plan
+    let mut issues_column = Column::new().spacing(2);
+    if !app.succession_issues.is_empty() {
+        issues_column = issues_column.push(Text::new("Import issues:").size(14));
+        for issue in &app.succession_issues {
+            issues_column = issues_column.push(Text::new(issue.clone()).size(12));
+        }
+    }
+
+    column![
+        Text::new("Succession Planning").size(24),
+        add_row,
+        Rule::horizontal(5),
+        scrollable(column![roles_column, issues_column].spacing(10)).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+fn view_critical_role<'a>(app: &'a App, role: &'a CriticalRole) -> Element<'a, Message> {
+    let employee_name = |id: &str| {
+        app.workspace.employees()
+            .iter()
+            .find(|e| e.user_id == id)
+            .map_or(format!("(ID: {})", id), |e| format!("{} {}", e.first_name, e.last_name))
+    };
+    let incumbent = role.incumbent_id.as_deref().map_or("Vacant".to_string(), employee_name);
+    let bench = role.bench_strength();
+    let position = role.position.clone();
+
+    let header = row![
+        Text::new(role.position.clone()).size(18),
+        Text::new(format!("Incumbent: {}", incumbent)).size(12),
+        Text::new("Criticality:").size(12),
+        pick_list(Criticality::ALL.to_vec(), role.criticality, {
+            let position = position.clone();
+            move |criticality| Message::CriticalitySelected(position.clone(), criticality)
+        })
+        .placeholder("Not set")
+        .text_size(12),
+        Button::new(Text::new("Remove Role")).on_press(Message::SuccessionRoleRemoved(position.clone())),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+    let bench_text = Text::new(format!(
+        "Bench: {} ready now | {} in 1-2 years | {} in 3-5 years | {} not assessed",
+        bench.ready_now, bench.one_to_two_years, bench.three_to_five_years, bench.unassessed
+    ))
+    .size(12);
+
+    let mut successors_column = Column::new().spacing(4);
+    for (rank, successor) in role.successors.iter().enumerate() {
+        let id = successor.employee_id.clone();
+        // Notes still being typed are shown as typed
+        let development = app
+            .development_draft
+            .as_ref()
+            .filter(|draft| draft.position == role.position && draft.employee_id == id)
+            .map_or(successor.development.as_str(), |draft| draft.text.as_str());
+        let mut successor_row = row![
+            Text::new(format!("#{} {}", rank + 1, employee_name(&id))).size(13).width(Length::Fixed(200.0)),
+            pick_list(Readiness::ALL.to_vec(), successor.readiness, {
+                let (position, id) = (position.clone(), id.clone());
+                move |readiness| Message::SuccessorReadinessSelected(position.clone(), id.clone(), readiness)
+            })
+            .placeholder("Readiness")
+            .text_size(12),
+            text_input("Development", development)
+                .on_input({
+                    let (position, id) = (position.clone(), id.clone());
+                    move |development| Message::SuccessorDevelopmentChanged(position.clone(), id.clone(), development)
+                })
+                .on_submit(Message::SuccessorDevelopmentSubmitted)
+                .size(12)
+                .width(Length::Fill),
+        ]
+        .spacing(5)
+        .align_items(iced::Alignment::Center);
+        let mut up = Button::new(Text::new("Up").size(12));
+        if rank > 0 {
+            up = up.on_press(Message::SuccessorRanked(position.clone(), id.clone(), rank - 1));
+        }
+        let mut down = Button::new(Text::new("Down").size(12));
+        if rank + 1 < role.successors.len() {
+            down = down.on_press(Message::SuccessorRanked(position.clone(), id.clone(), rank + 1));
+        }
+        successor_row = successor_row
+            .push(up)
+            .push(down)
+            .push(Button::new(Text::new("x").size(12)).on_press(Message::SuccessorRemoved(position.clone(), id)));
+        successors_column = successors_column.push(successor_row);
+    }
+
+    let candidates: Vec<EmployeeChoice> = app
+        .workspace
+        .employees()
+        .iter()
+        .filter(|e| role.incumbent_id.as_deref() != Some(e.user_id.as_str()) && role.successor(&e.user_id).is_none())
+        .map(EmployeeChoice::new)
+        .collect();
+    let add_successor = pick_list(candidates, None::<EmployeeChoice>, move |choice: EmployeeChoice| {
+        Message::SuccessorAdded(position.clone(), choice.id)
+    })
+    .placeholder("Add successor...")
+    .text_size(12);
+
+    column![header, bench_text, successors_column, add_successor]
+        .spacing(5)
         .into()
 }
 
+// Review dialog for an import: what will be loaded and every row or value that will not
+fn view_import_report(pending: &PendingImport) -> Element<'_, Message> {
+    let report = &pending.outcome.report;
+    let mut report_column = Column::new().spacing(4);
+
+    if !report.rejected_rows.is_empty() {
+        report_column = report_column.push(Text::new(format!("Rejected rows ({})", report.rejected_rows.len())).size(16));
+        for rejected in &report.rejected_rows {
+            let location = match (&rejected.column, &rejected.raw_value) {
+                (Some(column), Some(value)) => format!("column '{}', value '{}'", column, value),
+                (Some(column), None) => format!("column '{}'", column),
+                _ => "whole row".to_string(),
+            };
+            report_column = report_column.push(
+                Text::new(format!("Line {}: {} - {}", rejected.line, location, rejected.reason)).size(12),
+            );
+        }
+    }
+    if !report.duplicate_ids.is_empty() {
+        report_column = report_column.push(Text::new(format!("Duplicate user IDs ({})", report.duplicate_ids.len())).size(16));
+        for duplicate in &report.duplicate_ids {
+            let lines: Vec<String> = duplicate.lines.iter().map(|line| line.to_string()).collect();
+            report_column = report_column.push(
+                Text::new(format!("{} on lines {} (only the first is kept)", duplicate.user_id, lines.join(", "))).size(12),
+            );
+        }
+    }
+    if !report.unknown_managers.is_empty() {
+        report_column = report_column.push(Text::new(format!("Unknown managers ({})", report.unknown_managers.len())).size(16));
+        for unknown in &report.unknown_managers {
+            report_column = report_column.push(
+                Text::new(format!("Line {}: {} reports to unknown manager '{}'", unknown.line, unknown.user_id, unknown.manager_id)).size(12),
+            );
+        }
+    }
+    if report.is_clean() {
+        report_column = report_column.push(Text::new("No problems found.").size(12));
+    }
+
+    column![
+        Text::new("Import Report").size(24),
+        Text::new(format!("Source: {}", pending.source.display())).size(12),
+        Text::new(format!(
+            "{} employees ready to import, {} issue(s) found.",
+            pending.outcome.employees.len(),
+            report.issue_count()
+        )),
+        row![
+            Button::new(Text::new("Accept")).on_press(Message::ImportAccepted),
+            Button::new(Text::new("Discard")).on_press(Message::ImportDiscarded),
+        ]
+        .spacing(10),
+        Rule::horizontal(5),
+        scrollable(report_column).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+// Editor for CSV column mapping profiles with a preview of the first rows of a file
+fn view_mapping_editor(app: &App) -> Element<'_, Message> {
+    let editor = &app.mapping_editor;
+    let profile = &editor.profile;
+
+    let profile_names: Vec<String> = app
+        .app_settings
+        .available_mapping_profiles()
+        .into_iter()
+        .map(|profile| profile.name)
+        .collect();
+    let selected_name = profile_names.iter().find(|name| **name == profile.name).cloned();
+
+    let header_row = row![
+        Text::new("Profile:"),
+        pick_list(profile_names, selected_name, Message::MappingProfileSelected),
+        Text::new("Name:"),
+        text_input("Profile name", &profile.name)
+            .on_input(Message::MappingProfileNameChanged)
+            .width(Length::Fixed(200.0)),
+        Button::new(Text::new("Save & Use")).on_press(Message::MappingProfileSaved),
+        Button::new(Text::new("Preview CSV...")).on_press(Message::MappingPreviewRequested),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let mut rules = Column::new().spacing(4).push(
+        row![
+            Text::new("Field").width(Length::Fixed(160.0)),
+            Text::new("Header").width(Length::Fixed(220.0)),
+            Text::new("Aliases (comma-separated)"),
+        ]
+        .spacing(10),
+    );
+    for field in EmployeeField::ALL {
+        let rule = profile.field_rule(field);
+        rules = rules.push(
+            row![
+                Text::new(field.default_header()).width(Length::Fixed(160.0)),
+                text_input(field.default_header(), &rule.header)
+                    .on_input(move |header| Message::MappingFieldHeaderChanged(field, header))
+                    .width(Length::Fixed(220.0)),
+                text_input("", &rule.aliases.join(", "))
+                    .on_input(move |aliases| Message::MappingFieldAliasesChanged(field, aliases)),
+            ]
+            .spacing(10),
+        );
+    }
+    for cycle_field in CycleField::ALL {
+        let rule = profile.cycle_rule(cycle_field);
+        rules = rules.push(
+            row![
+                Text::new(format!("{} (per cycle)", cycle_field.label())).width(Length::Fixed(160.0)),
+                text_input(cycle_field.default_header_template(), &rule.header)
+                    .on_input(move |header| Message::MappingCycleHeaderChanged(cycle_field, header))
+                    .width(Length::Fixed(220.0)),
+                text_input("", &rule.aliases.join(", "))
+                    .on_input(move |aliases| Message::MappingCycleAliasesChanged(cycle_field, aliases)),
+            ]
+            .spacing(10),
+        );
+    }
+
+    // Preview: each source column with the field it maps to, followed by the first rows
+    let mut preview_column = Column::new().spacing(4);
+    match &editor.preview {
+        Some(preview) => {
+            if let Some(path) = &editor.preview_path {
+                preview_column = preview_column.push(Text::new(format!("Preview of {}", path.display())).size(14));
+            }
+            let mut preview_row = Row::new().spacing(10);
+            for (index, header) in preview.headers.iter().enumerate() {
+                let target = profile.resolve_column(header);
+                let mut cell_column = Column::new()
+                    .spacing(2)
+                    .width(Length::Fixed(130.0))
+                    .push(Text::new(header.clone()).size(13))
+                    .push(Text::new(format!("-> {}", target.describe())).size(11));
+                for preview_row_values in &preview.rows {
+                    let value = preview_row_values.get(index).cloned().unwrap_or_default();
+                    cell_column = cell_column.push(Text::new(value).size(11));
+                }
+                preview_row = preview_row.push(cell_column);
+            }
+            preview_column = preview_column.push(scrollable(preview_row).direction(
+                scrollable::Direction::Horizontal(scrollable::Properties::default()),
+            ));
+        }
+        None => {
+            preview_column = preview_column.push(Text::new("Pick a CSV file to preview how its columns are mapped.").size(12));
+        }
+    }
+
+    scrollable(
+        column![
+            Text::new("CSV Column Mapping").size(24),
+            header_row,
+            Rule::horizontal(5),
+            rules,
+            Rule::horizontal(5),
+            preview_column,
+        ]
+        .spacing(10)
+        .padding(10),
+    )
+    .into()
+}
+
 // New function to render the 9-Box Grid
-fn view_9box_grid(app: &App) -> Element<Message> {
-    let box_ids_labels = [
-        [("1A", "High Perf / High Pot"), ("1B", "High Perf / Med Pot"), ("1C", "High Perf / Low Pot")],
-        [("2A", "Med Perf / High Pot"),  ("2B", "Med Perf / Med Pot"),  ("2C", "Med Perf / Low Pot")],
-        [("3A", "Low Perf / High Pot"),  ("3B", "Low Perf / Med Pot"),  ("3C", "Low Perf / Low Pot")],
-    ];
+fn view_9box_grid(app: &App) -> Element<'_, Message> {
+    let template = &app.workspace.settings().grid_template;
+    let grid_column = view_grid(
+        app,
+        template,
+        app.workspace.grid_state(),
+        |position| app.workspace.settings().label_scheme.label(position, template),
+        Message::BoxClicked,
+        DropTarget::GridBox,
+    );
+
+    // Add a slider for scaling the view
+    let scale_slider = iced::widget::slider(0.5..=2.0, app.view_scale, Message::ScaleChanged)
+        .step(0.1);
+
+    let template_names: Vec<String> = GridTemplate::built_in().into_iter().map(|template| template.name).collect();
+    let scheme_names: Vec<String> = LabelScheme::built_in().into_iter().map(|scheme| scheme.name).collect();
+    let template_picker = row![
+        Text::new("Template:").size(12),
+        pick_list(template_names, Some(template.name.clone()), Message::GridTemplateSelected),
+        Button::new(Text::new("Load Template...")).on_press(Message::LoadGridTemplate),
+        Text::new("Labels:").size(12),
+        pick_list(
+            scheme_names,
+            Some(app.workspace.settings().label_scheme.name.clone()),
+            Message::LabelSchemeSelected
+        ),
+        Text::new("Place from cycle:").size(12),
+        pick_list(nine_box_cycles(app.workspace.employees()), app.placement_cycle(), Message::PlacementCycleSelected),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    // Who the last seeding could not place, so the data can be fixed at the source
+    let mut seed_report_column = Column::new().spacing(2).align_items(iced::Alignment::Center);
+    if let Some(report) = app.seed_report.as_ref().filter(|report| report.unplaced_count() > 0) {
+        if !report.missing.is_empty() {
+            seed_report_column = seed_report_column.push(
+                Text::new(format!("{} employee(s) have no 9-box value for {}.", report.missing.len(), report.cycle)).size(12),
+            );
+        }
+        for unrecognized in &report.unrecognized {
+            let name = app
+                .workspace
+                .employees()
+                .iter()
+                .find(|e| e.user_id == unrecognized.user_id)
+                .map_or(unrecognized.user_id.clone(), |e| format!("{} {}", e.first_name, e.last_name));
+            seed_report_column = seed_report_column.push(
+                Text::new(format!("{}: '{}' is not a box on this grid.", name, unrecognized.value)).size(12),
+            );
+        }
+    }
+
+    column![
+        Text::new(template.name.clone()).size(24),
+        template_picker,
+        scale_slider, // Add slider to control scale
+        Text::new(format!("Zoom: {:.1}x", app.view_scale)).size(12),
+        grid_column,
+        seed_report_column,
+        Text::new(format!("Selected Employee: {:?}", app.selected_employee_id)).size(12),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center)
+    .into()
+}
+
+// Retention review: impact of loss × risk of loss, placed from each employee's fields
+fn view_risk_matrix(app: &App) -> Element<'_, Message> {
+    let template = risk_matrix_template();
+    let grid_state = risk_matrix(app.workspace.employees());
+    let grid_column = view_grid(
+        app,
+        &template,
+        &grid_state,
+        |position| template.box_label(position),
+        Message::RiskBoxClicked,
+        DropTarget::RiskBox,
+    );
+    let placed: usize = grid_state.assignments.values().map(Vec::len).sum();
+
+    column![
+        Text::new(template.name.clone()).size(24),
+        Text::new("Select an employee, then click a box to set their impact and risk.").size(12),
+        grid_column,
+        Text::new(format!("{} of {} employees assessed.", placed, app.workspace.employees().len())).size(12),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center)
+    .into()
+}
+
+// The reporting tree from each employee's manager ID, every node tinted with the color of
+// the employee's current grid box. Pressing a node selects the employee.
+fn view_org_chart(app: &App) -> Element<'_, Message> {
+    let template = &app.workspace.settings().grid_template;
+    let org = OrgTree::build(app.workspace.employees());
+    let indent = 30.0 * app.view_scale;
+
+    let node = |employee_id: &str| {
+        let employee = app.workspace.employees().iter().find(|e| e.user_id == employee_id);
+        let name = employee.map_or(format!("(ID: {})", employee_id), |e| format!("{} {}", e.first_name, e.last_name));
+        let position_title = employee.map(|e| e.current_position.clone()).unwrap_or_default();
+        let placement = app.workspace.grid_state().position_of(employee_id);
+        let box_label = placement.map_or("Not placed".to_string(), |position| {
+            app.workspace.settings().label_scheme.label(position, template)
+        });
+        let reports = org.span_of_control(employee_id);
+        let selected = app.selected_employee_id.as_deref() == Some(employee_id);
+
+        let mut details = format!("{} | {}", position_title, box_label);
+        if reports > 0 {
+            details.push_str(&format!(" | {} direct report(s)", reports));
+        }
+        let mut node_container = Container::new(
+            column![
+                Text::new(if selected { format!("> {}", name) } else { name }).size(13),
+                Text::new(details).size(10),
+            ]
+            .spacing(2),
+        )
+        .width(Length::Fixed(260.0 * app.view_scale))
+        .padding(5);
+        let color = placement
+            .and_then(|position| template.grid_box(position))
+            .and_then(|grid_box| grid_box.color.as_deref())
+            .and_then(parse_hex_color);
+        if let Some(color) = color {
+            node_container = node_container.style(tinted_box(color));
+        }
+        Button::new(node_container)
+            .on_press(Message::EmployeeSelected(employee_id.to_string()))
+            .padding(0)
+    };
+
+    // Depth-first from each root so that reports sit directly below their manager;
+    // every employee is drawn once even if the data loops.
+    let mut chart_column = Column::new().spacing(4);
+    let mut drawn: HashSet<&str> = HashSet::new();
+    let mut stack: Vec<(&str, usize)> = org.roots().iter().rev().map(|root| (root.as_str(), 0)).collect();
+    while let Some((employee_id, depth)) = stack.pop() {
+        if !drawn.insert(employee_id) {
+            continue;
+        }
+        chart_column = chart_column.push(
+            row![iced::widget::Space::with_width(Length::Fixed(indent * depth as f32)), node(employee_id)]
+                .align_items(iced::Alignment::Center),
+        );
+        stack.extend(org.direct_reports(employee_id).iter().rev().map(|report| (report.as_str(), depth + 1)));
+    }
+
+    // Employees on or below a reporting cycle have no root to hang from
+    let unreachable: Vec<&Employee> =
+        app.workspace.employees().iter().filter(|e| !drawn.contains(e.user_id.as_str())).collect();
+    if !unreachable.is_empty() {
+        chart_column = chart_column.push(Text::new("Not in the hierarchy (reporting cycle):").size(14));
+        for employee in unreachable {
+            chart_column = chart_column.push(node(&employee.user_id));
+        }
+    }
+
+    // Which color stands for which box
+    let mut legend = Row::new().spacing(10).align_items(iced::Alignment::Center);
+    for position in template.positions() {
+        let Some(color) = template.grid_box(position).and_then(|grid_box| grid_box.color.as_deref()).and_then(parse_hex_color)
+        else {
+            continue;
+        };
+        legend = legend.push(
+            Container::new(Text::new(app.workspace.settings().label_scheme.label(position, template)).size(10))
+                .padding(3)
+                .style(tinted_box(color)),
+        );
+    }
+
+    column![
+        Text::new("Org Chart").size(24),
+        Text::new(format!(
+            "{} employees in {} layer(s). Colors show each employee's box on the {}.",
+            app.workspace.employees().len(),
+            org.layer_count(),
+            template.name
+        ))
+        .size(12),
+        scrollable(legend).direction(scrollable::Direction::Horizontal(scrollable::Properties::default())),
+        scrollable(chart_column).height(Length::Fill),
+    ]
+    .spacing(10)
+    .padding(10)
+    .into()
+}
+
+// Draws `template` with the employees placed in `grid_state`, headed by the axis levels.
+// Pressing a box sends `on_press` for its position.
+fn view_grid<'a>(
+    app: &'a App,
+    template: &GridTemplate,
+    grid_state: &GridState,
+    box_label: impl Fn(GridPosition) -> String,
+    on_press: impl Fn(GridPosition) -> Message,
+    drop_target: impl Fn(GridPosition) -> DropTarget,
+) -> Column<'a, Message> {
+    let box_width = 150.0 * app.view_scale;
+    let box_height = 100.0 * app.view_scale;
+    let row_header_width = 90.0;
 
     let mut grid_column = Column::new().spacing(5).align_items(iced::Alignment::Center);
 
-    for row_of_boxes in box_ids_labels.iter() {
-        let mut grid_row_element = Row::new().spacing(5).align_items(iced::Alignment::Start);
-        for (box_id, box_label) in row_of_boxes.iter() {
-            let mut employee_names_in_box = Vec::new();
-            if let Some(employee_ids) = app.grid_state.assignments.get(*box_id) {
+    // Column headings: the column axis levels, highest first
+    let axes = format!("{} ↓ / {} →", template.rows.name, template.columns.name);
+    let mut header_row = Row::new()
+        .spacing(5)
+        .push(Text::new(axes).size(11).width(Length::Fixed(row_header_width)));
+    for level in &template.columns.levels {
+        header_row = header_row.push(Text::new(level.clone()).size(12).width(Length::Fixed(box_width)));
+    }
+    grid_column = grid_column.push(header_row);
+
+    // One row per row-axis level, highest level at the top
+    let positions = template.positions();
+    for (row_of_boxes, row_level) in positions.chunks(template.column_count() as usize).zip(&template.rows.levels) {
+        let mut grid_row_element = Row::new()
+            .spacing(5)
+            .align_items(iced::Alignment::Center)
+            .push(Text::new(row_level.clone()).size(12).width(Length::Fixed(row_header_width)));
+        for box_id in row_of_boxes {
+            let grid_box = template.grid_box(*box_id);
+            let target = drop_target(*box_id);
+            // Each placed employee is a card that can be dragged to another box or back to the list
+            let mut employee_cards_in_box: Vec<Element<'a, Message>> = Vec::new();
+            if let Some(employee_ids) = grid_state.assignments.get(box_id) {
                 for emp_id in employee_ids {
-                    if let Some(employee) = app.employees.iter().find(|e| e.user_id == *emp_id) {
-                        employee_names_in_box.push(format!("- {} {}", employee.first_name, employee.last_name));
+                    if let Some(employee) = app.workspace.employees().iter().find(|e| e.user_id == *emp_id) {
+                        let card = EmployeeCard {
+                            employee,
+                            department_color: app.department_color(employee).and_then(parse_hex_color),
+                            has_note: app.has_note(employee),
+                            scale: app.view_scale,
+                        };
+                        let card = Draggable::new(card, Message::DragStarted(emp_id.clone()))
+                            .on_release(Message::DragEnded)
+                            .on_click(Message::EmployeeSelected(emp_id.clone()));
+                        employee_cards_in_box.push(card.into());
                     } else {
-                        employee_names_in_box.push(format!("- (ID: {})", emp_id)); // Fallback
+                        employee_cards_in_box.push(Text::new(format!("- (ID: {})", emp_id)).size(11).into()); // Fallback
                     }
                 }
             }
 
+            let box_label = box_label(*box_id);
+            let label = match grid_box.and_then(|grid_box| grid_box.icon.as_deref()) {
+                Some(icon) => format!("{} {}", icon, box_label),
+                None => box_label,
+            };
             let mut box_content_column = Column::new()
-                .push(Text::new(*box_label).size(14)) // Use descriptive label
+                .push(Text::new(label).size(14)) // Use descriptive label
+                .width(Length::Fill)
                 .spacing(3)
                 .align_items(iced::Alignment::Start); // Align text to the start
-
-            for name in &employee_names_in_box { // Changed to iterate by reference
-                box_content_column = box_content_column.push(Text::new(name.clone()).size(11)); // Added clone() as name is now &String
+            if let Some(description) = grid_box.map(|grid_box| grid_box.description.as_str()).filter(|d| !d.is_empty()) {
+                box_content_column = box_content_column.push(Text::new(description.to_string()).size(10));
             }
-            
+
             // Ensure there's always some content for consistent height if no employees
-            if employee_names_in_box.is_empty() { // This check is now valid
+            if employee_cards_in_box.is_empty() {
                  box_content_column = box_content_column.push(Text::new(" ").size(11)); // Add a space to ensure height
             }
+            for card in employee_cards_in_box {
+                box_content_column = box_content_column.push(card);
+            }
 
-            let grid_box_button = Button::new(
-                Container::new(scrollable(box_content_column)) // Make content scrollable if it overflows
-                    .width(Length::Fixed(150.0 * app.view_scale)) // Fixed size for boxes, scaled
-                    .height(Length::Fixed(100.0 * app.view_scale))
-                    .padding(5)
-                    .center_x() // Center content horizontally
-                    // .style(theme::Container::Bordered) // Example for styling, needs theme setup
-            )
-            .on_press(Message::BoxClicked(box_id.to_string()))
-            .width(Length::Fixed(150.0 * app.view_scale)) // Scaled button width
-            .height(Length::Fixed(100.0 * app.view_scale)); // Scaled button height
+            let mut box_container = Container::new(scrollable(box_content_column)) // Make content scrollable if it overflows
+                .width(Length::Fixed(box_width)) // Fixed size for boxes, scaled
+                .height(Length::Fixed(box_height))
+                .padding(5)
+                .center_x(); // Center content horizontally
+            let box_color = grid_box.and_then(|grid_box| grid_box.color.as_deref()).and_then(parse_hex_color);
+            if app.drag.as_ref().is_some_and(|drag| drag.hovered == Some(target)) {
+                box_container = box_container.style(drop_target_box(box_color.unwrap_or(DROP_HIGHLIGHT)));
+            } else if app.last_drop == Some(target) {
+                box_container = box_container.style(tinted_box(DROP_HIGHLIGHT));
+            } else if let Some(color) = box_color {
+                box_container = box_container.style(tinted_box(color));
+            }
+
+            let grid_box_button = Button::new(box_container)
+                .on_press(on_press(*box_id))
+                .padding(0)
+                .width(Length::Fixed(box_width)) // Scaled button width
+                .height(Length::Fixed(box_height)); // Scaled button height
             
-            grid_row_element = grid_row_element.push(grid_box_button);
+            grid_row_element = grid_row_element.push(drop_zone(app, grid_box_button, target));
         }
         grid_column = grid_column.push(grid_row_element);
     }
-    
-    // Add a slider for scaling the view
-    let scale_slider = iced::widget::slider(0.5..=2.0, app.view_scale, Message::ScaleChanged)
-        .step(0.1);
-
-    column![
-        Text::new("9-Box Grid").size(24),
-        scale_slider, // Add slider to control scale
-        Text::new(format!("Zoom: {:.1}x", app.view_scale)).size(12),
-        grid_column,
-        Text::new(format!("Selected Employee: {:?}", app.selected_employee_id)).size(12),
-    ]
-    .spacing(10)
-    .align_items(iced::Alignment::Center)
-    .into()
+    grid_column
 }
@@ -1 +1,519 @@
-// Custom widgets will be defined here.
\ No newline at end of file
+// Custom widgets and the styles the views share.
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::{tree, Operation, Tree};
+use iced::advanced::{mouse, overlay, renderer, Clipboard, Shell, Widget};
+use box_planner_core::models::Employee;
+use iced::widget::{column, container, row, Container, Row, Text};
+use iced::{event, Border, Color, Element, Event, Length, Rectangle, Size, Vector};
+
+/// Outline color of a drop target under a dragged card, and of the box that just took a drop.
+pub const DROP_HIGHLIGHT: Color = Color::from_rgb(0.16, 0.45, 0.85);
+
+// Card colors: the body, its outline, skill tags, and muted text such as the empty note marker
+const CARD_BACKGROUND: Color = Color::from_rgb(0.99, 0.99, 0.99);
+const CARD_OUTLINE: Color = Color::from_rgb(0.8, 0.8, 0.8);
+const TAG_BACKGROUND: Color = Color::from_rgb(0.87, 0.91, 0.97);
+const MUTED_TEXT: Color = Color::from_rgb(0.6, 0.6, 0.6);
+
+// How far the pointer has to move while pressed before a press becomes a drag
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Parses a hex color such as `"#597864"` or `"597864"`.
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+    Some(Color::from_rgb8(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+/// A box outlined in `color` with a light tint of it behind its content.
+pub fn tinted_box(color: Color) -> container::Appearance {
+    container::Appearance {
+        background: Some(Color { a: 0.15, ..color }.into()),
+        border: Border {
+            color,
+            width: 2.0,
+            radius: 4.0.into(),
+        },
+        ..container::Appearance::default()
+    }
+}
+
+/// A box under a dragged card: a thick outline and a stronger tint than `tinted_box`.
+pub fn drop_target_box(color: Color) -> container::Appearance {
+    container::Appearance {
+        background: Some(Color { a: 0.35, ..color }.into()),
+        border: Border {
+            color,
+            width: 4.0,
+            radius: 4.0.into(),
+        },
+        ..container::Appearance::default()
+    }
+}
+
+/// Content that can be picked up with the left mouse button. Moving the pointer a few pixels
+/// while pressed starts a drag; releasing without moving is a click.
+pub struct Draggable<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_drag: Message,
+    on_release: Option<Message>,
+    on_click: Option<Message>,
+}
+
+impl<'a, Message, Theme, Renderer> Draggable<'a, Message, Theme, Renderer> {
+    /// Wraps `content`, publishing `on_drag` when a drag starts.
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>, on_drag: Message) -> Self {
+        Draggable {
+            content: content.into(),
+            on_drag,
+            on_release: None,
+            on_click: None,
+        }
+    }
+
+    /// The message to publish when a drag ends, wherever the pointer is.
+    pub fn on_release(mut self, message: Message) -> Self {
+        self.on_release = Some(message);
+        self
+    }
+
+    /// The message to publish when the content is clicked rather than dragged.
+    pub fn on_click(mut self, message: Message) -> Self {
+        self.on_click = Some(message);
+        self
+    }
+}
+
+#[derive(Default)]
+struct DragState {
+    pressed_at: Option<iced::Point>,
+    dragging: bool,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Draggable<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<DragState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(DragState::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, renderer: &Renderer, operation: &mut dyn Operation<Message>) {
+        self.content.as_widget().operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        let state = tree.state.downcast_mut::<DragState>();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(layout.bounds()) {
+                    state.pressed_at = Some(position);
+                    return event::Status::Captured; // Keeps an enclosing button from taking the press
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                if let Some(pressed_at) = state.pressed_at.filter(|_| !state.dragging) {
+                    // Inside a scrollable the cursor becomes unavailable once it leaves the viewport
+                    let moved = cursor.position().is_none_or(|position| position.distance(pressed_at) > DRAG_THRESHOLD);
+                    if moved {
+                        state.dragging = true;
+                        shell.publish(self.on_drag.clone());
+                    }
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                let was_pressed = state.pressed_at.take().is_some();
+                if std::mem::take(&mut state.dragging) {
+                    if let Some(message) = &self.on_release {
+                        shell.publish(message.clone());
+                    }
+                } else if was_pressed && cursor.is_over(layout.bounds()) {
+                    if let Some(message) = &self.on_click {
+                        shell.publish(message.clone());
+                        return event::Status::Captured;
+                    }
+                }
+            }
+            _ => {}
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if tree.state.downcast_ref::<DragState>().dragging {
+            return mouse::Interaction::Grabbing;
+        }
+        match self.content.as_widget().mouse_interaction(&tree.children[0], layout, cursor, viewport, renderer) {
+            mouse::Interaction::Idle if cursor.is_over(layout.bounds()) => mouse::Interaction::Grab,
+            interaction => interaction,
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(&tree.children[0], renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Draggable<'a, Message, Theme, Renderer>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(draggable: Draggable<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(draggable)
+    }
+}
+
+/// Content that accepts whatever is being dragged. Without an `on_drop` message nothing is
+/// being dragged and the zone stays inert.
+pub struct DropZone<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_drop: Option<Message>,
+    on_enter: Option<Message>,
+    on_exit: Option<Message>,
+}
+
+impl<'a, Message, Theme, Renderer> DropZone<'a, Message, Theme, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        DropZone {
+            content: content.into(),
+            on_drop: None,
+            on_enter: None,
+            on_exit: None,
+        }
+    }
+
+    /// The message to publish when the drag is released over the zone.
+    pub fn on_drop(mut self, message: Message) -> Self {
+        self.on_drop = Some(message);
+        self
+    }
+
+    /// The message to publish when a drag moves over the zone.
+    pub fn on_enter(mut self, message: Message) -> Self {
+        self.on_enter = Some(message);
+        self
+    }
+
+    /// The message to publish when a drag moves off the zone.
+    pub fn on_exit(mut self, message: Message) -> Self {
+        self.on_exit = Some(message);
+        self
+    }
+}
+
+#[derive(Default)]
+struct DropZoneState {
+    hovered: bool,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer> for DropZone<'a, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<DropZoneState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(DropZoneState::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, renderer: &Renderer, operation: &mut dyn Operation<Message>) {
+        self.content.as_widget().operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+        let state = tree.state.downcast_mut::<DropZoneState>();
+        let Some(on_drop) = &self.on_drop else {
+            state.hovered = false;
+            return status;
+        };
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let was_hovered = state.hovered;
+                state.hovered = cursor.is_over(layout.bounds());
+                let message = match (was_hovered, state.hovered) {
+                    (false, true) => &self.on_enter,
+                    (true, false) => &self.on_exit,
+                    _ => &None,
+                };
+                if let Some(message) = message {
+                    shell.publish(message.clone());
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.hovered = false;
+                if cursor.is_over(layout.bounds()) {
+                    shell.publish(on_drop.clone());
+                    return event::Status::Captured;
+                }
+            }
+            _ => {}
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(&tree.children[0], layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(&tree.children[0], renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<DropZone<'a, Message, Theme, Renderer>> for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + renderer::Renderer,
+{
+    fn from(zone: DropZone<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(zone)
+    }
+}
+
+/// An employee as a card in a grid box: name with a note marker, position and latest PR score,
+/// the first few skill tags and, when set, attrition risk and impact of loss. A stripe down the
+/// left edge shows the department color.
+pub struct EmployeeCard<'a> {
+    pub employee: &'a Employee,
+    pub department_color: Option<Color>,
+    pub has_note: bool,
+    pub scale: f32, // The grid's zoom; text sizes follow it
+}
+
+impl<'a> EmployeeCard<'a> {
+    const MAX_TAGS: usize = 3;
+
+    fn text_size(&self, base: f32) -> f32 {
+        (base * self.scale).max(8.0)
+    }
+}
+
+impl<'a, Message: 'a> From<EmployeeCard<'a>> for Element<'a, Message> {
+    fn from(card: EmployeeCard<'a>) -> Self {
+        let employee = card.employee;
+        let note_marker = if card.has_note {
+            Text::new("✎ Note").size(card.text_size(9.0))
+        } else {
+            Text::new("✎").size(card.text_size(9.0)).style(MUTED_TEXT)
+        };
+        let name = row![
+            Text::new(format!("{} {}", employee.first_name, employee.last_name))
+                .size(card.text_size(12.0))
+                .width(Length::Fill),
+            note_marker,
+        ]
+        .spacing(4);
+
+        let mut details = vec![employee.current_position.clone()];
+        if let Some((_, rating)) = employee.latest_rating() {
+            details.push(format!("PR {}", rating));
+        }
+        details.retain(|detail| !detail.trim().is_empty());
+        let mut body = column![name, Text::new(details.join(" · ")).size(card.text_size(10.0))].spacing(2);
+
+        if !employee.skills.is_empty() {
+            let mut tags = Row::new().spacing(3);
+            for skill in employee.skills.iter().take(EmployeeCard::MAX_TAGS) {
+                tags = tags.push(
+                    Container::new(Text::new(skill.clone()).size(card.text_size(9.0)))
+                        .padding([1, 4])
+                        .style(tag_box()),
+                );
+            }
+            if employee.skills.len() > EmployeeCard::MAX_TAGS {
+                let more = employee.skills.len() - EmployeeCard::MAX_TAGS;
+                tags = tags.push(Text::new(format!("+{}", more)).size(card.text_size(9.0)).style(MUTED_TEXT));
+            }
+            body = body.push(tags);
+        }
+
+        let mut retention = Row::new().spacing(6);
+        if let Some(risk) = employee.attrition_risk {
+            let color = parse_hex_color(risk.color()).unwrap_or(MUTED_TEXT);
+            retention = retention.push(Text::new(format!("⚠ {} risk", risk.label())).size(card.text_size(9.0)).style(color));
+        }
+        if let Some(impact) = employee.impact_of_loss {
+            let color = parse_hex_color(impact.color()).unwrap_or(MUTED_TEXT);
+            retention = retention.push(Text::new(format!("◆ {} impact", impact.label())).size(card.text_size(9.0)).style(color));
+        }
+        if employee.attrition_risk.is_some() || employee.impact_of_loss.is_some() {
+            body = body.push(retention);
+        }
+
+        // The stripe is the outer box's background, left showing by its left padding
+        let body = Container::new(body).width(Length::Fill).padding([3, 5]).style(card_body());
+        Container::new(body)
+            .width(Length::Fill)
+            .padding([0, 0, 0, 4])
+            .style(card_stripe(card.department_color.unwrap_or(CARD_OUTLINE)))
+            .into()
+    }
+}
+
+fn card_stripe(color: Color) -> container::Appearance {
+    container::Appearance {
+        background: Some(color.into()),
+        border: Border {
+            color: CARD_OUTLINE,
+            width: 1.0,
+            radius: 3.0.into(),
+        },
+        ..container::Appearance::default()
+    }
+}
+
+fn card_body() -> container::Appearance {
+    container::Appearance {
+        background: Some(CARD_BACKGROUND.into()),
+        ..container::Appearance::default()
+    }
+}
+
+fn tag_box() -> container::Appearance {
+    container::Appearance {
+        background: Some(TAG_BACKGROUND.into()),
+        border: Border {
+            radius: 6.0.into(),
+            ..Border::default()
+        },
+        ..container::Appearance::default()
+    }
+}